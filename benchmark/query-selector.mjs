@@ -0,0 +1,55 @@
+// Times querySelectorAll() on a deep tree, where every element has a long
+// chain of ancestors to check. The tree is queried both in a document, where
+// the id/class/tag index can provide the candidates, and in a fragment,
+// whose elements aren't in any index, so the whole tree is walked.
+//
+//     yarn build && yarn bench
+import { Html5EverDom } from "../index.js";
+
+const DEPTH = 200;
+const ITERATIONS = 20;
+
+const html =
+    "<div class=level><span>a</span><span>b</span>".repeat(DEPTH) +
+    "<em id=deepest class=rare></em>" +
+    "</div>".repeat(DEPTH);
+
+const { document } = new Html5EverDom("<!DOCTYPE html>" + html).window;
+const fragment = document.createDocumentFragment(html);
+
+const cases = [
+    // most elements are candidates
+    "span",
+    // no element matches the ancestor part
+    "section span",
+    "article div span",
+    // the ancestor part matches almost everywhere
+    "div span",
+    ".level > span",
+    // few candidates
+    "#deepest",
+    "div .rare",
+    "section .rare",
+];
+
+for (const [name, root] of [
+    ["document", document],
+    ["fragment", fragment],
+]) {
+    console.log(name);
+
+    for (const selectors of cases) {
+        const count = root.querySelectorAll(selectors).length;
+
+        const start = process.hrtime.bigint();
+        for (let i = 0; i < ITERATIONS; i++) {
+            root.querySelectorAll(selectors);
+        }
+        const elapsed = Number(process.hrtime.bigint() - start) / 1e6;
+
+        console.log(
+            `    ${selectors.padEnd(20)} ${String(count).padStart(4)} matches ` +
+                `${(elapsed / ITERATIONS).toFixed(2).padStart(9)} ms/op`,
+        );
+    }
+}
@@ -4,7 +4,7 @@ pub fn to_css_kebab_case<T: AsRef<str>>(input: T) -> String {
     let result_length = input
         .chars()
         .fold(0, |acc, c| acc + usize::from(c.is_uppercase()) + 1)
-        + input.starts_with("webkit").then_some(1).unwrap_or_default();
+        + usize::from(input.starts_with("webkit"));
 
     let mut result = String::with_capacity(result_length);
 
@@ -24,6 +24,46 @@ pub fn to_css_kebab_case<T: AsRef<str>>(input: T) -> String {
     result
 }
 
+/// Converts `fooBar` to `foo-bar`, without the vendor prefix handling of
+/// `to_css_kebab_case`.
+pub fn to_kebab_case<T: AsRef<str>>(input: T) -> String {
+    let input = input.as_ref();
+
+    let mut result = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('-');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Converts `foo-bar` to `fooBar`, the reverse of `to_kebab_case`. Only
+/// dashes followed by an ASCII lowercase letter are removed.
+pub fn to_camel_case<T: AsRef<str>>(input: T) -> String {
+    let input = input.as_ref();
+
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(next) if c == '-' && next.is_ascii_lowercase() => {
+                result.push(next.to_ascii_uppercase());
+                chars.next();
+            },
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -36,4 +76,21 @@ mod test {
         assert_eq!(to_css_kebab_case("fooBarBazQux"), "foo-bar-baz-qux");
         assert_eq!(to_css_kebab_case("webkitFooBar"), "-webkit-foo-bar");
     }
+
+    #[test]
+    fn test_to_kebab_case_without_prefix() {
+        assert_eq!(to_kebab_case("fooBar"), "foo-bar");
+        assert_eq!(to_kebab_case("webkitFooBar"), "webkit-foo-bar");
+        assert_eq!(to_kebab_case("foo-bar"), "foo-bar");
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("foo"), "foo");
+        assert_eq!(to_camel_case("foo-bar"), "fooBar");
+        assert_eq!(to_camel_case("foo-bar-baz"), "fooBarBaz");
+        assert_eq!(to_camel_case("foo-1"), "foo-1");
+        assert_eq!(to_camel_case("foo-"), "foo-");
+        assert_eq!(to_camel_case("foo--bar"), "foo-Bar");
+    }
 }
@@ -2,7 +2,10 @@ use itertools::multiunzip;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
-use shared::node_type::NODE_TYPE_MAP;
+use shared::{
+    document_position::DOCUMENT_POSITION_MAP,
+    node_type::NODE_TYPE_MAP,
+};
 use syn::{
     self,
     parse::Parser,
@@ -62,8 +65,11 @@ pub fn create_node(
 
     let name = &ast.ident;
 
-    let node_types: Vec<_> = {
-        let mut list = NODE_TYPE_MAP.iter().collect::<Vec<_>>();
+    let constants: Vec<_> = {
+        let mut list = NODE_TYPE_MAP
+            .iter()
+            .chain(DOCUMENT_POSITION_MAP.iter())
+            .collect::<Vec<_>>();
         list.sort_by(|a, b| a.0.cmp(b.0));
         list
     }
@@ -82,72 +88,68 @@ pub fn create_node(
 
     let has_children_impl = match features.has_children {
         true => quote!(
-            #[napi(getter)]
-            pub fn get_child_nodes(&self) -> Vec<crate::ChildNode> {
-                self.as_node().shallow_child_nodes_iter().collect()
-            }
-
-            #[napi(getter)]
-            pub fn get_children(
-                &self
-            ) -> Vec<napi::bindgen_prelude::Reference<crate::Element>>
-            {
-                self.as_node().shallow_child_nodes_iter().collect()
-            }
-
-            #[napi]
-            pub fn append(
-                &self,
-                child_node_or_text: napi::Either<crate::ChildNode, String>,
-            ) -> napi::Result<()> {
-                self.as_node().append(self.env, child_node_or_text)
+            #[napi(getter, ts_return_type = "NodeList")]
+            pub fn get_child_nodes(&self) -> napi::Result<napi::JsObject> {
+                crate::NodeList::create(self.env, self.into(), None)
             }
 
-            #[napi]
-            pub fn prepend(
-                &self,
-                child_node_or_text: napi::Either<crate::ChildNode, String>,
-            ) -> napi::Result<()> {
-                self.as_node().prepend(self.env, child_node_or_text)
+            #[napi(getter, ts_return_type = "HTMLCollection")]
+            pub fn get_children(&self) -> napi::Result<napi::JsObject> {
+                crate::HtmlCollection::create(
+                    self.env,
+                    self.into(),
+                    crate::HtmlCollectionFilter::Children,
+                )
             }
 
             #[napi(
-                ts_generic_types = "T extends ChildNode",
+                ts_generic_types = "T extends ChildNode | DocumentFragment",
                 ts_args_type = "child: T",
                 ts_return_type = "T"
             )]
             pub fn append_child(
                 &self,
-                child: crate::ChildNode,
-            ) -> napi::Result<crate::ChildNode> {
-                self.as_node().insert_node(
-                    self.env,
-                    child.clone(),
-                    &crate::InsertPosition::Append,
-                )?;
+                child: crate::Node,
+            ) -> napi::Result<crate::Node> {
+                self.as_node().append_child(self.env, &child)?;
 
                 Ok(child)
             }
 
             #[napi(
-                ts_generic_types = "T extends ChildNode",
+                ts_generic_types = "T extends ChildNode | DocumentFragment",
                 ts_args_type = "new_node: T, reference_node: ChildNode",
                 ts_return_type = "T"
             )]
             pub fn insert_before(
                 &self,
-                new_node: crate::ChildNode,
+                new_node: crate::Node,
                 reference_node: crate::ChildNode,
-            ) -> napi::Result<crate::ChildNode> {
+            ) -> napi::Result<crate::Node> {
                 self.as_node().insert_before(
                     self.env,
-                    new_node.clone(),
-                    &reference_node.into(),
+                    &new_node,
+                    &reference_node,
                 )?;
 
                 Ok(new_node)
             }
 
+            #[napi(
+                ts_generic_types = "T extends ChildNode",
+                ts_args_type = "node: ChildNode | DocumentFragment, child: T",
+                ts_return_type = "T"
+            )]
+            pub fn replace_child(
+                &self,
+                node: crate::Node,
+                child: crate::ChildNode,
+            ) -> napi::Result<crate::ChildNode> {
+                self.as_node().replace_child(self.env, &node, &child)?;
+
+                Ok(child)
+            }
+
             #[napi(
                 ts_generic_types = "T extends ChildNode",
                 ts_args_type = "child: T",
@@ -157,7 +159,7 @@ pub fn create_node(
                 &self,
                 child: crate::ChildNode,
             ) -> napi::Result<crate::ChildNode> {
-                self.as_node().remove_node(&child)?;
+                self.as_node().remove_child(self.env, &child)?;
 
                 Ok(child)
             }
@@ -166,76 +168,54 @@ pub fn create_node(
             pub fn get_element_by_id(
                 &self,
                 id: String,
-            ) -> Option<napi::bindgen_prelude::Reference<crate::Element>>
-            {
-                self.as_node().deep_child_nodes_iter().find(
-                    |e: &napi::bindgen_prelude::Reference<crate::Element>| {
-                        e.get_id() == id
-                    },
-                )
+            ) -> napi::Result<
+                Option<napi::bindgen_prelude::Reference<crate::Element>>,
+            > {
+                self.as_node().get_element_by_id(id)
             }
 
-            #[napi]
+            #[napi(ts_return_type = "HTMLCollection")]
             pub fn get_elements_by_class_name(
                 &self,
                 class_name: String,
-            ) -> Vec<napi::bindgen_prelude::Reference<crate::Element>>
-            {
-                self.as_node()
-                    .deep_child_nodes_iter()
-                    .filter(
-                        |e: &napi::bindgen_prelude::Reference<
-                            crate::Element,
-                        >| {
-                            match e.get_attribute("class".to_string()) {
-                                Some(class) => class,
-                                None => return false,
-                            }
-                            .split_ascii_whitespace()
-                            .any(|c| c == class_name)
-                        },
-                    )
-                    .collect()
+            ) -> napi::Result<napi::JsObject> {
+                crate::HtmlCollection::create(
+                    self.env,
+                    self.into(),
+                    crate::HtmlCollectionFilter::ClassName(class_name),
+                )
             }
 
-            #[napi]
+            #[napi(ts_return_type = "HTMLCollection")]
             pub fn get_elements_by_tag_name(
                 &self,
                 qualified_name: String,
-            ) -> Vec<napi::bindgen_prelude::Reference<crate::Element>>
-            {
-                let tag_name: &str = &qualified_name;
-
-                self.as_node()
-                    .deep_child_nodes_iter()
-                    .filter(
-                        |e: &napi::bindgen_prelude::Reference<
-                            crate::Element,
-                        >| {
-                            e.get_tag_name().eq_ignore_ascii_case(tag_name)
-                        },
-                    )
-                    .collect()
+            ) -> napi::Result<napi::JsObject> {
+                crate::HtmlCollection::create(
+                    self.env,
+                    self.into(),
+                    crate::HtmlCollectionFilter::TagName(qualified_name),
+                )
             }
 
             #[napi]
             pub fn query_selector(
                 &self,
-                selectors: String,
+                selectors: napi::Either<String, &crate::CompiledSelector>,
             ) -> napi::Result<
                 Option<napi::bindgen_prelude::Reference<crate::Element>>,
             > {
-                self.as_node().selectors_iter(selectors)?.try_next()
+                self.as_node().selectors_iter(selectors, false)?.try_next()
             }
 
             #[napi]
             pub fn query_selector_all(
                 &self,
-                selectors: String,
+                selectors: napi::Either<String, &crate::CompiledSelector>,
             ) -> napi::Result<
                 Vec<napi::bindgen_prelude::Reference<crate::Element>>,
             > {
-                self.as_node().selectors_iter(selectors)?.collect()
+                self.as_node().selectors_iter(selectors, true)?.collect()
             }
 
             #[napi(getter)]
@@ -291,13 +271,6 @@ pub fn create_node(
                 self.as_node().parent_iterator().try_next()
             }
 
-            #[napi(getter)]
-            pub fn get_owner_document(
-                &self,
-            ) -> napi::Result<Option<crate::WeakReference<crate::Document>>> {
-                self.as_node().parent_iterator().try_next()
-            }
-
             #[napi]
             pub fn remove(&self) -> napi::Result<()> {
                 self.as_child_node().remove()
@@ -385,7 +358,63 @@ pub fn create_node(
                 self.as_node().get_node_value()
             }
 
-            fn as_node(&self) -> crate::Node {
+            #[napi(getter)]
+            pub fn get_owner_document(
+                &self,
+            ) -> napi::Result<Option<napi::bindgen_prelude::Reference<crate::Document>>> {
+                match self.as_node() {
+                    crate::Node::Document(_) => Ok(None),
+                    node => node.get_node_document(),
+                }
+            }
+
+            #[napi(getter, js_name = "baseURI")]
+            pub fn get_base_uri(&self) -> napi::Result<String> {
+                self.as_node().get_base_uri()
+            }
+
+            #[napi(getter)]
+            pub fn get_is_connected(&self) -> napi::Result<bool> {
+                Ok(self.as_node().get_connected_document()?.is_some())
+            }
+
+            #[napi]
+            pub fn get_root_node(&self) -> napi::Result<crate::Node> {
+                self.as_node().get_root(self.env)
+            }
+
+            #[napi]
+            pub fn contains(
+                &self,
+                other: Option<crate::Node>,
+            ) -> napi::Result<bool> {
+                match other {
+                    Some(other) => {
+                        self.as_node().is_inclusive_ancestor_of(self.env, &other)
+                    },
+                    None => Ok(false),
+                }
+            }
+
+            #[napi]
+            pub fn compare_document_position(
+                &self,
+                other: crate::Node,
+            ) -> napi::Result<u32> {
+                self.as_node().compare_document_position(self.env, &other)
+            }
+
+            #[napi]
+            pub fn is_equal_node(&self, other: Option<crate::Node>) -> bool {
+                other.is_some_and(|other| self.as_node().is_equal_node(&other))
+            }
+
+            #[napi]
+            pub fn is_same_node(&self, other: Option<crate::Node>) -> bool {
+                other.is_some_and(|other| self.as_node() == other)
+            }
+
+            pub(crate) fn as_node(&self) -> crate::Node {
                 let node: crate::Node = self.into();
                 node
             }
@@ -393,7 +422,7 @@ pub fn create_node(
             #is_child_impl
             #has_children_impl
 
-            #(#node_types)*
+            #(#constants)*
         }
     }
     .into()
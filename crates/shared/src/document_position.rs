@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+pub enum DocumentPosition {
+    Disconnected = 0x01,
+    Preceding = 0x02,
+    Following = 0x04,
+    Contains = 0x08,
+    ContainedBy = 0x10,
+    ImplementationSpecific = 0x20,
+}
+
+lazy_static! {
+    pub static ref DOCUMENT_POSITION_MAP: HashMap<&'static str, u32> = vec![
+        (
+            "DOCUMENT_POSITION_DISCONNECTED",
+            DocumentPosition::Disconnected as u32
+        ),
+        (
+            "DOCUMENT_POSITION_PRECEDING",
+            DocumentPosition::Preceding as u32
+        ),
+        (
+            "DOCUMENT_POSITION_FOLLOWING",
+            DocumentPosition::Following as u32
+        ),
+        (
+            "DOCUMENT_POSITION_CONTAINS",
+            DocumentPosition::Contains as u32
+        ),
+        (
+            "DOCUMENT_POSITION_CONTAINED_BY",
+            DocumentPosition::ContainedBy as u32
+        ),
+        (
+            "DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC",
+            DocumentPosition::ImplementationSpecific as u32
+        ),
+    ]
+    .into_iter()
+    .collect();
+}
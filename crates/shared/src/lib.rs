@@ -1,4 +1,5 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod document_position;
 pub mod node_type;
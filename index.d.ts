@@ -1,8 +1,6 @@
 export type ChildNode = Comment | DocumentType | Element | Text;
 export type ParentNode = Document | DocumentFragment | Element;
-export type ClassList = import("./types/generated").ClassList & {
-    [index: string]: string;
-};
+export type Node = ChildNode | ParentNode;
 export type StyleDeclaration = import("./types/generated").StyleDeclaration & {
     [index: string]: string;
 };
@@ -11,15 +9,348 @@ export type InsertPosition =
     | "afterbegin"
     | "beforeend"
     | "afterend";
+// Methods taking any number of arguments, which are defined at runtime (see
+// main/src/variadic.rs) and merged into the generated classes here.
+interface ChildNodeMethods {
+    before(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    after(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    replaceWith(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+}
+interface ParentNodeMethods {
+    prepend(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    append(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    replaceChildren(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+}
+export interface Comment extends ChildNodeMethods {}
+export interface DocumentType extends ChildNodeMethods {}
+export interface Element extends ChildNodeMethods, ParentNodeMethods {}
+export interface Text extends ChildNodeMethods {}
+export interface Document extends ParentNodeMethods {}
+export interface DocumentFragment extends ParentNodeMethods {}
+// Index access and the iteration methods shared with arrays, which are added
+// at runtime (see main/src/collections).
+export interface NodeList {
+    readonly [index: number]: ChildNode;
+    forEach(
+        callback: (value: ChildNode, key: number, parent: NodeList) => void,
+        thisArg?: any,
+    ): void;
+    entries(): IterableIterator<[number, ChildNode]>;
+    keys(): IterableIterator<number>;
+    values(): IterableIterator<ChildNode>;
+    [Symbol.iterator](): IterableIterator<ChildNode>;
+}
+export interface HTMLCollection {
+    readonly [index: number]: Element;
+    [Symbol.iterator](): IterableIterator<Element>;
+}
+export interface NamedNodeMap {
+    readonly [index: number]: Attr;
+    [Symbol.iterator](): IterableIterator<Attr>;
+}
+export interface DOMTokenList {
+    readonly [index: number]: string;
+    forEach(
+        callback: (value: string, key: number, parent: DOMTokenList) => void,
+        thisArg?: any,
+    ): void;
+    entries(): IterableIterator<[number, string]>;
+    keys(): IterableIterator<number>;
+    values(): IterableIterator<string>;
+    [Symbol.iterator](): IterableIterator<string>;
+}
+export interface DOMStringMap {
+    [name: string]: string | undefined;
+}
+// The interfaces of HTML elements, which are prototypes set at runtime (see
+// main/src/html_elements) rather than generated classes.
+export interface HTMLElement extends Element {
+    title: string;
+    lang: string;
+    dir: string;
+    hidden: boolean;
+    inert: boolean;
+    accessKey: string;
+    autofocus: boolean;
+    nonce: string;
+    tabIndex: number;
+}
+interface HTMLHyperlinkElementUtils {
+    href: string;
+    readonly origin: string;
+    protocol: string;
+    username: string;
+    password: string;
+    host: string;
+    hostname: string;
+    port: string;
+    pathname: string;
+    search: string;
+    hash: string;
+}
+export interface HTMLAnchorElement
+    extends HTMLElement,
+        HTMLHyperlinkElementUtils {
+    target: string;
+    download: string;
+    ping: string;
+    rel: string;
+    referrerPolicy: string;
+    hreflang: string;
+    type: string;
+    name: string;
+    text: string;
+}
+export interface HTMLFormElement extends HTMLElement {
+    acceptCharset: string;
+    action: string;
+    autocomplete: string;
+    enctype: string;
+    encoding: string;
+    method: string;
+    name: string;
+    noValidate: boolean;
+    target: string;
+    rel: string;
+    readonly elements: HTMLCollection;
+    readonly length: number;
+}
+export interface HTMLImageElement extends HTMLElement {
+    name: string;
+    alt: string;
+    src: string;
+    srcset: string;
+    sizes: string;
+    crossOrigin: string | null;
+    useMap: string;
+    isMap: boolean;
+    width: number;
+    height: number;
+    referrerPolicy: string;
+    decoding: string;
+    loading: string;
+}
+export interface HTMLInputElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    accept: string;
+    alt: string;
+    autocomplete: string;
+    defaultChecked: boolean;
+    checked: boolean;
+    dirName: string;
+    formAction: string;
+    formNoValidate: boolean;
+    formTarget: string;
+    max: string;
+    maxLength: number;
+    min: string;
+    minLength: number;
+    multiple: boolean;
+    pattern: string;
+    placeholder: string;
+    readOnly: boolean;
+    required: boolean;
+    size: number;
+    src: string;
+    step: string;
+    type: string;
+    defaultValue: string;
+    value: string;
+}
+export interface HTMLOptionElement extends HTMLElement {
+    disabled: boolean;
+    label: string;
+    defaultSelected: boolean;
+    selected: boolean;
+    value: string;
+    text: string;
+}
+export interface HTMLSelectElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    autocomplete: string;
+    multiple: boolean;
+    required: boolean;
+    size: number;
+    readonly type: string;
+    readonly options: HTMLCollection;
+    readonly length: number;
+    readonly selectedIndex: number;
+    value: string;
+}
+export interface HTMLTableCellElement extends HTMLElement {
+    colSpan: number;
+    rowSpan: number;
+    headers: string;
+    scope: string;
+    abbr: string;
+}
+export interface HTMLTextAreaElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    autocomplete: string;
+    cols: number;
+    rows: number;
+    dirName: string;
+    maxLength: number;
+    minLength: number;
+    placeholder: string;
+    readOnly: boolean;
+    required: boolean;
+    wrap: string;
+    defaultValue: string;
+    value: string;
+}
+// The interfaces that only exist at runtime, which cannot be constructed (see
+// main/src/node_wrappers/interface.rs and main/src/html_elements).
+interface Interface<T> extends Function {
+    readonly prototype: T;
+}
+export interface NodeInterface extends Interface<Node> {
+    readonly ELEMENT_NODE: 1;
+    readonly ATTRIBUTE_NODE: 2;
+    readonly TEXT_NODE: 3;
+    readonly CDATA_SECTION_NODE: 4;
+    readonly PROCESSING_INSTRUCTION_NODE: 7;
+    readonly COMMENT_NODE: 8;
+    readonly DOCUMENT_NODE: 9;
+    readonly DOCUMENT_TYPE_NODE: 10;
+    readonly DOCUMENT_FRAGMENT_NODE: 11;
+    readonly DOCUMENT_POSITION_DISCONNECTED: 0x01;
+    readonly DOCUMENT_POSITION_PRECEDING: 0x02;
+    readonly DOCUMENT_POSITION_FOLLOWING: 0x04;
+    readonly DOCUMENT_POSITION_CONTAINS: 0x08;
+    readonly DOCUMENT_POSITION_CONTAINED_BY: 0x10;
+    readonly DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC: 0x20;
+}
+// Classes that are globals in browsers, which are defined on every window at
+// runtime (see main/src/nodes/window.rs).
+export interface Window {
+    Node: NodeInterface;
+    Comment: typeof Comment;
+    Document: typeof Document;
+    DocumentFragment: typeof DocumentFragment;
+    DocumentType: typeof DocumentType;
+    Element: typeof Element;
+    Text: typeof Text;
+    DOMParser: typeof DOMParser;
+    XMLSerializer: typeof XMLSerializer;
+    HTMLElement: Interface<HTMLElement>;
+    HTMLAnchorElement: Interface<HTMLAnchorElement>;
+    HTMLFormElement: Interface<HTMLFormElement>;
+    HTMLImageElement: Interface<HTMLImageElement>;
+    HTMLInputElement: Interface<HTMLInputElement>;
+    HTMLOptionElement: Interface<HTMLOptionElement>;
+    HTMLSelectElement: Interface<HTMLSelectElement>;
+    HTMLTableCellElement: Interface<HTMLTableCellElement>;
+    HTMLTextAreaElement: Interface<HTMLTextAreaElement>;
+    readonly [name: `HTML${string}Element`]: Interface<HTMLElement>;
+}
 export const enum QuirksMode {
     Quirks = 0,
     LimitedQuirks = 1,
     NoQuirks = 2,
 }
+/** A single selector of a selector list passed to `parseSelector`. */
+export interface SelectorInfo {
+    /** The selector serialized in its canonical form. */
+    text: string;
+    /** `[ids, classes, types]` */
+    specificity: Array<number>;
+}
+export interface SelectorParseError {
+    message: string;
+    /** 1-based line of the error within the selector text. */
+    line: number;
+    /** 1-based column of the error within the selector text. */
+    column: number;
+}
+export interface SelectorParseResult {
+    selectors: Array<SelectorInfo>;
+    error?: SelectorParseError;
+}
+/**
+ * Parses a selector list without matching anything, returning the
+ * specificity and canonical serialization of each selector or where parsing
+ * failed.
+ */
+export function parseSelector(selectors: string): SelectorParseResult;
+export function compileSelector(selectors: string): CompiledSelector;
+export type HtmlCollection = HTMLCollection;
 export type Html5everDom = Html5EverDom;
+export type DomParser = DOMParser;
+export type DomImplementation = DOMImplementation;
+export type DomStringMap = DOMStringMap;
+export type DomTokenList = DOMTokenList;
+export type XmlSerializer = XMLSerializer;
+/**
+ * A collection of elements, as returned by `children`,
+ * `getElementsByTagName()` and `getElementsByClassName()`.
+ *
+ * The collection is live: it searches the tree below its root again once
+ * the tree changes.
+ */
+export class HTMLCollection {
+    /** Collections are only created by the tree, like in browsers. */
+    constructor();
+    get length(): number;
+    item(index: number): Element | null;
+    /** The first element with `name` as its `id` or `name` attribute. */
+    namedItem(name: string): Element | null;
+}
+/**
+ * The attributes of an element, as returned by `attributes`.
+ *
+ * The map is live: it reads the attributes of its element on every access,
+ * and its `Attr`s, one per attribute, write their value through to the
+ * element.
+ */
+export class NamedNodeMap {
+    /** Maps are only created by the tree, like in browsers. */
+    constructor();
+    get length(): number;
+    item(index: number): Attr | null;
+    getNamedItem(qualifiedName: string): Attr | null;
+    getNamedItemNS(
+        namespace: string | undefined | null,
+        localName: string,
+    ): Attr | null;
+    /**
+     * Puts `attr` on the element, replacing the attribute with the same
+     * namespace and local name, whose `Attr` is returned.
+     */
+    setNamedItem(attr: Attr): Attr | null;
+    setNamedItemNS(attr: Attr): Attr | null;
+    removeNamedItem(qualifiedName: string): Attr;
+    removeNamedItemNS(
+        namespace: string | undefined | null,
+        localName: string,
+    ): Attr;
+}
+/**
+ * The child nodes of a node, as returned by `childNodes`, or the elements
+ * below it that match a filter, as returned by `getElementsByName()`.
+ *
+ * The list is live: it reads the child nodes on every access, and searches
+ * the tree below its parent again once the tree changes.
+ */
+export class NodeList {
+    /** Collections are only created by the tree, like in browsers. */
+    constructor();
+    get length(): number;
+    item(index: number): ChildNode | null;
+}
 export class Html5EverDom {
     errors: Array<string>;
-    constructor(html?: string | undefined | null);
+    /**
+     * Parses `html` into a document with the URL `url`, `about:blank` by
+     * default, which relative URLs in it resolve against.
+     */
+    constructor(
+        html?: string | undefined | null,
+        url?: string | undefined | null,
+    );
     static createDocumentFragment(
         html: string,
         maybeQuirksMode?: QuirksMode | undefined | null,
@@ -28,14 +359,30 @@ export class Html5EverDom {
     get quirksMode(): QuirksMode;
     serialize(): string;
 }
+/** See https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#domparser */
+export class DOMParser {
+    constructor();
+    /**
+     * Parses `string` as HTML for `text/html` and as XML for the XML types,
+     * into a document without a window.
+     */
+    parseFromString(string: string, r#type: string): Document;
+}
 export class Comment {
     data: string;
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
     get parentElement(): Element | null;
     get parentNode(): ParentNode | null;
-    get ownerDocument(): Document | null;
     remove(): void;
     get previousSibling(): ChildNode | null;
     get previousElementSibling(): Element | null;
@@ -46,6 +393,12 @@ export class Comment {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
@@ -53,25 +406,74 @@ export class Comment {
     get textContent(): string | null;
     cloneNode(): this;
 }
+/**
+ * Creates documents and doctypes without parsing, as returned by
+ * `document.implementation`, see
+ * https://dom.spec.whatwg.org/#interface-domimplementation
+ */
+export class DOMImplementation {
+    /**
+     * Implementations are only created by their document, like in
+     * browsers.
+     */
+    constructor();
+    /** See https://dom.spec.whatwg.org/#dom-domimplementation-createdocumenttype */
+    createDocumentType(
+        name: string,
+        publicId: string,
+        systemId: string,
+    ): DocumentType;
+    /**
+     * An XML document with a document element named `qualified_name`,
+     * unless it is empty, see
+     * https://dom.spec.whatwg.org/#dom-domimplementation-createdocument
+     */
+    createDocument(
+        namespace?: string | undefined | null,
+        qualifiedName?: string | undefined | null,
+        doctype?: DocumentType | undefined | null,
+    ): Document;
+    /**
+     * An HTML document with a doctype, a head with a `title` if there is
+     * one, and a body, see
+     * https://dom.spec.whatwg.org/#dom-domimplementation-createhtmldocument
+     */
+    createHTMLDocument(title?: string | undefined | null): Document;
+    /**
+     * Always `true`, like in browsers, see
+     * https://dom.spec.whatwg.org/#dom-domimplementation-hasfeature
+     */
+    hasFeature(): boolean;
+}
 export class Document {
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
-    get childNodes(): Array<ChildNode>;
-    get children(): Array<Element>;
-    append(childNodeOrText: ChildNode | string): void;
-    prepend(childNodeOrText: ChildNode | string): void;
-    appendChild<T extends ChildNode>(child: T): T;
-    insertBefore<T extends ChildNode>(
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
+    get childNodes(): NodeList;
+    get children(): HTMLCollection;
+    appendChild<T extends ChildNode | DocumentFragment>(child: T): T;
+    insertBefore<T extends ChildNode | DocumentFragment>(
         new_node: T,
         reference_node: ChildNode,
     ): T;
+    replaceChild<T extends ChildNode>(
+        node: ChildNode | DocumentFragment,
+        child: T,
+    ): T;
     removeChild<T extends ChildNode>(child: T): T;
     getElementById(id: string): Element | null;
-    getElementsByClassName(className: string): Array<Element>;
-    getElementsByTagName(qualifiedName: string): Array<Element>;
-    querySelector(selectors: string): Element | null;
-    querySelectorAll(selectors: string): Array<Element>;
+    getElementsByClassName(className: string): HTMLCollection;
+    getElementsByTagName(qualifiedName: string): HTMLCollection;
+    querySelector(selectors: string | CompiledSelector): Element | null;
+    querySelectorAll(selectors: string | CompiledSelector): Array<Element>;
     get firstChild(): ChildNode | null;
     get firstElementChild(): Element | null;
     get lastChild(): ChildNode | null;
@@ -82,39 +484,115 @@ export class Document {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
     get TEXT_NODE(): number;
     get doctype(): DocumentType | null;
-    get documentElement(): Element;
+    /**
+     * The first element child, if any, see
+     * https://dom.spec.whatwg.org/#dom-document-documentelement
+     */
+    get documentElement(): Element | null;
+    get contentType(): string;
+    /**
+     * The same object on every access, see
+     * https://dom.spec.whatwg.org/#dom-document-implementation
+     */
+    get implementation(): DOMImplementation;
+    get URL(): string;
+    get documentURI(): string;
     get defaultView(): Window | null;
-    get head(): Element;
-    get body(): Element;
+    /** See https://html.spec.whatwg.org/#dom-document-head */
+    get head(): Element | null;
+    /** See https://html.spec.whatwg.org/#dom-document-body */
+    get body(): Element | null;
+    /** The text of the `title` element, with its whitespace collapsed. */
+    get title(): string;
+    /**
+     * Replaces the text of the `title` element, which is added to the head
+     * if there is none. Does nothing without a head, see
+     * https://html.spec.whatwg.org/#document.title
+     */
+    set title(title: string);
+    /**
+     * The `dir` of the `html` element, see
+     * https://html.spec.whatwg.org/#dom-document-dir
+     */
+    get dir(): string;
+    set dir(dir: string);
+    /** The `lang` of the `html` element. */
+    get lang(): string;
+    set lang(lang: string);
+    /** Always `null`, as nothing is ever focused. */
+    get activeElement(): Element | null;
+    get forms(): HTMLCollection;
+    get images(): HTMLCollection;
+    get embeds(): HTMLCollection;
+    get scripts(): HTMLCollection;
+    get links(): HTMLCollection;
+    get anchors(): HTMLCollection;
+    /** The HTML elements with `name` as their `name` attribute. */
+    getElementsByName(name: string): NodeList;
     get textContent(): string | null;
+    /** See https://dom.spec.whatwg.org/#dom-document-createelement */
     createElement(name: string): Element;
+    /** See https://dom.spec.whatwg.org/#dom-document-createelementns */
+    createElementNS(
+        namespace: string | undefined | null,
+        qualifiedName: string,
+    ): Element;
+    createComment(data: string): Comment;
+    /**
+     * An attribute that is not on an element yet, see
+     * https://dom.spec.whatwg.org/#dom-document-createattribute
+     */
+    createAttribute(localName: string): Attr;
+    createAttributeNS(
+        namespace: string | undefined | null,
+        qualifiedName: string,
+    ): Attr;
     createTextNode(data: string): Text;
     createDocumentFragment(html?: string | undefined | null): DocumentFragment;
+    cloneNode(deep?: boolean | undefined | null): this;
+    importNode(node: ChildNode | DocumentFragment, deep?: boolean): Node;
+    adoptNode<T extends ChildNode | DocumentFragment>(node: T): T;
 }
 export class DocumentFragment {
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
-    get childNodes(): Array<ChildNode>;
-    get children(): Array<Element>;
-    append(childNodeOrText: ChildNode | string): void;
-    prepend(childNodeOrText: ChildNode | string): void;
-    appendChild<T extends ChildNode>(child: T): T;
-    insertBefore<T extends ChildNode>(
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
+    get childNodes(): NodeList;
+    get children(): HTMLCollection;
+    appendChild<T extends ChildNode | DocumentFragment>(child: T): T;
+    insertBefore<T extends ChildNode | DocumentFragment>(
         new_node: T,
         reference_node: ChildNode,
     ): T;
+    replaceChild<T extends ChildNode>(
+        node: ChildNode | DocumentFragment,
+        child: T,
+    ): T;
     removeChild<T extends ChildNode>(child: T): T;
     getElementById(id: string): Element | null;
-    getElementsByClassName(className: string): Array<Element>;
-    getElementsByTagName(qualifiedName: string): Array<Element>;
-    querySelector(selectors: string): Element | null;
-    querySelectorAll(selectors: string): Array<Element>;
+    getElementsByClassName(className: string): HTMLCollection;
+    getElementsByTagName(qualifiedName: string): HTMLCollection;
+    querySelector(selectors: string | CompiledSelector): Element | null;
+    querySelectorAll(selectors: string | CompiledSelector): Array<Element>;
     get firstChild(): ChildNode | null;
     get firstElementChild(): Element | null;
     get lastChild(): ChildNode | null;
@@ -125,11 +603,18 @@ export class DocumentFragment {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
     get TEXT_NODE(): number;
     get textContent(): string | null;
+    cloneNode(deep?: boolean | undefined | null): this;
 }
 export class DocumentType {
     name: string;
@@ -138,9 +623,16 @@ export class DocumentType {
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
     get parentElement(): Element | null;
     get parentNode(): ParentNode | null;
-    get ownerDocument(): Document | null;
     remove(): void;
     get previousSibling(): ChildNode | null;
     get previousElementSibling(): Element | null;
@@ -151,6 +643,12 @@ export class DocumentType {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
@@ -160,14 +658,37 @@ export class DocumentType {
 }
 export class Attr {
     get localName(): string;
+    /** The qualified name, with the prefix if there is one. */
     get name(): string;
-    get namespaceUri(): string;
+    get namespaceURI(): string | null;
     get ownerDocument(): Document | null;
-    get ownerElement(): Element;
+    get ownerElement(): Element | null;
+    /**
+     * The prefix of an attribute created in a namespace. Attributes parsed
+     * from HTML have none, but a colon in their local name still reads as
+     * one, as it always has.
+     */
     get prefix(): string | null;
     get value(): string;
+    set value(value: string);
+}
+/**
+ * The `data-*` attributes of an element by their camelCase names, as
+ * returned by `dataset`, see
+ * https://html.spec.whatwg.org/#domstringmap
+ */
+export class DOMStringMap {
+    /** Maps are only created by the tree, like in browsers. */
+    constructor();
 }
-export class ClassList {
+/**
+ * The tokens of an attribute of an element, like `class` for `classList`
+ * and `rel` for `relList`, see
+ * https://dom.spec.whatwg.org/#interface-domtokenlist
+ */
+export class DOMTokenList {
+    /** Lists are only created by their element, like in browsers. */
+    constructor();
     item(index: number): string | null;
     add(
         token1?: string | undefined | null,
@@ -183,10 +704,24 @@ export class ClassList {
         token4?: string | undefined | null,
         token5?: string | undefined | null,
     ): void;
+    /**
+     * Adds `token` if it is missing and removes it otherwise, unless
+     * `force` says which to do. Returns whether it is there after.
+     */
     toggle(token: string, force?: boolean | undefined | null): boolean;
+    /**
+     * Puts `new_token` where `token` is, unless `token` is missing. Returns
+     * whether it was replaced.
+     */
+    replace(token: string, newToken: string): boolean;
     contains(token: string): boolean;
     get length(): number;
     get value(): string;
+    /**
+     * Whether `token` is one of the tokens the attribute supports. Throws a
+     * `TypeError` for attributes without supported tokens, like `class`.
+     */
+    supports(token: string): boolean;
     toString(): string;
     set value(value: string);
 }
@@ -194,29 +729,38 @@ export class Element {
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
     get parentElement(): Element | null;
     get parentNode(): ParentNode | null;
-    get ownerDocument(): Document | null;
     remove(): void;
     get previousSibling(): ChildNode | null;
     get previousElementSibling(): Element | null;
     get nextSibling(): ChildNode | null;
     get nextElementSibling(): Element | null;
-    get childNodes(): Array<ChildNode>;
-    get children(): Array<Element>;
-    append(childNodeOrText: ChildNode | string): void;
-    prepend(childNodeOrText: ChildNode | string): void;
-    appendChild<T extends ChildNode>(child: T): T;
-    insertBefore<T extends ChildNode>(
+    get childNodes(): NodeList;
+    get children(): HTMLCollection;
+    appendChild<T extends ChildNode | DocumentFragment>(child: T): T;
+    insertBefore<T extends ChildNode | DocumentFragment>(
         new_node: T,
         reference_node: ChildNode,
     ): T;
+    replaceChild<T extends ChildNode>(
+        node: ChildNode | DocumentFragment,
+        child: T,
+    ): T;
     removeChild<T extends ChildNode>(child: T): T;
     getElementById(id: string): Element | null;
-    getElementsByClassName(className: string): Array<Element>;
-    getElementsByTagName(qualifiedName: string): Array<Element>;
-    querySelector(selectors: string): Element | null;
-    querySelectorAll(selectors: string): Array<Element>;
+    getElementsByClassName(className: string): HTMLCollection;
+    getElementsByTagName(qualifiedName: string): HTMLCollection;
+    querySelector(selectors: string | CompiledSelector): Element | null;
+    querySelectorAll(selectors: string | CompiledSelector): Array<Element>;
     get firstChild(): ChildNode | null;
     get firstElementChild(): Element | null;
     get lastChild(): ChildNode | null;
@@ -227,18 +771,41 @@ export class Element {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
     get TEXT_NODE(): number;
-    get attributes(): Array<Attr>;
+    get content(): DocumentFragment | null;
+    get attributes(): NamedNodeMap;
     getAttribute(name: string): string | null;
     removeAttribute(name: string): void;
     setAttribute(name: string, value: string): void;
     hasAttribute(name: string): boolean;
-    get classList(): ClassList;
+    /**
+     * Adds the attribute `name` if it is missing and removes it otherwise,
+     * unless `force` says which to do. Returns whether it is there after.
+     */
+    toggleAttribute(name: string, force?: boolean | undefined | null): boolean;
+    getAttributeNames(): Array<string>;
+    hasAttributes(): boolean;
+    get dataset(): DOMStringMap;
+    get classList(): DOMTokenList;
+    /** The link types of `a`, `area`, `form` and `link` elements. */
+    get relList(): DOMTokenList | null;
+    /** The restrictions lifted for the content of `iframe` elements. */
+    get sandbox(): DOMTokenList | null;
+    get part(): DOMTokenList;
     get style(): StyleDeclaration;
     get tagName(): string;
+    get namespaceURI(): string | null;
+    get prefix(): string | null;
+    get localName(): string;
     get innerHTML(): string;
     set innerHTML(html: string);
     get outerHTML(): string;
@@ -252,16 +819,23 @@ export class Element {
     insertAdjacentElement(position: InsertPosition, element: Element): Element;
     insertAdjacentHTML(position: InsertPosition, html: string): void;
     insertAdjacentText(position: InsertPosition, text: string): void;
-    matches(selectors: string): boolean;
+    matches(selectors: string | CompiledSelector): boolean;
 }
 export class Text {
     data: string;
     get nodeName(): string;
     get nodeType(): number;
     get nodeValue(): string | null;
+    get ownerDocument(): Document | null;
+    get baseURI(): string;
+    get isConnected(): boolean;
+    getRootNode(): Node;
+    contains(other?: Node | undefined | null): boolean;
+    compareDocumentPosition(other: Node): number;
+    isEqualNode(other?: Node | undefined | null): boolean;
+    isSameNode(other?: Node | undefined | null): boolean;
     get parentElement(): Element | null;
     get parentNode(): ParentNode | null;
-    get ownerDocument(): Document | null;
     remove(): void;
     get previousSibling(): ChildNode | null;
     get previousElementSibling(): Element | null;
@@ -272,6 +846,12 @@ export class Text {
     get COMMENT_NODE(): number;
     get DOCUMENT_FRAGMENT_NODE(): number;
     get DOCUMENT_NODE(): number;
+    get DOCUMENT_POSITION_CONTAINED_BY(): number;
+    get DOCUMENT_POSITION_CONTAINS(): number;
+    get DOCUMENT_POSITION_DISCONNECTED(): number;
+    get DOCUMENT_POSITION_FOLLOWING(): number;
+    get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number;
+    get DOCUMENT_POSITION_PRECEDING(): number;
     get DOCUMENT_TYPE_NODE(): number;
     get ELEMENT_NODE(): number;
     get PROCESSING_INSTRUCTION_NODE(): number;
@@ -282,6 +862,13 @@ export class Text {
 export class Window {
     get document(): Document;
 }
+/**
+ * A selector list that has been parsed once and can be passed to `matches`,
+ * `querySelector` and `querySelectorAll` instead of a string.
+ */
+export class CompiledSelector {
+    get selectorText(): string;
+}
 export class StyleDeclaration {
     get accentColor(): string;
     set accentColor(value?: string | undefined | null);
@@ -1206,3 +1793,8 @@ export class StyleDeclaration {
     item(index: number): string | null;
     get length(): number;
 }
+/** See https://w3c.github.io/DOM-Parsing/#the-xmlserializer-interface */
+export class XMLSerializer {
+    constructor();
+    serializeToString(node: Node): string;
+}
@@ -236,17 +236,27 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Html5EverDom, Comment, Document, DocumentFragment, DocumentType, Attr, ClassList, Element, Text, Window, QuirksMode, StyleDeclaration } = nativeBinding
+const { HTMLCollection, NamedNodeMap, NodeList, Html5EverDom, DOMParser, Comment, DOMImplementation, Document, DocumentFragment, DocumentType, Attr, DOMStringMap, DOMTokenList, Element, Text, Window, QuirksMode, CompiledSelector, parseSelector, compileSelector, StyleDeclaration, XMLSerializer } = nativeBinding
 
+module.exports.HTMLCollection = HTMLCollection
+module.exports.NamedNodeMap = NamedNodeMap
+module.exports.NodeList = NodeList
 module.exports.Html5EverDom = Html5EverDom
+module.exports.DOMParser = DOMParser
 module.exports.Comment = Comment
+module.exports.DOMImplementation = DOMImplementation
 module.exports.Document = Document
 module.exports.DocumentFragment = DocumentFragment
 module.exports.DocumentType = DocumentType
 module.exports.Attr = Attr
-module.exports.ClassList = ClassList
+module.exports.DOMStringMap = DOMStringMap
+module.exports.DOMTokenList = DOMTokenList
 module.exports.Element = Element
 module.exports.Text = Text
 module.exports.Window = Window
 module.exports.QuirksMode = QuirksMode
+module.exports.CompiledSelector = CompiledSelector
+module.exports.parseSelector = parseSelector
+module.exports.compileSelector = compileSelector
 module.exports.StyleDeclaration = StyleDeclaration
+module.exports.XMLSerializer = XMLSerializer
@@ -0,0 +1,223 @@
+use html5ever::{
+    expanded_name,
+    local_name,
+    namespace_url,
+    ns,
+};
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    JsObject,
+    Result,
+    Status,
+};
+
+use super::{
+    indexed_access,
+    MatchedElements,
+};
+use crate::{
+    Element,
+    ParentNode,
+};
+
+/// Which elements below its root an `HTMLCollection` holds.
+pub enum HtmlCollectionFilter {
+    /// The element children, as returned by `children`.
+    Children,
+    /// The descendants with a tag name, as returned by
+    /// `getElementsByTagName()`.
+    TagName(String),
+    /// The descendants with a class, as returned by
+    /// `getElementsByClassName()`.
+    ClassName(String),
+    /// The form controls below a form, as returned by `elements`.
+    FormControls,
+    /// The options below a select, as returned by `options`.
+    Options,
+    /// The `form` elements of a document, as returned by `forms`.
+    Forms,
+    /// The `img` elements of a document, as returned by `images`.
+    Images,
+    /// The `embed` elements of a document, as returned by `embeds`.
+    Embeds,
+    /// The `script` elements of a document, as returned by `scripts`.
+    Scripts,
+    /// The `a` and `area` elements with an `href`, as returned by `links`.
+    Links,
+    /// The `a` elements with a `name`, as returned by `anchors`.
+    Anchors,
+    /// The HTML elements with a `name`, as returned by
+    /// `getElementsByName()`.
+    Name(String),
+}
+
+impl HtmlCollectionFilter {
+    pub(crate) fn matches(
+        &self,
+        element: &Element,
+    ) -> bool {
+        match self {
+            HtmlCollectionFilter::Children => true,
+            HtmlCollectionFilter::TagName(tag_name) => {
+                element.get_qualified_name().eq_ignore_ascii_case(tag_name)
+            },
+            HtmlCollectionFilter::ClassName(class_name) => element
+                .get_attribute("class".to_string())
+                .map(|class| {
+                    class.split_ascii_whitespace().any(|c| c == class_name)
+                })
+                .unwrap_or(false),
+            HtmlCollectionFilter::FormControls => {
+                element.name.ns == ns!(html)
+                    && match element.name.local {
+                        local_name!("button")
+                        | local_name!("fieldset")
+                        | local_name!("object")
+                        | local_name!("output")
+                        | local_name!("select")
+                        | local_name!("textarea") => true,
+                        // image buttons are left out, for legacy reasons
+                        local_name!("input") => !element
+                            .get_attribute("type".to_string())
+                            .is_some_and(|t| t.eq_ignore_ascii_case("image")),
+                        _ => false,
+                    }
+            },
+            HtmlCollectionFilter::Options => {
+                element.name.expanded() == expanded_name!(html "option")
+            },
+            HtmlCollectionFilter::Forms => {
+                element.name.expanded() == expanded_name!(html "form")
+            },
+            HtmlCollectionFilter::Images => {
+                element.name.expanded() == expanded_name!(html "img")
+            },
+            HtmlCollectionFilter::Embeds => {
+                element.name.expanded() == expanded_name!(html "embed")
+            },
+            HtmlCollectionFilter::Scripts => {
+                element.name.expanded() == expanded_name!(html "script")
+            },
+            HtmlCollectionFilter::Links => {
+                (element.name.expanded() == expanded_name!(html "a")
+                    || element.name.expanded() == expanded_name!(html "area"))
+                    && element.has_attribute("href".to_string())
+            },
+            HtmlCollectionFilter::Anchors => {
+                element.name.expanded() == expanded_name!(html "a")
+                    && element.has_attribute("name".to_string())
+            },
+            HtmlCollectionFilter::Name(name) => {
+                element.name.ns == ns!(html)
+                    && element.get_attribute("name".to_string()).as_ref()
+                        == Some(name)
+            },
+        }
+    }
+}
+
+/// A collection of elements, as returned by `children`,
+/// `getElementsByTagName()` and `getElementsByClassName()`.
+///
+/// The collection is live: it searches the tree below its root again once
+/// the tree changes.
+#[napi(js_name = "HTMLCollection")]
+pub struct HtmlCollection {
+    root: ParentNode,
+    filter: HtmlCollectionFilter,
+    matched: MatchedElements,
+    env: Env,
+}
+
+impl HtmlCollection {
+    /// Creates the collection, wrapped for index access.
+    pub(crate) fn create(
+        env: Env,
+        root: ParentNode,
+        filter: HtmlCollectionFilter,
+    ) -> Result<JsObject> {
+        let collection = Self {
+            root,
+            filter,
+            matched: Default::default(),
+            env,
+        }
+        .into_instance(env)?;
+
+        indexed_access::wrap(env, collection.as_object(env))
+    }
+
+    fn search(&self) -> Result<Vec<Reference<Element>>> {
+        let root = self.root.upgrade(self.env)?;
+
+        let elements: Box<dyn Iterator<Item = Reference<Element>>> =
+            match self.filter {
+                HtmlCollectionFilter::Children => {
+                    Box::new(root.shallow_child_nodes_iter())
+                },
+                _ => Box::new(root.deep_child_nodes_iter()),
+            };
+
+        Ok(elements
+            .filter(|element| self.filter.matches(element))
+            .collect())
+    }
+}
+
+#[napi]
+impl HtmlCollection {
+    /// Collections are only created by the tree, like in browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+
+    #[napi(getter)]
+    pub fn get_length(&self) -> Result<u32> {
+        let len = self.matched.len(|| self.search())?;
+
+        Ok(len.try_into().unwrap())
+    }
+
+    #[napi]
+    pub fn item(
+        &self,
+        index: i64,
+    ) -> Result<Option<Reference<Element>>> {
+        match usize::try_from(index) {
+            Ok(index) => self.matched.item(self.env, index, || self.search()),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// The first element with `name` as its `id` or `name` attribute.
+    #[napi]
+    pub fn named_item(
+        &self,
+        name: String,
+    ) -> Result<Option<Reference<Element>>> {
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        let elements = self
+            .matched
+            .get(|| self.search(), |elements| elements.to_vec())?;
+
+        for element in elements {
+            let element = element.upgrade(self.env)?;
+            let is_named = ["id", "name"].into_iter().any(|attribute| {
+                element.get_attribute(attribute.to_string()).as_ref()
+                    == Some(&name)
+            });
+
+            if is_named {
+                return Ok(Some(element));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,143 @@
+//! Index access on live collections, like `childNodes[0]`.
+//!
+//! napi classes can't have indexed properties, so collections are handed to
+//! JavaScript wrapped in a `Proxy` that reads indices through `item()` on
+//! every access. `item()` and `length` are cheap, as the collections keep
+//! what they matched until the tree changes.
+
+use napi::{
+    CallContext,
+    Env,
+    JsFunction,
+    JsObject,
+    JsString,
+    JsUnknown,
+    Result,
+    ValueType,
+};
+
+use crate::proxy::{
+    ProxyHandler,
+    ProxyHandlerCell,
+};
+
+thread_local! {
+    static HANDLER: ProxyHandlerCell = const { ProxyHandlerCell::new() };
+}
+
+static PROXY_HANDLER: ProxyHandler =
+    ProxyHandler::new(&HANDLER, &[("get", get), ("has", has)]);
+
+/// Wraps `target`, which must have `item()` and `length`, in a `Proxy` with
+/// index access.
+pub(crate) fn wrap(
+    env: Env,
+    target: JsObject,
+) -> Result<JsObject> {
+    PROXY_HANDLER.wrap(env, target)
+}
+
+#[js_function(2)]
+fn get(ctx: CallContext) -> Result<JsUnknown> {
+    let target: JsObject = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    if let Some(index) = as_index(&key)? {
+        let item: JsFunction = target.get_named_property("item")?;
+        let item =
+            item.call(Some(&target), &[ctx.env.create_uint32(index)?])?;
+
+        return match item.get_type()? {
+            ValueType::Null => Ok(ctx.env.get_undefined()?.into_unknown()),
+            _ => Ok(item),
+        };
+    }
+
+    // methods looked up before, see below
+    if target.has_own_property_js(ctx.get::<JsUnknown>(1)?)? {
+        return target.get_property(key);
+    }
+
+    let value: JsUnknown = target.get_property(key)?;
+
+    if value.get_type()? != ValueType::Function {
+        return Ok(value);
+    }
+
+    // The methods of the class can only be called on the collection itself,
+    // not on the proxy.
+    let method = match is_array_method(*ctx.env, ctx.get(1)?, &value)? {
+        true => value,
+        false => {
+            let method = value.coerce_to_object()?;
+            let bind: JsFunction = method.get_named_property("bind")?;
+            bind.call(Some(&method), &[&target])?
+        },
+    };
+
+    // Kept on the collection, so that they are only looked up once and are
+    // the same function on every access.
+    let object: JsFunction =
+        ctx.env.get_global()?.get_named_property("Object")?;
+    let define_property: JsFunction = object
+        .coerce_to_object()?
+        .get_named_property("defineProperty")?;
+    let mut descriptor = ctx.env.create_object()?;
+    descriptor.set_named_property("value", &method)?;
+    descriptor.set_named_property("configurable", true)?;
+    define_property.call(
+        None,
+        &[
+            target.into_unknown(),
+            ctx.get::<JsUnknown>(1)?,
+            descriptor.into_unknown(),
+        ],
+    )?;
+
+    Ok(method)
+}
+
+#[js_function(2)]
+fn has(ctx: CallContext) -> Result<bool> {
+    let target: JsObject = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    match as_index(&key)? {
+        Some(index) => {
+            let length: u32 = target.get_named_property("length")?;
+            Ok(index < length)
+        },
+        None => target.has_property_js(key),
+    }
+}
+
+/// Whether `value` is the method of `Array.prototype` with the same `key`,
+/// like the iteration methods shared with arrays. These read the collection
+/// through its indices, so they are called on the proxy.
+fn is_array_method(
+    env: Env,
+    key: JsUnknown,
+    value: &JsUnknown,
+) -> Result<bool> {
+    let array: JsFunction = env.get_global()?.get_named_property("Array")?;
+    let prototype: JsObject =
+        array.coerce_to_object()?.get_named_property("prototype")?;
+    let array_value: JsUnknown = prototype.get_property(key)?;
+
+    env.strict_equals(array_value, value)
+}
+
+/// The index `key` stands for, if it is the string of an array index.
+fn as_index(key: &JsUnknown) -> Result<Option<u32>> {
+    if key.get_type()? != ValueType::String {
+        return Ok(None);
+    }
+
+    let key = unsafe { key.cast::<JsString>() }.into_utf8()?;
+    let key = key.as_str()?;
+
+    Ok(key
+        .parse::<u32>()
+        .ok()
+        .filter(|index| *index != u32::MAX && index.to_string() == key))
+}
@@ -0,0 +1,125 @@
+mod html_collection;
+mod indexed_access;
+mod named_node_map;
+mod node_list;
+
+pub use html_collection::{
+    HtmlCollection,
+    HtmlCollectionFilter,
+};
+pub use named_node_map::NamedNodeMap;
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Result,
+};
+pub use node_list::NodeList;
+
+use crate::{
+    EinarCell,
+    Element,
+    NodeData,
+    WeakReference,
+};
+
+/// The elements a live collection matched, kept until the tree changes, so
+/// that reading `length` and every index in a loop searches the tree once.
+#[derive(Default)]
+struct MatchedElements(EinarCell<Option<(u64, Vec<WeakReference<Element>>)>>);
+
+impl MatchedElements {
+    /// The matched elements, found with `search` if the tree changed since
+    /// they last were.
+    fn get<R>(
+        &self,
+        search: impl FnOnce() -> Result<Vec<Reference<Element>>>,
+        f: impl FnOnce(&[WeakReference<Element>]) -> R,
+    ) -> Result<R> {
+        let version = NodeData::tree_version();
+
+        let is_current = self.0.borrow(|matched| {
+            matched
+                .as_ref()
+                .is_some_and(|(matched_version, _)| *matched_version == version)
+        });
+
+        if !is_current {
+            let elements = search()?
+                .iter()
+                .map(|element| element.downgrade().into())
+                .collect();
+            self.0.set(Some((version, elements)));
+        }
+
+        Ok(self.0.borrow(|matched| {
+            f(matched.as_ref().map_or(&[], |(_, elements)| elements))
+        }))
+    }
+
+    fn len(
+        &self,
+        search: impl FnOnce() -> Result<Vec<Reference<Element>>>,
+    ) -> Result<usize> {
+        self.get(search, <[_]>::len)
+    }
+
+    fn item(
+        &self,
+        env: Env,
+        index: usize,
+        search: impl FnOnce() -> Result<Vec<Reference<Element>>>,
+    ) -> Result<Option<Reference<Element>>> {
+        self.get(search, |elements| elements.get(index).cloned())?
+            .map(|element| element.upgrade(env))
+            .transpose()
+    }
+}
+
+/// Shares the iteration methods of arrays with the collections and
+/// `DOMTokenList`, like browsers do. They read the collections through their
+/// indices, see `indexed_access`.
+pub(crate) fn define_array_methods(
+    env: napi::Env,
+    exports: &napi::JsObject,
+) -> napi::Result<()> {
+    use napi::{
+        JsFunction,
+        JsObject,
+        JsUnknown,
+    };
+
+    let global = env.get_global()?;
+    let array: JsFunction = global.get_named_property("Array")?;
+    let array_prototype: JsObject =
+        array.coerce_to_object()?.get_named_property("prototype")?;
+    let symbol: JsFunction = global.get_named_property("Symbol")?;
+    let iterator: JsUnknown =
+        symbol.coerce_to_object()?.get_named_property("iterator")?;
+
+    for (class, methods) in [
+        ("NodeList", &["forEach", "entries", "keys", "values"][..]),
+        ("HTMLCollection", &[][..]),
+        ("NamedNodeMap", &[][..]),
+        (
+            "DOMTokenList",
+            &["forEach", "entries", "keys", "values"][..],
+        ),
+    ] {
+        let constructor: JsFunction = exports.get_named_property(class)?;
+        let mut prototype: JsObject = constructor
+            .coerce_to_object()?
+            .get_named_property("prototype")?;
+
+        for method in methods {
+            let function: JsFunction =
+                array_prototype.get_named_property(method)?;
+            prototype.set_named_property(method, function)?;
+        }
+
+        let values: JsFunction =
+            array_prototype.get_named_property("values")?;
+        prototype.set_property(&iterator, values)?;
+    }
+
+    Ok(())
+}
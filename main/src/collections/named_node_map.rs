@@ -0,0 +1,223 @@
+use html5ever::{
+    Attribute,
+    LocalName,
+    Namespace,
+};
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    JsObject,
+    Result,
+    Status,
+};
+
+use super::indexed_access;
+use crate::{
+    Attr,
+    DomException,
+    DomExceptionName,
+    Element,
+    WeakReference,
+};
+
+/// The attributes of an element, as returned by `attributes`.
+///
+/// The map is live: it reads the attributes of its element on every access,
+/// and its `Attr`s, one per attribute, write their value through to the
+/// element.
+#[napi]
+pub struct NamedNodeMap {
+    element: WeakReference<Element>,
+    env: Env,
+}
+
+impl NamedNodeMap {
+    /// Creates the map, wrapped for index access.
+    pub(crate) fn create(
+        env: Env,
+        element: WeakReference<Element>,
+    ) -> Result<JsObject> {
+        let map = Self {
+            element,
+            env,
+        }
+        .into_instance(env)?;
+
+        indexed_access::wrap(env, map.as_object(env))
+    }
+
+    /// The `Attr` of the attribute that `find` returns.
+    fn attr(
+        &self,
+        find: impl FnOnce(&Element) -> Option<&Attribute>,
+    ) -> Result<Option<Reference<Attr>>> {
+        let mut element = self.element.upgrade(self.env)?;
+
+        match find(&element).cloned() {
+            Some(attribute) => element.get_attr(&attribute).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Takes the attribute that `find` returns off the element.
+    fn remove(
+        &self,
+        find: impl FnOnce(&Element) -> Option<&Attribute>,
+    ) -> Result<Reference<Attr>> {
+        let mut element = self.element.upgrade(self.env)?;
+
+        let attribute = find(&element).cloned().ok_or_else(|| {
+            DomException::new(
+                DomExceptionName::NotFoundError,
+                "The attribute is not on the element",
+            )
+            .into_error(self.env)
+        })?;
+
+        let attr = element.get_attr(&attribute)?;
+        element.remove_attribute_node(&attribute.name)?;
+
+        Ok(attr)
+    }
+}
+
+#[napi]
+impl NamedNodeMap {
+    /// Maps are only created by the tree, like in browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+
+    #[napi(getter)]
+    pub fn get_length(&self) -> Result<u32> {
+        let element = self.element.upgrade(self.env)?;
+
+        Ok(element.attributes_wrapper.len().try_into().unwrap())
+    }
+
+    #[napi]
+    pub fn item(
+        &self,
+        index: i64,
+    ) -> Result<Option<Reference<Attr>>> {
+        let index = match usize::try_from(index) {
+            Ok(index) => index,
+            Err(_) => return Ok(None),
+        };
+
+        self.attr(|element| element.attributes_wrapper.get(index))
+    }
+
+    #[napi]
+    pub fn get_named_item(
+        &self,
+        qualified_name: String,
+    ) -> Result<Option<Reference<Attr>>> {
+        self.attr(|element| {
+            let qualified_name =
+                element.normalize_attribute_name(qualified_name);
+
+            element
+                .attributes_wrapper
+                .get_attribute_by_qualified_name(&qualified_name)
+        })
+    }
+
+    #[napi(js_name = "getNamedItemNS")]
+    pub fn get_named_item_ns(
+        &self,
+        namespace: Option<String>,
+        local_name: String,
+    ) -> Result<Option<Reference<Attr>>> {
+        let namespace = Namespace::from(namespace.unwrap_or_default());
+        let local_name = LocalName::from(local_name);
+
+        self.attr(|element| {
+            element
+                .attributes_wrapper
+                .get_attribute_ns(&namespace, &local_name)
+        })
+    }
+
+    /// Puts `attr` on the element, replacing the attribute with the same
+    /// namespace and local name, whose `Attr` is returned.
+    #[napi]
+    pub fn set_named_item(
+        &self,
+        mut attr: Reference<Attr>,
+    ) -> Result<Option<Reference<Attr>>> {
+        let mut element = self.element.upgrade(self.env)?;
+
+        if let Some(owner) = attr.get_owner_element()? {
+            if owner.id != element.id {
+                return Err(DomException::new(
+                    DomExceptionName::InUseAttributeError,
+                    "The attribute is already on another element",
+                )
+                .into_error(self.env));
+            }
+
+            return Ok(Some(attr));
+        }
+
+        let name = attr.qual_name();
+        let previous = element
+            .attributes_wrapper
+            .get_attribute_ns(&name.ns, &name.local)
+            .cloned();
+        let previous_attr = previous
+            .as_ref()
+            .map(|attribute| element.get_attr(attribute))
+            .transpose()?;
+
+        element.set_attribute_node(attr.to_attribute()?)?;
+        attr.attach(self.element.clone());
+        element.lazy_attrs.replace(
+            attr.clone(self.env)?,
+            previous.map(|attribute| attribute.value),
+        );
+
+        Ok(previous_attr)
+    }
+
+    #[napi(js_name = "setNamedItemNS")]
+    pub fn set_named_item_ns(
+        &self,
+        attr: Reference<Attr>,
+    ) -> Result<Option<Reference<Attr>>> {
+        self.set_named_item(attr)
+    }
+
+    #[napi]
+    pub fn remove_named_item(
+        &self,
+        qualified_name: String,
+    ) -> Result<Reference<Attr>> {
+        self.remove(|element| {
+            let qualified_name =
+                element.normalize_attribute_name(qualified_name);
+
+            element
+                .attributes_wrapper
+                .get_attribute_by_qualified_name(&qualified_name)
+        })
+    }
+
+    #[napi(js_name = "removeNamedItemNS")]
+    pub fn remove_named_item_ns(
+        &self,
+        namespace: Option<String>,
+        local_name: String,
+    ) -> Result<Reference<Attr>> {
+        let namespace = Namespace::from(namespace.unwrap_or_default());
+        let local_name = LocalName::from(local_name);
+
+        self.remove(|element| {
+            element
+                .attributes_wrapper
+                .get_attribute_ns(&namespace, &local_name)
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    JsObject,
+    Result,
+    Status,
+};
+
+use super::{
+    indexed_access,
+    HtmlCollectionFilter,
+    MatchedElements,
+};
+use crate::{
+    ChildNode,
+    Element,
+    NodeData,
+    ParentNode,
+};
+
+/// The child nodes of a node, as returned by `childNodes`, or the elements
+/// below it that match a filter, as returned by `getElementsByName()`.
+///
+/// The list is live: it reads the child nodes on every access, and searches
+/// the tree below its parent again once the tree changes.
+#[napi]
+pub struct NodeList {
+    parent: ParentNode,
+    filter: Option<HtmlCollectionFilter>,
+    matched: MatchedElements,
+    env: Env,
+}
+
+impl NodeList {
+    /// Creates the list, wrapped for index access.
+    pub(crate) fn create(
+        env: Env,
+        parent: ParentNode,
+        filter: Option<HtmlCollectionFilter>,
+    ) -> Result<JsObject> {
+        let list = Self {
+            parent,
+            filter,
+            matched: Default::default(),
+            env,
+        }
+        .into_instance(env)?;
+
+        indexed_access::wrap(env, list.as_object(env))
+    }
+
+    fn search(
+        &self,
+        filter: &HtmlCollectionFilter,
+    ) -> Result<Vec<Reference<Element>>> {
+        let parent = self.parent.upgrade(self.env)?;
+
+        Ok(parent
+            .deep_child_nodes_iter::<Reference<Element>>()
+            .filter(|element| filter.matches(element))
+            .collect())
+    }
+}
+
+#[napi]
+impl NodeList {
+    /// Collections are only created by the tree, like in browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+
+    #[napi(getter)]
+    pub fn get_length(&self) -> Result<u32> {
+        if let Some(filter) = &self.filter {
+            let len = self.matched.len(|| self.search(filter))?;
+            return Ok(len.try_into().unwrap());
+        }
+
+        let parent = self.parent.upgrade(self.env)?;
+        let len = NodeData::from(&parent).child_nodes.borrow(|c| c.len());
+
+        Ok(len.try_into().unwrap())
+    }
+
+    #[napi]
+    pub fn item(
+        &self,
+        index: i64,
+    ) -> Result<Option<ChildNode>> {
+        let index = match usize::try_from(index) {
+            Ok(index) => index,
+            Err(_) => return Ok(None),
+        };
+
+        if let Some(filter) = &self.filter {
+            let element =
+                self.matched.item(self.env, index, || self.search(filter))?;
+            return Ok(element.map(ChildNode::from));
+        }
+
+        let parent = self.parent.upgrade(self.env)?;
+
+        Ok(NodeData::from(&parent)
+            .child_nodes
+            .borrow(|child_nodes| child_nodes.get(index).cloned()))
+    }
+}
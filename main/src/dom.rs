@@ -11,10 +11,15 @@ use html5ever::{
 use napi::{
     bindgen_prelude::Reference,
     Env,
+    Error,
     Result,
+    Status,
 };
+use url::Url;
+use xml5ever::driver::XmlParseOpts;
 
 use crate::{
+    about_blank,
     serialize,
     ChildNode,
     Comment,
@@ -30,6 +35,11 @@ use crate::{
     Window,
 };
 
+/// The namespace of the element replacing documents with XML errors, the
+/// same as in browsers.
+const PARSER_ERROR_NAMESPACE: &str =
+    "http://www.mozilla.org/newlayout/xml/parsererror.xml";
+
 #[napi]
 pub struct Html5everDom {
     document_reference: Reference<Document>,
@@ -38,20 +48,46 @@ pub struct Html5everDom {
     #[napi(writable = false)]
     pub errors: Vec<String>,
 
+    /// The node document of the created nodes when parsing a fragment for
+    /// an existing document.
+    owner_document: Option<Reference<Document>>,
+
+    /// Processing instructions, which are left out of the tree as there are
+    /// no nodes for them.
+    skipped_nodes: Vec<Node>,
+
     env: Env,
 }
 
 #[napi]
 impl Html5everDom {
+    /// Parses `html` into a document with the URL `url`, `about:blank` by
+    /// default, which relative URLs in it resolve against.
     #[napi(constructor)]
     pub fn new(
         env: Env,
         html: Option<String>,
+        url: Option<String>,
     ) -> Result<Html5everDom> {
+        let url = match url {
+            Some(url) => match Url::parse(&url) {
+                Ok(url) => url,
+                Err(_) => {
+                    env.throw_type_error(
+                        &format!("Invalid URL: {}", url),
+                        None,
+                    )?;
+                    return Err(Error::from_status(Status::PendingException));
+                },
+            },
+            None => about_blank(),
+        };
         let html: String = html.unwrap_or_default();
-        let dom: Html5everDom =
-            parse_document(Self::create_sink(env)?, ParseOpts::default())
-                .one(html);
+        let dom: Html5everDom = parse_document(
+            Self::create_sink(env, None, url, "text/html")?,
+            ParseOpts::default(),
+        )
+        .one(html);
 
         Ok(dom)
     }
@@ -77,28 +113,96 @@ impl Html5everDom {
         node: Node,
         html: String,
     ) -> Result<()> {
+        let child_nodes = Self::parse_fragment_nodes(env, &node, html)?;
+
+        node.insert_nodes(env, child_nodes, &InsertPosition::Append)
+    }
+
+    /// Parses `html` as the children of `context`, without inserting them.
+    pub(crate) fn parse_fragment_nodes(
+        env: Env,
+        context: &Node,
+        html: String,
+    ) -> Result<Vec<ChildNode>> {
         let dom: Html5everDom = parse_fragment(
-            Self::create_sink(env)?,
+            Self::create_sink(
+                env,
+                context.get_node_document()?,
+                about_blank(),
+                "text/html",
+            )?,
             ParseOpts::default(),
-            node.get_qual_name(),
+            context.get_qual_name(),
             Vec::new(),
         )
         .one(html);
 
-        let document_node: Node =
-            dom.document_reference.get_document_element()?.into();
+        // the nodes are parsed into an `html` element, the document element
+        let root: Node = match dom.document_reference.get_document_element() {
+            Some(root) => root.into(),
+            None => return Ok(vec![]),
+        };
 
-        let tmp: Vec<ChildNode> =
-            document_node.shallow_child_nodes_iter().collect();
+        Ok(root.shallow_child_nodes_iter().collect())
+    }
 
-        node.insert_nodes(env, tmp, &InsertPosition::Append)?;
+    /// Parses `input` as a document of `content_type` without a window, like
+    /// the ones from `DOMParser`. Anything but `text/html` is parsed as XML,
+    /// and a document with XML errors only has a `parsererror` element, see
+    /// https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-domparser-parsefromstring
+    pub(crate) fn parse_detached_document(
+        env: Env,
+        input: String,
+        content_type: &str,
+    ) -> Result<Reference<Document>> {
+        let sink = Self::create_sink(env, None, about_blank(), content_type)?;
+
+        let dom: Html5everDom = match content_type {
+            "text/html" => {
+                parse_document(sink, ParseOpts::default()).one(input)
+            },
+            _ => {
+                xml5ever::driver::parse_document(sink, XmlParseOpts::default())
+                    .one(input)
+            },
+        };
 
-        Ok(())
+        let mut document = dom.document_reference;
+        document.window = None;
+
+        if content_type != "text/html" && !dom.errors.is_empty() {
+            let parser_error = document.create_element_ns(
+                Some(PARSER_ERROR_NAMESPACE.to_string()),
+                "parsererror".to_string(),
+            )?;
+            let text = document.create_text_node(dom.errors.join("\n"))?;
+            parser_error.as_node().append_child(env, &text.into())?;
+
+            let document_node = document.as_node();
+            while let Some(child_node) = document_node.get_child_node(0) {
+                child_node.remove()?;
+            }
+            document_node.append_child(env, &parser_error.into())?;
+        }
+
+        Ok(document)
     }
 
-    fn create_sink(env: Env) -> Result<Html5everDom> {
-        let document_reference =
-            Document::new_reference(env, QuirksMode::NoQuirks, None)?;
+    fn create_sink(
+        env: Env,
+        owner_document: Option<Reference<Document>>,
+        url: Url,
+        content_type: &str,
+    ) -> Result<Html5everDom> {
+        let document_reference = Document::new_reference(
+            env,
+            QuirksMode::NoQuirks,
+            None,
+            Default::default(),
+            url,
+            content_type.to_string(),
+            LazyReference::new(env),
+        )?;
         let window_reference =
             Window::new_reference(env, document_reference.clone(env)?)?;
 
@@ -109,12 +213,49 @@ impl Html5everDom {
             window_reference,
             document_reference,
             errors: vec![],
+            owner_document,
+            skipped_nodes: vec![],
             env,
         };
 
         Ok(sink)
     }
 
+    /// Sets the node document of a node created while parsing.
+    fn adopt_created(
+        &self,
+        node: Node,
+    ) -> Node {
+        let document = self
+            .owner_document
+            .as_ref()
+            .unwrap_or(&self.document_reference);
+        node.set_node_document(Some(document)).unwrap();
+
+        node
+    }
+
+    /// The node to insert for `child`, unless it is left out of the tree.
+    fn to_child_node(
+        &self,
+        child: NodeOrText<Node>,
+    ) -> Option<ChildNode> {
+        match child {
+            NodeOrText::AppendNode(node) => {
+                match self.skipped_nodes.contains(&node) {
+                    true => None,
+                    false => Some(node.into()),
+                }
+            },
+            NodeOrText::AppendText(content) => {
+                let r =
+                    Text::new_reference(self.env, content.to_string()).unwrap();
+                let text: Node = self.adopt_created(r.into());
+                Some(text.into())
+            },
+        }
+    }
+
     #[napi(getter)]
     pub fn get_window(&mut self) -> Result<Reference<Window>> {
         self.window_reference.clone(self.env)
@@ -171,22 +312,22 @@ impl TreeSink for Html5everDom {
         // TODO: set flags
         flags: html5ever::tree_builder::ElementFlags,
     ) -> Self::Handle {
-        let r = Element::new_reference(
+        let r = Element::create(
             self.env,
-            attrs.into(),
+            attrs,
             name,
-            LazyReference::new(self.env),
-            LazyReference::new(self.env),
             flags.template.then(|| {
-                DocumentFragment::new_reference(
+                let contents = DocumentFragment::new_reference(
                     self.env,
                     self.document_reference.quirks_mode,
                 )
-                .unwrap()
+                .unwrap();
+                self.adopt_created(contents.clone(self.env).unwrap().into());
+                contents
             }),
         )
         .unwrap();
-        r.into()
+        self.adopt_created(r.into())
     }
 
     fn create_comment(
@@ -194,7 +335,7 @@ impl TreeSink for Html5everDom {
         text: html5ever::tendril::StrTendril,
     ) -> Self::Handle {
         let r = Comment::new_reference(self.env, text.to_string()).unwrap();
-        r.into()
+        self.adopt_created(r.into())
     }
 
     fn create_pi(
@@ -202,7 +343,12 @@ impl TreeSink for Html5everDom {
         target: html5ever::tendril::StrTendril,
         data: html5ever::tendril::StrTendril,
     ) -> Self::Handle {
-        todo!()
+        let r =
+            Comment::new_reference(self.env, format!("?{} {}", target, data))
+                .unwrap();
+        let node = self.adopt_created(r.into());
+        self.skipped_nodes.push(node.clone());
+        node
     }
 
     fn append(
@@ -210,18 +356,11 @@ impl TreeSink for Html5everDom {
         parent: &Self::Handle,
         child: NodeOrText<Self::Handle>,
     ) {
-        let child: ChildNode = match child {
-            NodeOrText::AppendNode(node) => node.into(),
-            NodeOrText::AppendText(content) => {
-                let r =
-                    Text::new_reference(self.env, content.to_string()).unwrap();
-                r.into()
-            },
-        };
-
-        parent
-            .insert_node(self.env, child, &InsertPosition::Append)
-            .unwrap();
+        if let Some(child) = self.to_child_node(child) {
+            parent
+                .insert_node(self.env, child, &InsertPosition::Append)
+                .unwrap();
+        }
     }
 
     fn append_based_on_parent_node(
@@ -230,7 +369,10 @@ impl TreeSink for Html5everDom {
         prev_element: &Self::Handle,
         child: html5ever::tree_builder::NodeOrText<Self::Handle>,
     ) {
-        todo!()
+        match element.parent_as_node(self.env).unwrap() {
+            Some(_) => self.append_before_sibling(element, child),
+            None => self.append(prev_element, child),
+        }
     }
 
     fn append_doctype_to_document(
@@ -246,7 +388,7 @@ impl TreeSink for Html5everDom {
             system_id.to_string(),
         )
         .unwrap();
-        let doc_type: Node = r.into();
+        let doc_type: Node = self.adopt_created(r.into());
         let child = NodeOrText::AppendNode(doc_type);
         let node = self.get_document();
         self.append(&node, child);
@@ -287,7 +429,11 @@ impl TreeSink for Html5everDom {
         sibling: &Self::Handle,
         new_node: html5ever::tree_builder::NodeOrText<Self::Handle>,
     ) {
-        todo!()
+        if let Some(child) = self.to_child_node(new_node) {
+            sibling
+                .insert_node(self.env, child, &InsertPosition::Before)
+                .unwrap();
+        }
     }
 
     fn add_attrs_if_missing(
@@ -295,14 +441,30 @@ impl TreeSink for Html5everDom {
         target: &Self::Handle,
         attrs: Vec<html5ever::Attribute>,
     ) {
-        todo!()
+        let mut element = target.as_element().unwrap().clone(self.env).unwrap();
+
+        element
+            .update_attributes(|attributes| {
+                for attribute in attrs {
+                    if attributes
+                        .get_attribute_ns(
+                            &attribute.name.ns,
+                            &attribute.name.local,
+                        )
+                        .is_none()
+                    {
+                        attributes.push(attribute);
+                    }
+                }
+            })
+            .unwrap();
     }
 
     fn remove_from_parent(
         &mut self,
         target: &Self::Handle,
     ) {
-        todo!()
+        ChildNode::from(target.clone()).remove().unwrap();
     }
 
     fn reparent_children(
@@ -310,6 +472,15 @@ impl TreeSink for Html5everDom {
         node: &Self::Handle,
         new_parent: &Self::Handle,
     ) {
-        todo!()
+        let children: Vec<ChildNode> =
+            node.shallow_child_nodes_iter().collect();
+
+        for child in &children {
+            child.remove().unwrap();
+        }
+
+        new_parent
+            .insert_nodes(self.env, children, &InsertPosition::Append)
+            .unwrap();
     }
 }
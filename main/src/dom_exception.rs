@@ -0,0 +1,93 @@
+use napi::{
+    Env,
+    Error,
+    JsObject,
+    Result,
+    Status,
+};
+
+/// The `name` of a `DOMException`, with its legacy `code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomExceptionName {
+    IndexSizeError,
+    HierarchyRequestError,
+    InvalidCharacterError,
+    NotFoundError,
+    NotSupportedError,
+    InUseAttributeError,
+    SyntaxError,
+    NamespaceError,
+}
+
+impl DomExceptionName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomExceptionName::IndexSizeError => "IndexSizeError",
+            DomExceptionName::HierarchyRequestError => "HierarchyRequestError",
+            DomExceptionName::InvalidCharacterError => "InvalidCharacterError",
+            DomExceptionName::NotFoundError => "NotFoundError",
+            DomExceptionName::NotSupportedError => "NotSupportedError",
+            DomExceptionName::InUseAttributeError => "InUseAttributeError",
+            DomExceptionName::SyntaxError => "SyntaxError",
+            DomExceptionName::NamespaceError => "NamespaceError",
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            DomExceptionName::IndexSizeError => 1,
+            DomExceptionName::HierarchyRequestError => 3,
+            DomExceptionName::InvalidCharacterError => 5,
+            DomExceptionName::NotFoundError => 8,
+            DomExceptionName::NotSupportedError => 9,
+            DomExceptionName::InUseAttributeError => 10,
+            DomExceptionName::SyntaxError => 12,
+            DomExceptionName::NamespaceError => 14,
+        }
+    }
+}
+
+/// An error thrown to JavaScript with the `name` and `code` of a
+/// `DOMException`.
+///
+/// Node's own `DOMException` is not a native error, which napi only rethrows
+/// as is, so this is an `Error` with `name` and `code` set instead.
+pub struct DomException {
+    name: DomExceptionName,
+    message: String,
+}
+
+impl DomException {
+    pub fn new(
+        name: DomExceptionName,
+        message: impl ToString,
+    ) -> Self {
+        Self {
+            name,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn into_error(
+        self,
+        env: Env,
+    ) -> Error {
+        let message = self.message.clone();
+
+        self.create(env)
+            .map(|error| error.into_unknown().into())
+            .unwrap_or_else(|_| Error::new(Status::GenericFailure, message))
+    }
+
+    fn create(
+        self,
+        env: Env,
+    ) -> Result<JsObject> {
+        let mut error = env.create_error(Error::from_reason(self.message))?;
+
+        error.set_named_property("name", self.name.as_str())?;
+        error.set_named_property("code", self.name.code())?;
+
+        Ok(error)
+    }
+}
@@ -0,0 +1,54 @@
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    Result,
+    Status,
+};
+
+use crate::{
+    Document,
+    Html5everDom,
+};
+
+/// See https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#domparser
+#[napi(js_name = "DOMParser")]
+pub struct DomParser {
+    env: Env,
+}
+
+#[napi]
+impl DomParser {
+    #[napi(constructor)]
+    pub fn new(env: Env) -> Self {
+        Self {
+            env,
+        }
+    }
+
+    /// Parses `string` as HTML for `text/html` and as XML for the XML types,
+    /// into a document without a window.
+    #[napi]
+    pub fn parse_from_string(
+        &self,
+        string: String,
+        r#type: String,
+    ) -> Result<Reference<Document>> {
+        match r#type.as_str() {
+            "text/html"
+            | "text/xml"
+            | "application/xml"
+            | "application/xhtml+xml"
+            | "image/svg+xml" => {
+                Html5everDom::parse_detached_document(self.env, string, &r#type)
+            },
+            _ => {
+                self.env.throw_type_error(
+                    &format!("Unsupported type: {}", r#type),
+                    None,
+                )?;
+                Err(Error::from_status(Status::PendingException))
+            },
+        }
+    }
+}
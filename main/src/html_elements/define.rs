@@ -0,0 +1,235 @@
+use std::{
+    ffi::{
+        c_void,
+        CString,
+    },
+    mem::ManuallyDrop,
+    ptr,
+};
+
+use napi::{
+    bindgen_prelude::FromNapiValue,
+    check_status,
+    sys,
+    CallContext,
+    Env,
+    Error,
+    JsError,
+    JsFunction,
+    JsObject,
+    JsUnknown,
+    NapiRaw,
+    NapiValue,
+    Property,
+    PropertyAttributes,
+    Result,
+    Status,
+};
+
+use super::{
+    interfaces::{
+        HTML_ELEMENT,
+        INTERFACES,
+    },
+    properties::HtmlProperty,
+    Prototypes,
+    PROTOTYPES,
+};
+use crate::Element;
+
+/// Returns `this`, the first argument and the property of a call to an
+/// accessor.
+unsafe fn get_call_info(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> Result<(sys::napi_value, sys::napi_value, &'static HtmlProperty)> {
+    let mut argc = 1;
+    let mut arg = ptr::null_mut();
+    let mut this = ptr::null_mut();
+    let mut data = ptr::null_mut();
+
+    check_status!(sys::napi_get_cb_info(
+        env, info, &mut argc, &mut arg, &mut this, &mut data,
+    ))?;
+
+    Ok((this, arg, &*(data as *const HtmlProperty)))
+}
+
+unsafe extern "C" fn get_property(
+    raw_env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    let result =
+        get_call_info(raw_env, info).and_then(|(this, _, property)| {
+            let element = <&Element>::from_napi_value(raw_env, this)?;
+
+            property.get(element).map(|value| value.raw())
+        });
+
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            JsError::from(err).throw_into(raw_env);
+            ptr::null_mut()
+        },
+    }
+}
+
+unsafe extern "C" fn set_property(
+    raw_env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> sys::napi_value {
+    let result =
+        get_call_info(raw_env, info).and_then(|(this, value, property)| {
+            let element = <&mut Element>::from_napi_value(raw_env, this)?;
+
+            property.set(element, JsUnknown::from_raw_unchecked(raw_env, value))
+        });
+
+    if let Err(err) = result {
+        JsError::from(err).throw_into(raw_env);
+    }
+
+    // `undefined`
+    ptr::null_mut()
+}
+
+/// Defines the accessors of `properties` on `prototype`.
+fn define_properties(
+    env: Env,
+    prototype: &JsObject,
+    properties: &'static [HtmlProperty],
+) -> Result<()> {
+    let names = properties
+        .iter()
+        .map(|property| CString::new(property.name))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|err| Error::new(Status::InvalidArg, err.to_string()))?;
+
+    let descriptors = properties
+        .iter()
+        .zip(&names)
+        .map(|(property, name)| sys::napi_property_descriptor {
+            utf8name: name.as_ptr(),
+            name: ptr::null_mut(),
+            method: None,
+            getter: Some(get_property),
+            setter: match property.is_read_only() {
+                true => None,
+                false => Some(set_property),
+            },
+            value: ptr::null_mut(),
+            attributes: sys::PropertyAttributes::enumerable
+                | sys::PropertyAttributes::configurable,
+            data: property as *const HtmlProperty as *mut c_void,
+        })
+        .collect::<Vec<_>>();
+
+    check_status!(unsafe {
+        sys::napi_define_properties(
+            env.raw(),
+            prototype.raw(),
+            descriptors.len(),
+            descriptors.as_ptr(),
+        )
+    })
+}
+
+#[js_function]
+pub(crate) fn illegal_constructor(_ctx: CallContext) -> Result<()> {
+    Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+}
+
+/// Functions are objects too, for setting their properties.
+pub(crate) fn as_object(
+    env: Env,
+    function: &JsFunction,
+) -> JsObject {
+    unsafe { JsObject::from_raw_unchecked(env.raw(), function.raw()) }
+}
+
+/// Creates the constructor of the interface `name` with a prototype that
+/// inherits from the one of `parent`, like a subclass.
+fn create_interface(
+    env: Env,
+    name: &str,
+    parent: &JsFunction,
+) -> Result<(JsFunction, JsObject)> {
+    let object: JsFunction = env.get_global()?.get_named_property("Object")?;
+    let object = object.coerce_to_object()?;
+    let create: JsFunction = object.get_named_property("create")?;
+    let set_prototype_of: JsFunction =
+        object.get_named_property("setPrototypeOf")?;
+
+    let parent_prototype: JsObject =
+        as_object(env, parent).get_named_property("prototype")?;
+
+    let constructor = env.create_function(name, illegal_constructor)?;
+    let mut prototype: JsObject = create
+        .call(Some(&object), &[parent_prototype])?
+        .coerce_to_object()?;
+
+    prototype.define_properties(&[Property::new("constructor")?
+        .with_value(&constructor)
+        .with_property_attributes(
+            PropertyAttributes::Writable | PropertyAttributes::Configurable,
+        )])?;
+    as_object(env, &constructor).set_named_property("prototype", &prototype)?;
+    set_prototype_of.call(Some(&object), &[&constructor, parent])?;
+
+    Ok((constructor, prototype))
+}
+
+/// Creates `HTMLElement` and the interfaces of HTML elements, and returns
+/// their constructors.
+pub(crate) fn define_interfaces(
+    env: Env,
+    exports: &JsObject,
+) -> Result<Vec<(&'static str, JsFunction)>> {
+    let element: JsFunction = exports.get_named_property("Element")?;
+
+    let (html_element, prototype) =
+        create_interface(env, "HTMLElement", &element)?;
+    define_properties(env, &prototype, HTML_ELEMENT)?;
+
+    let mut constructors = vec![("HTMLElement", html_element)];
+    let mut prototypes =
+        vec![ManuallyDrop::new(env.create_reference(&prototype)?)];
+
+    for interface in INTERFACES {
+        let parent = interface.parent.unwrap_or("HTMLElement");
+        let parent = &constructors
+            .iter()
+            .find(|(name, _)| *name == parent)
+            .expect("the parent of an interface is defined before it")
+            .1;
+
+        let (constructor, prototype) =
+            create_interface(env, interface.name, parent)?;
+        for properties in interface.properties {
+            define_properties(env, &prototype, properties)?;
+        }
+
+        constructors.push((interface.name, constructor));
+        prototypes.push(ManuallyDrop::new(env.create_reference(&prototype)?));
+    }
+
+    let object: JsFunction = env.get_global()?.get_named_property("Object")?;
+    let set_prototype_of: JsFunction = object
+        .coerce_to_object()?
+        .get_named_property("setPrototypeOf")?;
+
+    PROTOTYPES.with(|cell| -> Result<()> {
+        cell.set(Prototypes {
+            prototypes,
+            set_prototype_of: ManuallyDrop::new(
+                env.create_reference(&set_prototype_of)?,
+            ),
+        })
+        .ok();
+
+        Ok(())
+    })?;
+
+    Ok(constructors)
+}
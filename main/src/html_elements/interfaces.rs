@@ -0,0 +1,1098 @@
+//! The HTML element interfaces with their properties, see
+//! https://html.spec.whatwg.org/#semantics
+
+use super::{
+    properties::{
+        self as p,
+        HtmlProperty,
+    },
+    reflect::Reflection::{
+        self,
+        *,
+    },
+};
+
+/// An interface of HTML elements, like `HTMLAnchorElement` for `a`.
+pub(crate) struct HtmlInterface {
+    pub(crate) name: &'static str,
+    /// The interface this one inherits from, other than `HTMLElement`.
+    pub(crate) parent: Option<&'static str>,
+    /// The local names of the elements with this interface.
+    pub(crate) tags: &'static [&'static str],
+    pub(crate) properties: &'static [&'static [HtmlProperty]],
+}
+
+const fn string(
+    name: &'static str,
+    attribute: &'static str,
+) -> HtmlProperty {
+    HtmlProperty::reflect(name, attribute, String)
+}
+
+const fn url(
+    name: &'static str,
+    attribute: &'static str,
+) -> HtmlProperty {
+    HtmlProperty::reflect(name, attribute, Url)
+}
+
+const fn boolean(
+    name: &'static str,
+    attribute: &'static str,
+) -> HtmlProperty {
+    HtmlProperty::reflect(name, attribute, Boolean)
+}
+
+const fn enumerated(
+    name: &'static str,
+    attribute: &'static str,
+    keywords: &'static [&'static str],
+    missing: Option<&'static str>,
+    invalid: &'static str,
+) -> HtmlProperty {
+    HtmlProperty::reflect(
+        name,
+        attribute,
+        Enumerated {
+            keywords,
+            missing,
+            invalid,
+        },
+    )
+}
+
+const fn number(
+    name: &'static str,
+    attribute: &'static str,
+    reflection: Reflection,
+) -> HtmlProperty {
+    HtmlProperty::reflect(name, attribute, reflection)
+}
+
+const CROSS_ORIGIN: &[&str] = &["anonymous", "use-credentials"];
+
+const REFERRER_POLICY: &[&str] = &[
+    "",
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "same-origin",
+    "origin",
+    "strict-origin",
+    "origin-when-cross-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+const LOADING: &[&str] = &["lazy", "eager"];
+
+const INPUT_TYPES: &[&str] = &[
+    "hidden",
+    "text",
+    "search",
+    "tel",
+    "url",
+    "email",
+    "password",
+    "date",
+    "month",
+    "week",
+    "time",
+    "datetime-local",
+    "number",
+    "range",
+    "color",
+    "checkbox",
+    "radio",
+    "file",
+    "submit",
+    "image",
+    "reset",
+    "button",
+];
+
+const fn cross_origin() -> HtmlProperty {
+    enumerated(
+        "crossOrigin",
+        "crossorigin",
+        CROSS_ORIGIN,
+        None,
+        "anonymous",
+    )
+}
+
+const fn referrer_policy() -> HtmlProperty {
+    enumerated(
+        "referrerPolicy",
+        "referrerpolicy",
+        REFERRER_POLICY,
+        Some(""),
+        "",
+    )
+}
+
+const fn loading() -> HtmlProperty {
+    enumerated("loading", "loading", LOADING, Some("eager"), "eager")
+}
+
+/// The properties all HTML elements have.
+pub(crate) const HTML_ELEMENT: &[HtmlProperty] = &[
+    string("title", "title"),
+    string("lang", "lang"),
+    enumerated("dir", "dir", &["ltr", "rtl", "auto"], Some(""), ""),
+    boolean("hidden", "hidden"),
+    boolean("inert", "inert"),
+    string("accessKey", "accesskey"),
+    boolean("autofocus", "autofocus"),
+    string("nonce", "nonce"),
+    HtmlProperty::computed(
+        "tabIndex",
+        p::get_tab_index,
+        Some(p::set_tab_index),
+    ),
+];
+
+/// The URL parts of hyperlinks, see
+/// https://html.spec.whatwg.org/#htmlhyperlinkelementutils
+const HYPERLINK: &[HtmlProperty] = &[
+    url("href", "href"),
+    HtmlProperty::computed("origin", p::get_origin, None),
+    HtmlProperty::computed("protocol", p::get_protocol, Some(p::set_protocol)),
+    HtmlProperty::computed("username", p::get_username, Some(p::set_username)),
+    HtmlProperty::computed("password", p::get_password, Some(p::set_password)),
+    HtmlProperty::computed("host", p::get_host, Some(p::set_host)),
+    HtmlProperty::computed("hostname", p::get_hostname, Some(p::set_hostname)),
+    HtmlProperty::computed("port", p::get_port, Some(p::set_port)),
+    HtmlProperty::computed("pathname", p::get_pathname, Some(p::set_pathname)),
+    HtmlProperty::computed("search", p::get_search, Some(p::set_search)),
+    HtmlProperty::computed("hash", p::get_hash, Some(p::set_hash)),
+    string("target", "target"),
+    string("download", "download"),
+    string("ping", "ping"),
+    string("rel", "rel"),
+    referrer_policy(),
+];
+
+/// The properties `input`, `textarea` and the other form controls share.
+const FORM_CONTROL: &[HtmlProperty] =
+    &[boolean("disabled", "disabled"), string("name", "name")];
+
+const MEDIA: &[HtmlProperty] = &[
+    url("src", "src"),
+    cross_origin(),
+    enumerated(
+        "preload",
+        "preload",
+        &["none", "metadata", "auto"],
+        Some("metadata"),
+        "auto",
+    ),
+    boolean("autoplay", "autoplay"),
+    boolean("loop", "loop"),
+    boolean("controls", "controls"),
+    boolean("defaultMuted", "muted"),
+];
+
+const TEXT: &[HtmlProperty] = &[HtmlProperty::computed(
+    "text",
+    p::get_text,
+    Some(p::set_text_value),
+)];
+
+/// The interfaces, with the ones others inherit from first.
+pub(crate) const INTERFACES: &[HtmlInterface] = &[
+    HtmlInterface {
+        name: "HTMLMediaElement",
+        parent: None,
+        tags: &[],
+        properties: &[MEDIA],
+    },
+    HtmlInterface {
+        name: "HTMLAnchorElement",
+        parent: None,
+        tags: &["a"],
+        properties: &[
+            HYPERLINK,
+            TEXT,
+            &[
+                string("hreflang", "hreflang"),
+                string("type", "type"),
+                string("name", "name"),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLAreaElement",
+        parent: None,
+        tags: &["area"],
+        properties: &[
+            HYPERLINK,
+            &[
+                string("alt", "alt"),
+                string("coords", "coords"),
+                string("shape", "shape"),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLAudioElement",
+        parent: Some("HTMLMediaElement"),
+        tags: &["audio"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLBaseElement",
+        parent: None,
+        tags: &["base"],
+        properties: &[&[
+            HtmlProperty::computed(
+                "href",
+                p::get_base_href,
+                Some(p::set_base_href),
+            ),
+            string("target", "target"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLBodyElement",
+        parent: None,
+        tags: &["body"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLBRElement",
+        parent: None,
+        tags: &["br"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLButtonElement",
+        parent: None,
+        tags: &["button"],
+        properties: &[
+            FORM_CONTROL,
+            &[
+                enumerated(
+                    "type",
+                    "type",
+                    &["submit", "reset", "button"],
+                    Some("submit"),
+                    "submit",
+                ),
+                string("value", "value"),
+                url("formAction", "formaction"),
+                boolean("formNoValidate", "formnovalidate"),
+                string("formTarget", "formtarget"),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLCanvasElement",
+        parent: None,
+        tags: &["canvas"],
+        properties: &[&[
+            number(
+                "width",
+                "width",
+                UnsignedLong {
+                    default: 300,
+                },
+            ),
+            number(
+                "height",
+                "height",
+                UnsignedLong {
+                    default: 150,
+                },
+            ),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLDataElement",
+        parent: None,
+        tags: &["data"],
+        properties: &[&[string("value", "value")]],
+    },
+    HtmlInterface {
+        name: "HTMLDataListElement",
+        parent: None,
+        tags: &["datalist"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLDetailsElement",
+        parent: None,
+        tags: &["details"],
+        properties: &[&[boolean("open", "open"), string("name", "name")]],
+    },
+    HtmlInterface {
+        name: "HTMLDialogElement",
+        parent: None,
+        tags: &["dialog"],
+        properties: &[&[boolean("open", "open")]],
+    },
+    HtmlInterface {
+        name: "HTMLDirectoryElement",
+        parent: None,
+        tags: &["dir"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLDivElement",
+        parent: None,
+        tags: &["div"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLDListElement",
+        parent: None,
+        tags: &["dl"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLEmbedElement",
+        parent: None,
+        tags: &["embed"],
+        properties: &[&[
+            url("src", "src"),
+            string("type", "type"),
+            string("width", "width"),
+            string("height", "height"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLFieldSetElement",
+        parent: None,
+        tags: &["fieldset"],
+        properties: &[FORM_CONTROL],
+    },
+    HtmlInterface {
+        name: "HTMLFontElement",
+        parent: None,
+        tags: &["font"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLFormElement",
+        parent: None,
+        tags: &["form"],
+        properties: &[&[
+            string("acceptCharset", "accept-charset"),
+            HtmlProperty::computed(
+                "action",
+                p::get_form_action,
+                Some(p::set_form_action),
+            ),
+            enumerated(
+                "autocomplete",
+                "autocomplete",
+                &["on", "off"],
+                Some("on"),
+                "on",
+            ),
+            enumerated(
+                "enctype",
+                "enctype",
+                &[
+                    "application/x-www-form-urlencoded",
+                    "multipart/form-data",
+                    "text/plain",
+                ],
+                Some("application/x-www-form-urlencoded"),
+                "application/x-www-form-urlencoded",
+            ),
+            enumerated(
+                "encoding",
+                "enctype",
+                &[
+                    "application/x-www-form-urlencoded",
+                    "multipart/form-data",
+                    "text/plain",
+                ],
+                Some("application/x-www-form-urlencoded"),
+                "application/x-www-form-urlencoded",
+            ),
+            enumerated(
+                "method",
+                "method",
+                &["get", "post", "dialog"],
+                Some("get"),
+                "get",
+            ),
+            string("name", "name"),
+            boolean("noValidate", "novalidate"),
+            string("target", "target"),
+            string("rel", "rel"),
+            HtmlProperty::computed("elements", p::get_form_elements, None),
+            HtmlProperty::computed("length", p::get_form_length, None),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLFrameElement",
+        parent: None,
+        tags: &["frame"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLFrameSetElement",
+        parent: None,
+        tags: &["frameset"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLHeadElement",
+        parent: None,
+        tags: &["head"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLHeadingElement",
+        parent: None,
+        tags: &["h1", "h2", "h3", "h4", "h5", "h6"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLHRElement",
+        parent: None,
+        tags: &["hr"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLHtmlElement",
+        parent: None,
+        tags: &["html"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLIFrameElement",
+        parent: None,
+        tags: &["iframe"],
+        properties: &[&[
+            url("src", "src"),
+            string("srcdoc", "srcdoc"),
+            string("name", "name"),
+            string("allow", "allow"),
+            boolean("allowFullscreen", "allowfullscreen"),
+            string("width", "width"),
+            string("height", "height"),
+            referrer_policy(),
+            loading(),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLImageElement",
+        parent: None,
+        tags: &["img"],
+        properties: &[&[
+            string("name", "name"),
+            string("alt", "alt"),
+            url("src", "src"),
+            string("srcset", "srcset"),
+            string("sizes", "sizes"),
+            cross_origin(),
+            string("useMap", "usemap"),
+            boolean("isMap", "ismap"),
+            number(
+                "width",
+                "width",
+                UnsignedLong {
+                    default: 0,
+                },
+            ),
+            number(
+                "height",
+                "height",
+                UnsignedLong {
+                    default: 0,
+                },
+            ),
+            referrer_policy(),
+            enumerated(
+                "decoding",
+                "decoding",
+                &["sync", "async", "auto"],
+                Some("auto"),
+                "auto",
+            ),
+            loading(),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLInputElement",
+        parent: None,
+        tags: &["input"],
+        properties: &[
+            FORM_CONTROL,
+            &[
+                string("accept", "accept"),
+                string("alt", "alt"),
+                string("autocomplete", "autocomplete"),
+                boolean("defaultChecked", "checked"),
+                HtmlProperty::computed(
+                    "checked",
+                    p::get_checked,
+                    Some(p::set_checked),
+                ),
+                string("dirName", "dirname"),
+                url("formAction", "formaction"),
+                boolean("formNoValidate", "formnovalidate"),
+                string("formTarget", "formtarget"),
+                string("max", "max"),
+                number(
+                    "maxLength",
+                    "maxlength",
+                    NonNegativeLong {
+                        default: -1,
+                    },
+                ),
+                string("min", "min"),
+                number(
+                    "minLength",
+                    "minlength",
+                    NonNegativeLong {
+                        default: -1,
+                    },
+                ),
+                boolean("multiple", "multiple"),
+                string("pattern", "pattern"),
+                string("placeholder", "placeholder"),
+                boolean("readOnly", "readonly"),
+                boolean("required", "required"),
+                number(
+                    "size",
+                    "size",
+                    PositiveUnsignedLong {
+                        default: 20,
+                    },
+                ),
+                url("src", "src"),
+                string("step", "step"),
+                enumerated("type", "type", INPUT_TYPES, Some("text"), "text"),
+                string("defaultValue", "value"),
+                HtmlProperty::computed(
+                    "value",
+                    p::get_input_value,
+                    Some(p::set_input_value),
+                ),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLLabelElement",
+        parent: None,
+        tags: &["label"],
+        properties: &[&[string("htmlFor", "for")]],
+    },
+    HtmlInterface {
+        name: "HTMLLegendElement",
+        parent: None,
+        tags: &["legend"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLLIElement",
+        parent: None,
+        tags: &["li"],
+        properties: &[&[number(
+            "value",
+            "value",
+            Long {
+                default: 0,
+            },
+        )]],
+    },
+    HtmlInterface {
+        name: "HTMLLinkElement",
+        parent: None,
+        tags: &["link"],
+        properties: &[&[
+            url("href", "href"),
+            cross_origin(),
+            string("rel", "rel"),
+            string("as", "as"),
+            string("media", "media"),
+            string("integrity", "integrity"),
+            string("hreflang", "hreflang"),
+            string("type", "type"),
+            referrer_policy(),
+            boolean("disabled", "disabled"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLMapElement",
+        parent: None,
+        tags: &["map"],
+        properties: &[&[string("name", "name")]],
+    },
+    HtmlInterface {
+        name: "HTMLMarqueeElement",
+        parent: None,
+        tags: &["marquee"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLMenuElement",
+        parent: None,
+        tags: &["menu"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLMetaElement",
+        parent: None,
+        tags: &["meta"],
+        properties: &[&[
+            string("name", "name"),
+            string("httpEquiv", "http-equiv"),
+            string("content", "content"),
+            string("media", "media"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLMeterElement",
+        parent: None,
+        tags: &["meter"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLModElement",
+        parent: None,
+        tags: &["ins", "del"],
+        properties: &[&[url("cite", "cite"), string("dateTime", "datetime")]],
+    },
+    HtmlInterface {
+        name: "HTMLObjectElement",
+        parent: None,
+        tags: &["object"],
+        properties: &[&[
+            url("data", "data"),
+            string("type", "type"),
+            string("name", "name"),
+            string("useMap", "usemap"),
+            string("width", "width"),
+            string("height", "height"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLOListElement",
+        parent: None,
+        tags: &["ol"],
+        properties: &[&[
+            boolean("reversed", "reversed"),
+            number(
+                "start",
+                "start",
+                Long {
+                    default: 1,
+                },
+            ),
+            string("type", "type"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLOptGroupElement",
+        parent: None,
+        tags: &["optgroup"],
+        properties: &[&[
+            boolean("disabled", "disabled"),
+            string("label", "label"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLOptionElement",
+        parent: None,
+        tags: &["option"],
+        properties: &[&[
+            boolean("disabled", "disabled"),
+            HtmlProperty::computed(
+                "label",
+                p::get_option_label,
+                Some(p::set_option_label),
+            ),
+            boolean("defaultSelected", "selected"),
+            HtmlProperty::computed(
+                "selected",
+                p::get_selected,
+                Some(p::set_selected),
+            ),
+            HtmlProperty::computed(
+                "value",
+                p::get_option_value,
+                Some(p::set_option_value),
+            ),
+            HtmlProperty::computed(
+                "text",
+                p::get_option_text,
+                Some(p::set_text_value),
+            ),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLOutputElement",
+        parent: None,
+        tags: &["output"],
+        properties: &[&[string("name", "name")]],
+    },
+    HtmlInterface {
+        name: "HTMLParagraphElement",
+        parent: None,
+        tags: &["p"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLParamElement",
+        parent: None,
+        tags: &["param"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLPictureElement",
+        parent: None,
+        tags: &["picture"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLPreElement",
+        parent: None,
+        tags: &["pre", "listing", "xmp"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLProgressElement",
+        parent: None,
+        tags: &["progress"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLQuoteElement",
+        parent: None,
+        tags: &["blockquote", "q"],
+        properties: &[&[url("cite", "cite")]],
+    },
+    HtmlInterface {
+        name: "HTMLScriptElement",
+        parent: None,
+        tags: &["script"],
+        properties: &[
+            TEXT,
+            &[
+                url("src", "src"),
+                string("type", "type"),
+                boolean("noModule", "nomodule"),
+                boolean("async", "async"),
+                boolean("defer", "defer"),
+                cross_origin(),
+                string("integrity", "integrity"),
+                referrer_policy(),
+                string("charset", "charset"),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLSelectElement",
+        parent: None,
+        tags: &["select"],
+        properties: &[
+            FORM_CONTROL,
+            &[
+                string("autocomplete", "autocomplete"),
+                boolean("multiple", "multiple"),
+                boolean("required", "required"),
+                number(
+                    "size",
+                    "size",
+                    UnsignedLong {
+                        default: 0,
+                    },
+                ),
+                HtmlProperty::computed("type", p::get_select_type, None),
+                HtmlProperty::computed("options", p::get_options, None),
+                HtmlProperty::computed("length", p::get_options_length, None),
+                HtmlProperty::computed(
+                    "selectedIndex",
+                    p::get_selected_index,
+                    None,
+                ),
+                HtmlProperty::computed(
+                    "value",
+                    p::get_select_value,
+                    Some(p::set_select_value),
+                ),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLSlotElement",
+        parent: None,
+        tags: &["slot"],
+        properties: &[&[string("name", "name")]],
+    },
+    HtmlInterface {
+        name: "HTMLSourceElement",
+        parent: None,
+        tags: &["source"],
+        properties: &[&[
+            url("src", "src"),
+            string("type", "type"),
+            string("srcset", "srcset"),
+            string("sizes", "sizes"),
+            string("media", "media"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLSpanElement",
+        parent: None,
+        tags: &["span"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLStyleElement",
+        parent: None,
+        tags: &["style"],
+        properties: &[&[string("media", "media")]],
+    },
+    HtmlInterface {
+        name: "HTMLTableCaptionElement",
+        parent: None,
+        tags: &["caption"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLTableCellElement",
+        parent: None,
+        tags: &["td", "th"],
+        properties: &[&[
+            number(
+                "colSpan",
+                "colspan",
+                ClampedUnsignedLong {
+                    default: 1,
+                    min: 1,
+                    max: 1000,
+                },
+            ),
+            number(
+                "rowSpan",
+                "rowspan",
+                ClampedUnsignedLong {
+                    default: 1,
+                    min: 0,
+                    max: 65534,
+                },
+            ),
+            string("headers", "headers"),
+            enumerated(
+                "scope",
+                "scope",
+                &["row", "col", "rowgroup", "colgroup"],
+                Some(""),
+                "",
+            ),
+            string("abbr", "abbr"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLTableColElement",
+        parent: None,
+        tags: &["col", "colgroup"],
+        properties: &[&[number(
+            "span",
+            "span",
+            ClampedUnsignedLong {
+                default: 1,
+                min: 1,
+                max: 1000,
+            },
+        )]],
+    },
+    HtmlInterface {
+        name: "HTMLTableElement",
+        parent: None,
+        tags: &["table"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLTableRowElement",
+        parent: None,
+        tags: &["tr"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLTableSectionElement",
+        parent: None,
+        tags: &["tbody", "thead", "tfoot"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLTemplateElement",
+        parent: None,
+        tags: &["template"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLTextAreaElement",
+        parent: None,
+        tags: &["textarea"],
+        properties: &[
+            FORM_CONTROL,
+            &[
+                string("autocomplete", "autocomplete"),
+                number(
+                    "cols",
+                    "cols",
+                    PositiveUnsignedLong {
+                        default: 20,
+                    },
+                ),
+                number(
+                    "rows",
+                    "rows",
+                    PositiveUnsignedLong {
+                        default: 2,
+                    },
+                ),
+                string("dirName", "dirname"),
+                number(
+                    "maxLength",
+                    "maxlength",
+                    NonNegativeLong {
+                        default: -1,
+                    },
+                ),
+                number(
+                    "minLength",
+                    "minlength",
+                    NonNegativeLong {
+                        default: -1,
+                    },
+                ),
+                string("placeholder", "placeholder"),
+                boolean("readOnly", "readonly"),
+                boolean("required", "required"),
+                string("wrap", "wrap"),
+                HtmlProperty::computed(
+                    "defaultValue",
+                    p::get_textarea_value,
+                    Some(p::set_text_value),
+                ),
+                HtmlProperty::computed(
+                    "value",
+                    p::get_textarea_value,
+                    Some(p::set_text_value),
+                ),
+            ],
+        ],
+    },
+    HtmlInterface {
+        name: "HTMLTimeElement",
+        parent: None,
+        tags: &["time"],
+        properties: &[&[string("dateTime", "datetime")]],
+    },
+    HtmlInterface {
+        name: "HTMLTitleElement",
+        parent: None,
+        tags: &["title"],
+        properties: &[TEXT],
+    },
+    HtmlInterface {
+        name: "HTMLTrackElement",
+        parent: None,
+        tags: &["track"],
+        properties: &[&[
+            url("src", "src"),
+            string("srclang", "srclang"),
+            string("label", "label"),
+            boolean("default", "default"),
+        ]],
+    },
+    HtmlInterface {
+        name: "HTMLUListElement",
+        parent: None,
+        tags: &["ul"],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLUnknownElement",
+        parent: None,
+        tags: &[],
+        properties: &[],
+    },
+    HtmlInterface {
+        name: "HTMLVideoElement",
+        parent: Some("HTMLMediaElement"),
+        tags: &["video"],
+        properties: &[&[
+            number(
+                "width",
+                "width",
+                UnsignedLong {
+                    default: 0,
+                },
+            ),
+            number(
+                "height",
+                "height",
+                UnsignedLong {
+                    default: 0,
+                },
+            ),
+            url("poster", "poster"),
+            boolean("playsInline", "playsinline"),
+        ]],
+    },
+];
+
+/// The elements in the HTML standard that only have the `HTMLElement`
+/// interface, see https://html.spec.whatwg.org/#element-interfaces
+pub(crate) const PLAIN_HTML_ELEMENTS: &[&str] = &[
+    "abbr",
+    "acronym",
+    "address",
+    "article",
+    "aside",
+    "b",
+    "basefont",
+    "bdi",
+    "bdo",
+    "big",
+    "center",
+    "cite",
+    "code",
+    "dd",
+    "dfn",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "header",
+    "hgroup",
+    "i",
+    "kbd",
+    "main",
+    "mark",
+    "nav",
+    "nobr",
+    "noembed",
+    "noframes",
+    "noscript",
+    "plaintext",
+    "rb",
+    "rp",
+    "rt",
+    "rtc",
+    "ruby",
+    "s",
+    "samp",
+    "search",
+    "section",
+    "small",
+    "strike",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "tt",
+    "u",
+    "var",
+    "wbr",
+];
@@ -0,0 +1,137 @@
+//! The interfaces of HTML elements, like `HTMLInputElement`, with their
+//! properties, most of which reflect an attribute.
+//!
+//! All elements are instances of the one `Element` class, so an HTML element
+//! gets the prototype of its interface instead, which inherits from the one
+//! of `HTMLElement` and then `Element`. The prototypes are created when the
+//! module is loaded, see `define_interfaces`.
+
+mod define;
+mod interfaces;
+mod properties;
+mod reflect;
+
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    mem::ManuallyDrop,
+};
+
+pub(crate) use define::{
+    as_object,
+    define_interfaces,
+    illegal_constructor,
+};
+use html5ever::{
+    namespace_url,
+    ns,
+    QualName,
+};
+use interfaces::{
+    INTERFACES,
+    PLAIN_HTML_ELEMENTS,
+};
+use lazy_static::lazy_static;
+use napi::{
+    bindgen_prelude::{
+        Reference,
+        ToNapiValue,
+    },
+    Env,
+    JsFunction,
+    JsObject,
+    NapiValue,
+    Ref,
+    Result,
+};
+
+use crate::Element;
+
+/// The prototypes of the interfaces, and `Object.setPrototypeOf()` to give
+/// them to elements.
+struct Prototypes {
+    /// `HTMLElement.prototype`, then the ones of `INTERFACES`, in order.
+    prototypes: Vec<ManuallyDrop<Ref<()>>>,
+    set_prototype_of: ManuallyDrop<Ref<()>>,
+}
+
+thread_local! {
+    // lives as long as the env of the thread, so it is never released
+    static PROTOTYPES: OnceCell<Prototypes> = const { OnceCell::new() };
+}
+
+lazy_static! {
+    /// The index in `INTERFACES` of the interface of each element.
+    static ref INTERFACES_BY_TAG: HashMap<&'static str, usize> = INTERFACES
+        .iter()
+        .enumerate()
+        .flat_map(|(index, interface)| {
+            interface.tags.iter().map(move |tag| (*tag, index))
+        })
+        .collect();
+}
+
+/// The index in `Prototypes::prototypes` of the prototype of the element
+/// `name`, unless it is not an HTML element.
+fn prototype_index(name: &QualName) -> Option<usize> {
+    if name.ns != ns!(html) {
+        return None;
+    }
+
+    let local_name: &str = &name.local;
+
+    if let Some(index) = INTERFACES_BY_TAG.get(local_name) {
+        return Some(index + 1);
+    }
+
+    // custom elements are `HTMLElement`s until they are defined
+    if PLAIN_HTML_ELEMENTS.contains(&local_name) || local_name.contains('-') {
+        return Some(0);
+    }
+
+    INTERFACES
+        .iter()
+        .position(|interface| interface.name == "HTMLUnknownElement")
+        .map(|index| index + 1)
+}
+
+/// Gives a new element the prototype of its interface. Without the module
+/// loaded, like in unit tests, elements keep the one of `Element`.
+pub(crate) fn set_interface(
+    env: Env,
+    element: &Reference<Element>,
+) -> Result<()> {
+    let index = match prototype_index(&element.name) {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+
+    let functions = PROTOTYPES.with(|prototypes| {
+        prototypes
+            .get()
+            .map(|prototypes| -> Result<(JsObject, JsFunction)> {
+                Ok((
+                    env.get_reference_value(&prototypes.prototypes[index])?,
+                    env.get_reference_value(&prototypes.set_prototype_of)?,
+                ))
+            })
+            .transpose()
+    })?;
+
+    let (prototype, set_prototype_of) = match functions {
+        Some(functions) => functions,
+        None => return Ok(()),
+    };
+
+    let object = unsafe {
+        let ptr = <Reference<Element> as ToNapiValue>::to_napi_value(
+            env.raw(),
+            element.clone(env)?,
+        )?;
+        JsObject::from_raw(env.raw(), ptr)?
+    };
+
+    set_prototype_of.call(None, &[object, prototype])?;
+
+    Ok(())
+}
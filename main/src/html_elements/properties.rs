@@ -0,0 +1,553 @@
+//! The properties of the HTML element interfaces, which either reflect an
+//! attribute or are computed from the element.
+
+use html5ever::{
+    expanded_name,
+    local_name,
+    namespace_url,
+    ns,
+};
+use napi::{
+    bindgen_prelude::Reference,
+    Either,
+    Env,
+    JsUnknown,
+    Result,
+};
+use url::{
+    quirks,
+    Url,
+};
+
+use super::reflect::Reflection;
+use crate::{
+    about_blank,
+    Element,
+    HtmlCollection,
+    HtmlCollectionFilter,
+    Node,
+};
+
+type Getter = fn(&Element) -> Result<JsUnknown>;
+type Setter = fn(&mut Element, JsUnknown) -> Result<()>;
+
+pub(crate) enum PropertyKind {
+    Reflect {
+        attribute: &'static str,
+        reflection: Reflection,
+    },
+    Computed {
+        get: Getter,
+        set: Option<Setter>,
+    },
+}
+
+/// A property of an HTML element interface, like `href` of
+/// `HTMLAnchorElement`.
+pub(crate) struct HtmlProperty {
+    pub(crate) name: &'static str,
+    pub(crate) kind: PropertyKind,
+}
+
+impl HtmlProperty {
+    pub(crate) const fn reflect(
+        name: &'static str,
+        attribute: &'static str,
+        reflection: Reflection,
+    ) -> Self {
+        Self {
+            name,
+            kind: PropertyKind::Reflect {
+                attribute,
+                reflection,
+            },
+        }
+    }
+
+    pub(crate) const fn computed(
+        name: &'static str,
+        get: Getter,
+        set: Option<Setter>,
+    ) -> Self {
+        Self {
+            name,
+            kind: PropertyKind::Computed {
+                get,
+                set,
+            },
+        }
+    }
+
+    pub(crate) fn is_read_only(&self) -> bool {
+        matches!(
+            self.kind,
+            PropertyKind::Computed {
+                set: None,
+                ..
+            }
+        )
+    }
+
+    pub(crate) fn get(
+        &self,
+        element: &Element,
+    ) -> Result<JsUnknown> {
+        match &self.kind {
+            PropertyKind::Reflect {
+                attribute,
+                reflection,
+            } => reflection.get(element, attribute),
+            PropertyKind::Computed {
+                get, ..
+            } => get(element),
+        }
+    }
+
+    pub(crate) fn set(
+        &self,
+        element: &mut Element,
+        value: JsUnknown,
+    ) -> Result<()> {
+        match &self.kind {
+            PropertyKind::Reflect {
+                attribute,
+                reflection,
+            } => reflection.set(element, attribute, value),
+            PropertyKind::Computed {
+                set: Some(set), ..
+            } => set(element, value),
+            // like a getter without a setter in a class, in sloppy mode
+            PropertyKind::Computed {
+                set: None, ..
+            } => Ok(()),
+        }
+    }
+}
+
+fn string(
+    env: Env,
+    value: &str,
+) -> Result<JsUnknown> {
+    Ok(env.create_string(value)?.into_unknown())
+}
+
+fn boolean(
+    env: Env,
+    value: bool,
+) -> Result<JsUnknown> {
+    Ok(env.get_boolean(value)?.into_unknown())
+}
+
+fn to_string(value: JsUnknown) -> Result<String> {
+    value.coerce_to_string()?.into_utf8()?.into_owned()
+}
+
+fn to_bool(value: JsUnknown) -> Result<bool> {
+    value.coerce_to_bool()?.get_value()
+}
+
+fn attribute(
+    element: &Element,
+    name: &str,
+) -> Option<String> {
+    element
+        .attributes_wrapper
+        .get_attribute(name.into())
+        .map(|attribute| attribute.value.to_string())
+}
+
+/// Adds the boolean attribute `name` if `value` is true and removes it
+/// otherwise.
+fn set_boolean_attribute(
+    element: &mut Element,
+    name: &str,
+    value: bool,
+) -> Result<()> {
+    match value {
+        true => element.set_attribute(name.to_string(), String::new()),
+        false => element.remove_attribute(name.to_string()),
+    }
+}
+
+/// Replaces the children of `element` with the text `value`, like
+/// `textContent`.
+fn set_text(
+    element: &mut Element,
+    value: String,
+) -> Result<()> {
+    let nodes = match value.is_empty() {
+        true => vec![],
+        false => vec![Either::B(value)],
+    };
+
+    Node::from(&*element).replace_children(element.env, nodes)
+}
+
+/// `value` without leading and trailing ASCII whitespace and with the
+/// whitespace within collapsed into single spaces, see
+/// https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace
+fn strip_and_collapse(value: &str) -> String {
+    value.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(crate) fn get_text(element: &Element) -> Result<JsUnknown> {
+    string(element.env, &element.get_text_content().unwrap_or_default())
+}
+
+pub(crate) fn set_text_value(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    set_text(element, to_string(value)?)
+}
+
+/// Whether the element can be focused by default, which `tabIndex` tells by
+/// being 0 rather than -1.
+fn is_focusable(element: &Element) -> bool {
+    match element.name.local {
+        local_name!("a") | local_name!("area") => {
+            attribute(element, "href").is_some()
+        },
+        local_name!("button")
+        | local_name!("frame")
+        | local_name!("iframe")
+        | local_name!("input")
+        | local_name!("object")
+        | local_name!("select")
+        | local_name!("summary")
+        | local_name!("textarea") => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn get_tab_index(element: &Element) -> Result<JsUnknown> {
+    let default = match is_focusable(element) {
+        true => 0,
+        false => -1,
+    };
+
+    let value = attribute(element, "tabindex")
+        .as_deref()
+        .and_then(super::reflect::parse_integer)
+        .and_then(|value| i32::try_from(value).ok())
+        .unwrap_or(default);
+
+    Ok(element.env.create_int32(value)?.into_unknown())
+}
+
+pub(crate) fn set_tab_index(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    Reflection::Long {
+        default: 0,
+    }
+    .set(element, "tabindex", value)
+}
+
+/// The type of an `input` element, in lowercase.
+fn input_type(element: &Element) -> String {
+    attribute(element, "type")
+        .map(|value| value.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// The value of an `input` element. Without user interaction, it is the
+/// `value` attribute.
+pub(crate) fn get_input_value(element: &Element) -> Result<JsUnknown> {
+    let value =
+        attribute(element, "value").unwrap_or_else(|| {
+            match input_type(element).as_str() {
+                "checkbox" | "radio" => "on".to_string(),
+                _ => String::new(),
+            }
+        });
+
+    string(element.env, &value)
+}
+
+pub(crate) fn set_input_value(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    element.set_attribute("value".to_string(), to_string(value)?)
+}
+
+/// Whether an `input` element is checked. Without user interaction, it is
+/// whether it has the `checked` attribute.
+pub(crate) fn get_checked(element: &Element) -> Result<JsUnknown> {
+    boolean(element.env, attribute(element, "checked").is_some())
+}
+
+pub(crate) fn set_checked(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    set_boolean_attribute(element, "checked", to_bool(value)?)
+}
+
+/// The text of an `option` element, see
+/// https://html.spec.whatwg.org/#dom-option-text
+fn option_text(element: &Element) -> String {
+    strip_and_collapse(&element.get_text_content().unwrap_or_default())
+}
+
+pub(crate) fn get_option_text(element: &Element) -> Result<JsUnknown> {
+    string(element.env, &option_text(element))
+}
+
+pub(crate) fn get_option_value(element: &Element) -> Result<JsUnknown> {
+    let value =
+        attribute(element, "value").unwrap_or_else(|| option_text(element));
+
+    string(element.env, &value)
+}
+
+pub(crate) fn set_option_value(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    element.set_attribute("value".to_string(), to_string(value)?)
+}
+
+pub(crate) fn get_option_label(element: &Element) -> Result<JsUnknown> {
+    let label =
+        attribute(element, "label").unwrap_or_else(|| option_text(element));
+
+    string(element.env, &label)
+}
+
+pub(crate) fn set_option_label(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    element.set_attribute("label".to_string(), to_string(value)?)
+}
+
+/// Whether an `option` element is selected. Without user interaction, it is
+/// whether it has the `selected` attribute.
+pub(crate) fn get_selected(element: &Element) -> Result<JsUnknown> {
+    boolean(element.env, attribute(element, "selected").is_some())
+}
+
+pub(crate) fn set_selected(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    set_boolean_attribute(element, "selected", to_bool(value)?)
+}
+
+/// The `option` elements of a `select` element.
+fn options(element: &Element) -> Vec<Reference<Element>> {
+    Node::from(element)
+        .deep_child_nodes_iter::<Reference<Element>>()
+        .filter(|option| {
+            option.name.expanded() == expanded_name!(html "option")
+        })
+        .collect()
+}
+
+/// The index of the selected option of a `select` element, which is the
+/// first one unless one has the `selected` attribute or it is a multiple
+/// select.
+fn selected_index(element: &Element) -> Option<usize> {
+    let options = options(element);
+
+    options
+        .iter()
+        .position(|option| attribute(option, "selected").is_some())
+        .or_else(|| {
+            let multiple = attribute(element, "multiple").is_some();
+            (!multiple && !options.is_empty()).then_some(0)
+        })
+}
+
+pub(crate) fn get_options(element: &Element) -> Result<JsUnknown> {
+    Ok(HtmlCollection::create(
+        element.env,
+        element.into(),
+        HtmlCollectionFilter::Options,
+    )?
+    .into_unknown())
+}
+
+pub(crate) fn get_options_length(element: &Element) -> Result<JsUnknown> {
+    let length = options(element).len() as u32;
+
+    Ok(element.env.create_uint32(length)?.into_unknown())
+}
+
+pub(crate) fn get_select_type(element: &Element) -> Result<JsUnknown> {
+    let select_type = match attribute(element, "multiple") {
+        Some(_) => "select-multiple",
+        None => "select-one",
+    };
+
+    string(element.env, select_type)
+}
+
+pub(crate) fn get_selected_index(element: &Element) -> Result<JsUnknown> {
+    let index = selected_index(element).map_or(-1, |index| index as i32);
+
+    Ok(element.env.create_int32(index)?.into_unknown())
+}
+
+pub(crate) fn get_select_value(element: &Element) -> Result<JsUnknown> {
+    match selected_index(element) {
+        Some(index) => get_option_value(&options(element)[index]),
+        None => string(element.env, ""),
+    }
+}
+
+/// Selects the first option with `value`, and deselects the others.
+pub(crate) fn set_select_value(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    let value = to_string(value)?;
+    let mut found = false;
+
+    for mut option in options(element) {
+        let matches = !found
+            && attribute(&option, "value")
+                .unwrap_or_else(|| option_text(&option))
+                == value;
+        found |= matches;
+
+        set_boolean_attribute(&mut option, "selected", matches)?;
+    }
+
+    Ok(())
+}
+
+/// The value of a `textarea` element. Without user interaction, it is its
+/// text.
+pub(crate) fn get_textarea_value(element: &Element) -> Result<JsUnknown> {
+    get_text(element)
+}
+
+pub(crate) fn get_form_elements(element: &Element) -> Result<JsUnknown> {
+    Ok(HtmlCollection::create(
+        element.env,
+        element.into(),
+        HtmlCollectionFilter::FormControls,
+    )?
+    .into_unknown())
+}
+
+pub(crate) fn get_form_length(element: &Element) -> Result<JsUnknown> {
+    let length = Node::from(element)
+        .deep_child_nodes_iter::<Reference<Element>>()
+        .filter(|control| HtmlCollectionFilter::FormControls.matches(control))
+        .count() as u32;
+
+    Ok(element.env.create_uint32(length)?.into_unknown())
+}
+
+/// The `href` of a hyperlink, like an `a` element, if it is a valid URL.
+fn hyperlink_url(element: &Element) -> Result<Option<Url>> {
+    element.resolve_attribute_url("href")
+}
+
+/// Defines the getter and setter of a part of the URL of hyperlinks, see
+/// https://html.spec.whatwg.org/#htmlhyperlinkelementutils
+macro_rules! hyperlink_part {
+    ($get:ident, $set:ident, $part:ident, $set_part:expr) => {
+        pub(crate) fn $get(element: &Element) -> Result<JsUnknown> {
+            let part = hyperlink_url(element)?
+                .map(|url| quirks::$part(&url).to_string())
+                .unwrap_or_default();
+
+            string(element.env, &part)
+        }
+
+        pub(crate) fn $set(
+            element: &mut Element,
+            value: JsUnknown,
+        ) -> Result<()> {
+            let mut url = match hyperlink_url(element)? {
+                Some(url) => url,
+                None => return Ok(()),
+            };
+
+            #[allow(clippy::redundant_closure_call)]
+            $set_part(&mut url, &to_string(value)?);
+
+            element.set_attribute("href".to_string(), url.into())
+        }
+    };
+}
+
+hyperlink_part!(get_protocol, set_protocol, protocol, |url, value| {
+    let _ = quirks::set_protocol(url, value);
+});
+hyperlink_part!(get_username, set_username, username, |url, value| {
+    let _ = quirks::set_username(url, value);
+});
+hyperlink_part!(get_password, set_password, password, |url, value| {
+    let _ = quirks::set_password(url, value);
+});
+hyperlink_part!(get_host, set_host, host, |url, value| {
+    let _ = quirks::set_host(url, value);
+});
+hyperlink_part!(get_hostname, set_hostname, hostname, |url, value| {
+    let _ = quirks::set_hostname(url, value);
+});
+hyperlink_part!(get_port, set_port, port, |url, value| {
+    let _ = quirks::set_port(url, value);
+});
+hyperlink_part!(get_pathname, set_pathname, pathname, quirks::set_pathname);
+hyperlink_part!(get_search, set_search, search, quirks::set_search);
+hyperlink_part!(get_hash, set_hash, hash, quirks::set_hash);
+
+pub(crate) fn get_origin(element: &Element) -> Result<JsUnknown> {
+    let origin = hyperlink_url(element)?
+        .map(|url| quirks::origin(&url))
+        .unwrap_or_default();
+
+    string(element.env, &origin)
+}
+
+/// The URL of the node document of `element`, without its base URL.
+fn document_url(element: &Element) -> Result<Url> {
+    Ok(match Node::from(element).get_node_document()? {
+        Some(document) => document.url.clone(),
+        None => about_blank(),
+    })
+}
+
+/// The `action` of forms, which is the document URL without one, see
+/// https://html.spec.whatwg.org/#dom-fs-action
+pub(crate) fn get_form_action(element: &Element) -> Result<JsUnknown> {
+    match attribute(element, "action").filter(|action| !action.is_empty()) {
+        Some(_) => Reflection::Url.get(element, "action"),
+        None => string(element.env, document_url(element)?.as_str()),
+    }
+}
+
+pub(crate) fn set_form_action(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    Reflection::Url.set(element, "action", value)
+}
+
+/// The `href` of `base` elements, which resolves against the document URL
+/// as it is what sets the base URL, see
+/// https://html.spec.whatwg.org/#dom-base-href
+pub(crate) fn get_base_href(element: &Element) -> Result<JsUnknown> {
+    let href = attribute(element, "href").unwrap_or_default();
+    let url = document_url(element)?
+        .join(&href)
+        .map(String::from)
+        .unwrap_or(href);
+
+    string(element.env, &url)
+}
+
+pub(crate) fn set_base_href(
+    element: &mut Element,
+    value: JsUnknown,
+) -> Result<()> {
+    Reflection::String.set(element, "href", value)
+}
@@ -0,0 +1,302 @@
+//! Properties that reflect a content attribute, see
+//! https://html.spec.whatwg.org/#reflecting-content-attributes-in-idl-attributes
+
+use napi::{
+    Env,
+    JsUnknown,
+    Result,
+    ValueType,
+};
+
+use crate::{
+    DomException,
+    DomExceptionName,
+    Element,
+};
+
+/// How a property reads and writes its attribute.
+pub(crate) enum Reflection {
+    /// The value as is, or the empty string.
+    String,
+    /// The value resolved as a URL, see `Element::resolve_url`.
+    Url,
+    /// Whether the attribute is there.
+    Boolean,
+    /// The keyword the value matches ASCII case-insensitively, or `missing`
+    /// (`null` if `None`) without the attribute and `invalid` without a
+    /// match.
+    Enumerated {
+        keywords: &'static [&'static str],
+        missing: Option<&'static str>,
+        invalid: &'static str,
+    },
+    Long {
+        default: i32,
+    },
+    /// A long that can't be set to a negative number, like `maxLength`.
+    NonNegativeLong {
+        default: i32,
+    },
+    UnsignedLong {
+        default: u32,
+    },
+    /// An unsigned long that can't be set to zero, like `size`.
+    PositiveUnsignedLong {
+        default: u32,
+    },
+    /// An unsigned long kept between `min` and `max`, like `colSpan`.
+    ClampedUnsignedLong {
+        default: u32,
+        min: u32,
+        max: u32,
+    },
+}
+
+/// The largest number a reflected long or unsigned long can have.
+const MAX_LONG: i64 = i32::MAX as i64;
+
+impl Reflection {
+    pub(crate) fn get(
+        &self,
+        element: &Element,
+        attribute: &str,
+    ) -> Result<JsUnknown> {
+        let env = element.env;
+        let value = element
+            .attributes_wrapper
+            .get_attribute(attribute.into())
+            .map(|attribute| attribute.value.to_string());
+
+        match self {
+            Reflection::String => string(env, &value.unwrap_or_default()),
+            Reflection::Url => {
+                let url = match value {
+                    Some(value) => element
+                        .resolve_url(&value)?
+                        .map(String::from)
+                        .unwrap_or(value),
+                    None => String::new(),
+                };
+
+                string(env, &url)
+            },
+            Reflection::Boolean => {
+                Ok(env.get_boolean(value.is_some())?.into_unknown())
+            },
+            Reflection::Enumerated {
+                keywords,
+                missing,
+                invalid,
+            } => {
+                let keyword = match value {
+                    Some(value) => Some(
+                        keywords
+                            .iter()
+                            .find(|keyword| {
+                                keyword.eq_ignore_ascii_case(&value)
+                            })
+                            .unwrap_or(invalid),
+                    ),
+                    None => missing.as_ref(),
+                };
+
+                match keyword {
+                    Some(keyword) => string(env, keyword),
+                    None => Ok(env.get_null()?.into_unknown()),
+                }
+            },
+            Reflection::Long {
+                default,
+            } => {
+                let value = value
+                    .as_deref()
+                    .and_then(parse_integer)
+                    .filter(|value| i32::try_from(*value).is_ok())
+                    .map_or(*default, |value| value as i32);
+
+                Ok(env.create_int32(value)?.into_unknown())
+            },
+            Reflection::NonNegativeLong {
+                default,
+            } => {
+                let value = value
+                    .as_deref()
+                    .and_then(parse_integer)
+                    .filter(|value| (0..=MAX_LONG).contains(value))
+                    .map_or(*default, |value| value as i32);
+
+                Ok(env.create_int32(value)?.into_unknown())
+            },
+            Reflection::UnsignedLong {
+                default,
+            } => {
+                let value = value
+                    .as_deref()
+                    .and_then(parse_integer)
+                    .filter(|value| (0..=MAX_LONG).contains(value))
+                    .map_or(*default, |value| value as u32);
+
+                Ok(env.create_uint32(value)?.into_unknown())
+            },
+            Reflection::PositiveUnsignedLong {
+                default,
+            } => {
+                let value = value
+                    .as_deref()
+                    .and_then(parse_integer)
+                    .filter(|value| (1..=MAX_LONG).contains(value))
+                    .map_or(*default, |value| value as u32);
+
+                Ok(env.create_uint32(value)?.into_unknown())
+            },
+            Reflection::ClampedUnsignedLong {
+                default,
+                min,
+                max,
+            } => {
+                let value = value
+                    .as_deref()
+                    .and_then(parse_integer)
+                    .filter(|value| *value >= 0)
+                    .map_or(*default, |value| {
+                        value.clamp(*min as i64, *max as i64) as u32
+                    });
+
+                Ok(env.create_uint32(value)?.into_unknown())
+            },
+        }
+    }
+
+    pub(crate) fn set(
+        &self,
+        element: &mut Element,
+        attribute: &str,
+        value: JsUnknown,
+    ) -> Result<()> {
+        let value = match self {
+            Reflection::Enumerated {
+                missing: None, ..
+            } if value.get_type()? == ValueType::Null => {
+                // a nullable property, like `crossOrigin`
+                return element.remove_attribute(attribute.to_string());
+            },
+            Reflection::String
+            | Reflection::Url
+            | Reflection::Enumerated {
+                ..
+            } => value.coerce_to_string()?.into_utf8()?.into_owned()?,
+            Reflection::Boolean => {
+                return match value.coerce_to_bool()?.get_value()? {
+                    true => element
+                        .set_attribute(attribute.to_string(), String::new()),
+                    false => element.remove_attribute(attribute.to_string()),
+                };
+            },
+            Reflection::Long {
+                ..
+            } => to_long(value)?.to_string(),
+            Reflection::NonNegativeLong {
+                ..
+            } => {
+                let value = to_long(value)?;
+                if value < 0 {
+                    return Err(index_size_error(element.env, attribute));
+                }
+
+                value.to_string()
+            },
+            Reflection::UnsignedLong {
+                default,
+            }
+            | Reflection::ClampedUnsignedLong {
+                default, ..
+            } => {
+                let value = to_unsigned_long(value)?;
+
+                match value as i64 <= MAX_LONG {
+                    true => value,
+                    false => *default,
+                }
+                .to_string()
+            },
+            Reflection::PositiveUnsignedLong {
+                default,
+            } => {
+                let value = to_unsigned_long(value)?;
+                if value == 0 {
+                    return Err(index_size_error(element.env, attribute));
+                }
+
+                match value as i64 <= MAX_LONG {
+                    true => value,
+                    false => *default,
+                }
+                .to_string()
+            },
+        };
+
+        element.set_attribute(attribute.to_string(), value)
+    }
+}
+
+fn string(
+    env: Env,
+    value: &str,
+) -> Result<JsUnknown> {
+    Ok(env.create_string(value)?.into_unknown())
+}
+
+fn index_size_error(
+    env: Env,
+    attribute: &str,
+) -> napi::Error {
+    DomException::new(
+        DomExceptionName::IndexSizeError,
+        format!(
+            "The value is out of range for the '{}' attribute",
+            attribute
+        ),
+    )
+    .into_error(env)
+}
+
+/// Converts `value` to a WebIDL `long`, wrapping around like JavaScript.
+fn to_long(value: JsUnknown) -> Result<i32> {
+    Ok(to_unsigned_long(value)? as i32)
+}
+
+/// Converts `value` to a WebIDL `unsigned long`, wrapping around like
+/// JavaScript.
+fn to_unsigned_long(value: JsUnknown) -> Result<u32> {
+    let value = value.coerce_to_number()?.get_double()?;
+
+    if !value.is_finite() {
+        return Ok(0);
+    }
+
+    Ok(value.trunc().rem_euclid(4294967296.0) as u32)
+}
+
+/// Parses the integer at the start of `value`, see
+/// https://html.spec.whatwg.org/#rules-for-parsing-integers
+pub(crate) fn parse_integer(value: &str) -> Option<i64> {
+    let value = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(value, |end| &value[..end]);
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    // anything too large to be a long is out of range anyway
+    let value = digits.parse::<i64>().unwrap_or(i64::MAX);
+
+    Some(sign * value)
+}
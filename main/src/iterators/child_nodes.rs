@@ -3,13 +3,20 @@ use std::{
     marker::PhantomData,
 };
 
+use selectors::bloom::BloomFilter;
+
 use crate::{
+    AncestorFilter,
     ChildNode,
     NodeData,
 };
 
 pub struct DeepChildNodesIterator<T> {
-    queue: Vec<ChildNode>,
+    // nodes to visit along with how deep below the root they are
+    queue: Vec<(ChildNode, usize)>,
+    ancestor_filter: Option<AncestorFilter>,
+    // the last visited node, if its children are visited next
+    parent: Option<ChildNode>,
     _phantom: PhantomData<T>,
 }
 
@@ -17,13 +24,34 @@ impl<T> DeepChildNodesIterator<T> {
     pub fn new(node_data: &NodeData) -> Self {
         let queue = ShallowChildNodesIterator::<ChildNode>::new(node_data)
             .rev()
+            .map(|child_node| (child_node, 0))
             .collect();
 
         Self {
             queue,
+            ancestor_filter: None,
+            parent: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Keeps `ancestor_filter` in sync with the ancestors of the last visited
+    /// node while descending.
+    pub fn with_ancestor_filter(
+        mut self,
+        ancestor_filter: AncestorFilter,
+    ) -> Self {
+        self.ancestor_filter = Some(ancestor_filter);
+        self
+    }
+
+    /// Holds the ancestors of the last visited node, see
+    /// `with_ancestor_filter`.
+    pub fn bloom_filter(&self) -> Option<&BloomFilter> {
+        self.ancestor_filter
+            .as_ref()
+            .map(AncestorFilter::bloom_filter)
+    }
 }
 
 impl<T> Iterator for DeepChildNodesIterator<T>
@@ -33,13 +61,30 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.queue.pop() {
+        while let Some((node, depth)) = self.queue.pop() {
+            if let Some(ancestor_filter) = self.ancestor_filter.as_mut() {
+                if let Some(ChildNode::Element(parent)) = self.parent.take() {
+                    ancestor_filter.push(&parent);
+                }
+
+                while ancestor_filter.depth() > depth {
+                    ancestor_filter.pop();
+                }
+            }
+
             if let ChildNode::Element(r) = &node {
                 let node_data = &r.node_data;
+                let len = self.queue.len();
+
                 self.queue.extend(
                     ShallowChildNodesIterator::<ChildNode>::new(node_data)
-                        .rev(),
+                        .rev()
+                        .map(|child_node| (child_node, depth + 1)),
                 );
+
+                if self.ancestor_filter.is_some() && self.queue.len() > len {
+                    self.parent = Some(node.clone());
+                }
             }
 
             if let Ok(child) = node.try_into() {
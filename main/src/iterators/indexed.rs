@@ -0,0 +1,160 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use napi::{
+    bindgen_prelude::Reference,
+    Result,
+};
+use selectors::bloom::BloomFilter;
+
+use crate::{
+    AncestorFilter,
+    Element,
+    Node,
+    NodeData,
+};
+
+/// Elements from the document's element index that are descendants of a
+/// root, in tree order.
+///
+/// Instead of working out the position of every element, which means
+/// walking up to the root from each of them, the tree of their ancestors is
+/// built by walking up only until an ancestor that was seen before, and then
+/// walked down like `DeepChildNodesIterator` does.
+pub struct IndexedDescendantsIterator {
+    // elements to visit along with how deep below the root they are
+    queue: Vec<(Reference<Element>, usize)>,
+    // the children leading to the elements, with their positions, by the id
+    // of their parent, `None` for the root
+    children: HashMap<Option<usize>, Vec<(usize, Reference<Element>)>>,
+    // the ids of the elements from the index, as opposed to their ancestors
+    elements: HashSet<usize>,
+    ancestor_filter: Option<AncestorFilter>,
+    // the last visited element, if its children are visited next
+    parent: Option<Reference<Element>>,
+}
+
+impl IndexedDescendantsIterator {
+    pub fn new(
+        root: &Node,
+        elements: Vec<Reference<Element>>,
+    ) -> Result<Self> {
+        let root_data = NodeData::from(root);
+        let mut children: HashMap<_, Vec<_>> = HashMap::new();
+        // the elements that were linked to their parent already
+        let mut linked = HashSet::new();
+        let element_ids = elements.iter().map(|element| element.id).collect();
+
+        for element in elements {
+            let mut current = element;
+
+            while linked.insert(current.id) {
+                let ctx = match current.node_data.parent_context.cloned() {
+                    Some(ctx) => ctx,
+                    None => break,
+                };
+                let parent = ctx.get_node()?;
+
+                if NodeData::from(&parent).is_same(&root_data) {
+                    children
+                        .entry(None)
+                        .or_default()
+                        .push((ctx.position, current));
+                    break;
+                }
+
+                let parent = match parent {
+                    Node::Element(parent) => parent,
+                    // the root of another tree, so not a descendant of `root`
+                    _ => break,
+                };
+
+                children
+                    .entry(Some(parent.id))
+                    .or_default()
+                    .push((ctx.position, current));
+                current = parent;
+            }
+        }
+
+        let mut iterator = Self {
+            queue: vec![],
+            children,
+            elements: element_ids,
+            ancestor_filter: None,
+            parent: None,
+        };
+        iterator.queue_children(None, 0);
+
+        Ok(iterator)
+    }
+
+    /// Keeps `ancestor_filter` in sync with the ancestors of the last visited
+    /// element while descending.
+    pub fn with_ancestor_filter(
+        mut self,
+        ancestor_filter: AncestorFilter,
+    ) -> Self {
+        self.ancestor_filter = Some(ancestor_filter);
+        self
+    }
+
+    /// Holds the ancestors of the last visited element, see
+    /// `with_ancestor_filter`.
+    pub fn bloom_filter(&self) -> Option<&BloomFilter> {
+        self.ancestor_filter
+            .as_ref()
+            .map(AncestorFilter::bloom_filter)
+    }
+
+    /// Queues the children leading to elements of `parent`, so that they are
+    /// visited in tree order. Returns whether there were any.
+    fn queue_children(
+        &mut self,
+        parent: Option<usize>,
+        depth: usize,
+    ) -> bool {
+        let mut children = match self.children.remove(&parent) {
+            Some(children) => children,
+            None => return false,
+        };
+
+        children.sort_unstable_by_key(|(position, _)| *position);
+        self.queue.extend(
+            children.into_iter().rev().map(|(_, child)| (child, depth)),
+        );
+
+        true
+    }
+}
+
+impl Iterator for IndexedDescendantsIterator {
+    type Item = Reference<Element>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((element, depth)) = self.queue.pop() {
+            if let Some(ancestor_filter) = self.ancestor_filter.as_mut() {
+                if let Some(parent) = self.parent.take() {
+                    ancestor_filter.push(&parent);
+                }
+
+                while ancestor_filter.depth() > depth {
+                    ancestor_filter.pop();
+                }
+            }
+
+            if self.queue_children(Some(element.id), depth + 1)
+                && self.ancestor_filter.is_some()
+            {
+                self.parent = element.clone(element.env).ok();
+            }
+
+            if self.elements.contains(&element.id) {
+                return Some(element);
+            }
+        }
+        None
+    }
+}
@@ -1,4 +1,5 @@
 mod child_nodes;
+mod indexed;
 mod parent;
 mod selector;
 mod sibling;
@@ -7,8 +8,12 @@ pub use child_nodes::{
     DeepChildNodesIterator,
     ShallowChildNodesIterator,
 };
+pub use indexed::IndexedDescendantsIterator;
 pub use parent::ParentIterator;
-pub use selector::SelectorsIterator;
+pub use selector::{
+    SelectorsCandidates,
+    SelectorsIterator,
+};
 pub use sibling::{
     SiblingIterator,
     SiblingIteratorType,
@@ -7,22 +7,31 @@ use crate::{
     DeepChildNodesIterator,
     Element,
     ElementRef,
-    Selectors,
+    IndexedDescendantsIterator,
+    SelectorsMatcher,
 };
 
+pub enum SelectorsCandidates {
+    /// Every descendant, with the ancestor bloom filter maintained.
+    Tree(DeepChildNodesIterator<ElementRef>),
+    /// Descendants from the document's element index, with the ancestor
+    /// bloom filter maintained as well.
+    Indexed(IndexedDescendantsIterator),
+}
+
 pub struct SelectorsIterator {
-    selectors: Selectors,
-    iter: DeepChildNodesIterator<ElementRef>,
+    matcher: SelectorsMatcher,
+    candidates: SelectorsCandidates,
 }
 
 impl SelectorsIterator {
     pub fn new(
-        selectors: Selectors,
-        iter: DeepChildNodesIterator<ElementRef>,
+        matcher: SelectorsMatcher,
+        candidates: SelectorsCandidates,
     ) -> Self {
         Self {
-            selectors,
-            iter,
+            matcher,
+            candidates,
         }
     }
 }
@@ -31,12 +40,25 @@ impl Iterator for SelectorsIterator {
     type Item = Result<Reference<Element>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        for element_ref in self.iter.by_ref() {
-            match self.selectors.matches(&element_ref) {
-                Ok(true) => return Some(Ok(element_ref.into())),
-                Ok(false) => continue,
-                Err(err) => return Some(Err(err)),
-            }
+        match &mut self.candidates {
+            SelectorsCandidates::Tree(iter) => {
+                while let Some(element_ref) = iter.next() {
+                    let element_ref = self.matcher.element_ref(element_ref);
+
+                    if self.matcher.matches(&element_ref, iter.bloom_filter()) {
+                        return Some(Ok(element_ref.into()));
+                    }
+                }
+            },
+            SelectorsCandidates::Indexed(iter) => {
+                while let Some(element) = iter.next() {
+                    let element_ref = self.matcher.element_ref(element);
+
+                    if self.matcher.matches(&element_ref, iter.bloom_filter()) {
+                        return Some(Ok(element_ref.into()));
+                    }
+                }
+            },
         }
 
         None
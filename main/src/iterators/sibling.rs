@@ -50,10 +50,7 @@ impl<T> SiblingIterator<T> {
         };
 
         let next_index_fn = self.next_index;
-        let next_index = match next_index_fn(*index) {
-            Some(i) => i,
-            None => return None,
-        };
+        let next_index = next_index_fn(*index)?;
 
         match node.get_child_node(next_index) {
             Some(child_node) => {
@@ -7,25 +7,44 @@ extern crate napi_derive;
 #[macro_use]
 extern crate node_html5ever_derive;
 
+mod collections;
 mod cyclic_reference;
 mod dom;
+mod dom_exception;
+mod dom_parser;
 mod einar_cell;
+mod html_elements;
 mod id;
 mod insert_position;
 mod iterators;
 mod lazy_reference;
+mod names;
 mod node_data;
 mod node_wrappers;
 mod nodes;
+mod proxy;
 mod quirks_mode;
 mod selectors;
 mod serializer;
 mod style_declaration;
 mod traits;
+mod variadic;
 mod weak_reference;
+mod xml_serializer;
 
+pub use collections::{
+    HtmlCollection,
+    HtmlCollectionFilter,
+    NamedNodeMap,
+    NodeList,
+};
 pub use cyclic_reference::CyclicReference;
 pub use dom::Html5everDom;
+pub use dom_exception::{
+    DomException,
+    DomExceptionName,
+};
+pub use dom_parser::DomParser;
 pub use einar_cell::EinarCell;
 pub use id::get_id;
 pub use insert_position::InsertPosition;
@@ -41,11 +60,19 @@ pub use node_wrappers::{
     Node,
     ParentNode,
 };
+pub(crate) use nodes::{
+    about_blank,
+    IndexKey,
+    IndexedAttributes,
+};
 pub use nodes::{
+    Attr,
     Comment,
     Document,
     DocumentFragment,
     DocumentType,
+    DomImplementation,
+    DomTokenList,
     Element,
     ElementRef,
     Text,
@@ -56,5 +83,14 @@ pub use serializer::serialize;
 pub use style_declaration::StyleDeclaration;
 pub use traits::*;
 pub use weak_reference::WeakReference;
+pub use xml_serializer::{
+    serialize_xml,
+    XmlSerializer,
+};
 
-pub use crate::selectors::Selectors;
+pub use crate::selectors::{
+    AncestorFilter,
+    CompiledSelector,
+    Selectors,
+    SelectorsMatcher,
+};
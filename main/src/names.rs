@@ -0,0 +1,141 @@
+//! Checks for the names of elements, attributes and doctypes, see
+//! https://dom.spec.whatwg.org/#namespaces
+
+use html5ever::{
+    namespace_url,
+    ns,
+    Namespace,
+    QualName,
+};
+use napi::{
+    Env,
+    Error,
+    Result,
+};
+
+use crate::{
+    DomException,
+    DomExceptionName,
+};
+
+/// What a qualified name is validated for.
+#[derive(Clone, Copy)]
+pub(crate) enum NameKind {
+    Element,
+    Attribute,
+}
+
+/// See https://dom.spec.whatwg.org/#valid-element-local-name
+pub(crate) fn is_valid_element_local_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() => {
+            !name.contains(|c: char| {
+                c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '>')
+            })
+        },
+        Some(first) if matches!(first, ':' | '_') || first >= '\u{80}' => chars
+            .all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '-' | '.' | ':' | '_')
+                    || c >= '\u{80}'
+            }),
+        _ => false,
+    }
+}
+
+/// See https://dom.spec.whatwg.org/#valid-attribute-local-name
+pub(crate) fn is_valid_attribute_local_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(|c: char| {
+            c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '=' | '>')
+        })
+}
+
+/// See https://dom.spec.whatwg.org/#valid-namespace-prefix
+fn is_valid_namespace_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && !prefix.contains(|c: char| {
+            c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '>')
+        })
+}
+
+/// See https://dom.spec.whatwg.org/#valid-doctype-name
+pub(crate) fn is_valid_doctype_name(name: &str) -> bool {
+    !name.contains(|c: char| c.is_ascii_whitespace() || matches!(c, '\0' | '>'))
+}
+
+pub(crate) fn invalid_character_error(
+    env: Env,
+    name: &str,
+) -> Error {
+    DomException::new(
+        DomExceptionName::InvalidCharacterError,
+        format!("'{}' is not a valid name", name),
+    )
+    .into_error(env)
+}
+
+fn namespace_error(
+    env: Env,
+    message: &str,
+) -> Error {
+    DomException::new(DomExceptionName::NamespaceError, message).into_error(env)
+}
+
+/// Splits `qualified_name` into its prefix and local name, throwing unless
+/// they are valid in `namespace`, see
+/// https://dom.spec.whatwg.org/#validate-and-extract
+pub(crate) fn validate_and_extract(
+    env: Env,
+    namespace: Option<String>,
+    qualified_name: &str,
+    kind: NameKind,
+) -> Result<QualName> {
+    let namespace = namespace.filter(|namespace| !namespace.is_empty());
+
+    let (prefix, local_name) = match qualified_name.split_once(':') {
+        Some((prefix, local_name)) => (Some(prefix), local_name),
+        None => (None, qualified_name),
+    };
+
+    if prefix.is_some_and(|prefix| !is_valid_namespace_prefix(prefix)) {
+        return Err(invalid_character_error(env, qualified_name));
+    }
+
+    let is_valid_local_name = match kind {
+        NameKind::Element => is_valid_element_local_name(local_name),
+        NameKind::Attribute => is_valid_attribute_local_name(local_name),
+    };
+    if !is_valid_local_name {
+        return Err(invalid_character_error(env, qualified_name));
+    }
+
+    let namespace = namespace.map_or(ns!(), Namespace::from);
+    let is_xmlns = qualified_name == "xmlns" || prefix == Some("xmlns");
+
+    if prefix.is_some() && namespace == ns!() {
+        return Err(namespace_error(env, "A prefix needs a namespace"));
+    }
+
+    if prefix == Some("xml") && namespace != ns!(xml) {
+        return Err(namespace_error(
+            env,
+            "The xml prefix is only for the XML namespace",
+        ));
+    }
+
+    if is_xmlns != (namespace == ns!(xmlns)) {
+        return Err(namespace_error(
+            env,
+            "The xmlns prefix is only, and always, for the XMLNS namespace",
+        ));
+    }
+
+    Ok(QualName::new(
+        prefix.map(Into::into),
+        namespace,
+        local_name.into(),
+    ))
+}
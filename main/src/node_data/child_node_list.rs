@@ -22,7 +22,7 @@ impl ChildNodeList {
         self.0.len()
     }
 
-    pub(crate) fn iter(&self) -> Iter<ChildNode> {
+    pub(crate) fn iter(&self) -> Iter<'_, ChildNode> {
         self.0.iter()
     }
 
@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     ops::Deref,
     rc::Rc,
 };
@@ -21,6 +22,7 @@ use crate::{
     Node,
     ParentNode,
     Text,
+    WeakReference,
 };
 
 mod child_node_list;
@@ -33,11 +35,40 @@ pub struct NodeDataInner {
     pub(crate) env: Env,
     pub(crate) child_nodes: EinarCell<ChildNodeList>,
     pub(crate) parent_context: EinarCell<Option<ParentContext>>,
+    /// The document this node was created in or last removed from, which is
+    /// its node document while it is the root of a tree. Nodes in a tree
+    /// don't hold on to it, as the document holding them would be a cycle.
+    pub(crate) owner_document: EinarCell<Option<OwnerDocument>>,
+}
+
+/// The node document of a root, which the root keeps alive, so that it
+/// still has one after everything else of the document is gone.
+pub(crate) enum OwnerDocument {
+    Strong(Reference<Document>),
+    /// For template contents, which their template keeps alive instead, as
+    /// a strong reference would be a cycle through the template.
+    Weak(WeakReference<Document>),
+}
+
+impl OwnerDocument {
+    pub(crate) fn get(
+        &self,
+        env: Env,
+    ) -> Result<Option<Reference<Document>>> {
+        match self {
+            OwnerDocument::Strong(document) => document.clone(env).map(Some),
+            OwnerDocument::Weak(document) => document.try_upgrade(env),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct NodeData(Rc<NodeDataInner>);
 
+thread_local! {
+    static TREE_VERSION: Cell<u64> = const { Cell::new(0) };
+}
+
 impl Deref for NodeData {
     type Target = NodeDataInner;
 
@@ -52,8 +83,28 @@ impl NodeData {
             env,
             child_nodes: Default::default(),
             parent_context: Default::default(),
+            owner_document: Default::default(),
         }))
     }
+
+    /// Changes whenever a node is inserted or removed anywhere, or the
+    /// attributes of an element change, so that live collections know when
+    /// what they matched is out of date.
+    pub(crate) fn tree_version() -> u64 {
+        TREE_VERSION.get()
+    }
+
+    pub(crate) fn tree_changed() {
+        TREE_VERSION.set(TREE_VERSION.get() + 1);
+    }
+
+    /// Whether both are the data of the same node.
+    pub(crate) fn is_same(
+        &self,
+        other: &NodeData,
+    ) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 impl TryFrom<&ParentContext> for NodeData {
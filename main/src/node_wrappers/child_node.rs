@@ -124,8 +124,8 @@ impl ValidateNapiValue for ChildNode {
     ) -> Result<napi::sys::napi_value> {
         <&Element>::validate(env, napi_val)
             .or_else(|_| <&Text>::validate(env, napi_val))
+            .or_else(|_| <&Comment>::validate(env, napi_val))
             .or_else(|_| <&DocumentType>::validate(env, napi_val))
-            .or_else(|_| <&Text>::validate(env, napi_val))
     }
 }
 
@@ -0,0 +1,70 @@
+//! The `Node` interface, which the node classes inherit from like in
+//! browsers, so that `instanceof Node` works for all of them.
+
+use napi::{
+    Env,
+    JsFunction,
+    JsObject,
+    Property,
+    PropertyAttributes,
+    Result,
+};
+use shared::{
+    document_position::DOCUMENT_POSITION_MAP,
+    node_type::NODE_TYPE_MAP,
+};
+
+use crate::html_elements::{
+    as_object,
+    illegal_constructor,
+};
+
+/// The exported classes of nodes, which inherit from `Node`.
+pub(crate) const NODE_CLASSES: [&str; 6] = [
+    "Comment",
+    "Document",
+    "DocumentFragment",
+    "DocumentType",
+    "Element",
+    "Text",
+];
+
+/// Creates `Node`, with the node type and document position constants on it
+/// and its prototype, and makes it the parent of `NODE_CLASSES`.
+pub(crate) fn define_node_interface(
+    env: Env,
+    exports: &JsObject,
+) -> Result<JsFunction> {
+    let object: JsFunction = env.get_global()?.get_named_property("Object")?;
+    let object = object.coerce_to_object()?;
+    let set_prototype_of: JsFunction =
+        object.get_named_property("setPrototypeOf")?;
+
+    let node = env.create_function("Node", illegal_constructor)?;
+    let mut prototype: JsObject =
+        as_object(env, &node).get_named_property("prototype")?;
+
+    let constants = NODE_TYPE_MAP
+        .iter()
+        .chain(DOCUMENT_POSITION_MAP.iter())
+        .map(|(name, value)| {
+            Ok(Property::new(name)?
+                .with_value(&env.create_uint32(*value)?)
+                .with_property_attributes(PropertyAttributes::Enumerable))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    as_object(env, &node).define_properties(&constants)?;
+    prototype.define_properties(&constants)?;
+
+    for class in NODE_CLASSES {
+        let constructor: JsFunction = exports.get_named_property(class)?;
+        let class_prototype: JsObject =
+            as_object(env, &constructor).get_named_property("prototype")?;
+
+        set_prototype_of
+            .call(Some(&object), &[&class_prototype, &prototype])?;
+        set_prototype_of.call(Some(&object), &[&constructor, &node])?;
+    }
+
+    Ok(node)
+}
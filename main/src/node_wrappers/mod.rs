@@ -1,7 +1,12 @@
 mod child_node;
+mod interface;
 mod node;
 mod parent_node;
 
 pub use child_node::ChildNode;
+pub(crate) use interface::{
+    define_node_interface,
+    NODE_CLASSES,
+};
 pub use node::Node;
 pub use parent_node::ParentNode;
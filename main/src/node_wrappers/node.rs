@@ -6,34 +6,54 @@ use html5ever::{
 use napi::{
     bindgen_prelude::{
         Error,
+        FromNapiValue,
         Reference,
         Result,
+        ToNapiValue,
+        TypeName,
+        ValidateNapiValue,
     },
     Either,
     Env,
     Status,
+    ValueType,
+};
+use shared::{
+    document_position::DocumentPosition,
+    node_type::NodeTypeEnum,
 };
-use shared::node_type::NodeTypeEnum;
 
 use crate::{
+    about_blank,
+    node_data::OwnerDocument,
+    AncestorFilter,
     ChildNode,
     ChildNodeList,
     Comment,
+    CompiledSelector,
     DeepChildNodesIterator,
     Document,
     DocumentFragment,
     DocumentType,
+    DomException,
+    DomExceptionName,
     Element,
+    IndexKey,
+    IndexedDescendantsIterator,
     InsertPosition,
     NodeData,
     ParentContext,
     ParentIterator,
     ParentNode,
+    QuirksMode,
+    SelectorsCandidates,
     SelectorsIterator,
+    SelectorsMatcher,
     ShallowChildNodesIterator,
     SiblingIterator,
     SiblingIteratorType,
     Text,
+    WeakReference,
 };
 pub enum Node {
     Comment(Reference<Comment>),
@@ -122,6 +142,68 @@ impl Clone for Node {
     }
 }
 
+impl ToNapiValue for Node {
+    unsafe fn to_napi_value(
+        env: napi::sys::napi_env,
+        val: Self,
+    ) -> Result<napi::sys::napi_value> {
+        match val {
+            Node::Comment(r) => Reference::<Comment>::to_napi_value(env, r),
+            Node::DocumentType(r) => {
+                Reference::<DocumentType>::to_napi_value(env, r)
+            },
+            Node::Document(r) => Reference::<Document>::to_napi_value(env, r),
+            Node::DocumentFragment(r) => {
+                Reference::<DocumentFragment>::to_napi_value(env, r)
+            },
+            Node::Element(r) => Reference::<Element>::to_napi_value(env, r),
+            Node::Text(r) => Reference::<Text>::to_napi_value(env, r),
+        }
+    }
+}
+
+impl ValidateNapiValue for Node {
+    unsafe fn validate(
+        env: napi::sys::napi_env,
+        napi_val: napi::sys::napi_value,
+    ) -> Result<napi::sys::napi_value> {
+        <ChildNode>::validate(env, napi_val)
+            .or_else(|_| <&DocumentFragment>::validate(env, napi_val))
+            .or_else(|_| <&Document>::validate(env, napi_val))
+    }
+}
+
+impl FromNapiValue for Node {
+    unsafe fn from_napi_value(
+        env: napi::sys::napi_env,
+        napi_val: napi::sys::napi_value,
+    ) -> Result<Self> {
+        if <ChildNode>::validate(env, napi_val).is_ok() {
+            ChildNode::from_napi_value(env, napi_val).map(Into::into)
+        } else if <&DocumentFragment>::validate(env, napi_val).is_ok() {
+            <&DocumentFragment>::from_napi_value(env, napi_val)
+                .map(|r| r.into())
+        } else if <&Document>::validate(env, napi_val).is_ok() {
+            <&Document>::from_napi_value(env, napi_val).map(|r| r.into())
+        } else {
+            Err(Error::new(
+                Status::InvalidArg,
+                "Could not convert napi_value to Node".to_string(),
+            ))
+        }
+    }
+}
+
+impl TypeName for Node {
+    fn type_name() -> &'static str {
+        "Node"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Unknown
+    }
+}
+
 impl Node {
     pub(crate) fn as_element(&self) -> Result<&Reference<Element>> {
         match &self {
@@ -143,7 +225,7 @@ impl Node {
         node_data.child_nodes.borrow_mut(f);
     }
 
-    fn parent_as_node(
+    pub(crate) fn parent_as_node(
         &self,
         env: Env,
     ) -> Result<Option<Node>> {
@@ -164,6 +246,8 @@ impl Node {
         nodes: Vec<ChildNode>,
         position: &InsertPosition,
     ) -> Result<()> {
+        let mut maybe_document = None;
+
         if matches!(
             position,
             InsertPosition::Prepend
@@ -172,9 +256,19 @@ impl Node {
         ) {
             for child_node in &nodes {
                 child_node.remove()?;
+
+                // the node document is now that of the root
+                NodeData::from(child_node).owner_document.set(None);
             }
+
+            maybe_document = self.get_connected_document()?;
         }
 
+        let indexed_nodes = match maybe_document {
+            Some(_) => nodes.clone(),
+            None => vec![],
+        };
+
         match position {
             InsertPosition::Before => {
                 let parent_position = self.get_position()?;
@@ -222,6 +316,13 @@ impl Node {
         }
 
         self.sync_parent_context();
+        NodeData::tree_changed();
+
+        if let Some(mut document) = maybe_document {
+            for child_node in &indexed_nodes {
+                Self::index_child_node(&mut document, child_node, true)?;
+            }
+        }
 
         Ok(())
     }
@@ -239,19 +340,233 @@ impl Node {
         &self,
         child_node: &ChildNode,
     ) -> Result<()> {
+        let node_document = self.get_node_document()?;
+
+        if let Some(mut document) = self.get_connected_document()? {
+            Self::index_child_node(&mut document, child_node, false)?;
+        }
+
         let parent_node_data: NodeData = self.into();
         parent_node_data
             .child_nodes
             .borrow_mut(|child_nodes| child_nodes.remove_node(child_node))?;
 
         self.sync_parent_context();
+        NodeData::tree_changed();
 
         let child_node_data: NodeData = child_node.into();
         child_node_data.parent_context.set(None);
+        Node::from(child_node.clone()).set_node_document(node_document.as_ref())
+    }
+
+    /// Adds `child_node` and its descendants to, or removes them from, the
+    /// element index of the document they are connected to.
+    fn index_child_node(
+        document: &mut Document,
+        child_node: &ChildNode,
+        insert: bool,
+    ) -> Result<()> {
+        let element = match child_node {
+            ChildNode::Element(element) => element.clone(element.env)?,
+            _ => return Ok(()),
+        };
+        let node: Node = element.clone(element.env)?.into();
+        let elements = std::iter::once(element)
+            .chain(node.deep_child_nodes_iter::<Reference<Element>>());
+
+        for element in elements {
+            if insert {
+                document.element_index.insert(&element);
+            } else {
+                document.element_index.remove(&element);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The document at the root of the tree this node is in, if any.
+    pub(crate) fn get_connected_document(
+        &self
+    ) -> Result<Option<Reference<Document>>> {
+        if let Node::Document(document) = self {
+            return document.clone(document.env).map(Some);
+        }
+
+        let node_data: NodeData = self.into();
+        self.parent_iterator::<WeakReference<Document>>()
+            .try_next()?
+            .map(|document| document.upgrade(node_data.env))
+            .transpose()
+    }
+
+    /// The document this node belongs to, see
+    /// https://dom.spec.whatwg.org/#concept-node-document
+    pub(crate) fn get_node_document(
+        &self
+    ) -> Result<Option<Reference<Document>>> {
+        let node_data: NodeData = self.into();
+        let root = self.get_root(node_data.env)?;
+
+        if let Node::Document(document) = root {
+            return Ok(Some(document));
+        }
+
+        let root_data: NodeData = (&root).into();
+        root_data
+            .owner_document
+            .borrow(|owner_document| match owner_document {
+                Some(owner_document) => owner_document.get(node_data.env),
+                None => Ok(None),
+            })
+    }
+
+    /// The base URL of the node document, see
+    /// https://dom.spec.whatwg.org/#dom-node-baseuri
+    pub(crate) fn get_base_uri(&self) -> Result<String> {
+        let url = match self.get_node_document()? {
+            Some(document) => document.base_url()?,
+            None => about_blank(),
+        };
+
+        Ok(url.into())
+    }
+
+    /// See https://dom.spec.whatwg.org/#concept-node-clone
+    pub(crate) fn clone_node(
+        &self,
+        deep: Option<bool>,
+    ) -> Result<Node> {
+        let clone = match self {
+            Node::Comment(r) => r.clone_node()?.into(),
+            Node::DocumentType(r) => r.clone_node(deep)?.into(),
+            Node::Document(r) => r.clone_node(deep)?.into(),
+            Node::DocumentFragment(r) => r.clone_node(deep)?.into(),
+            Node::Element(r) => r.clone_node(deep)?.into(),
+            Node::Text(r) => r.clone_node()?.into(),
+        };
+        Ok(clone)
+    }
+
+    /// Appends deep clones of the children of this node to `clone`.
+    pub(crate) fn clone_children_into(
+        &self,
+        clone: &Node,
+    ) -> Result<()> {
+        let node_data: NodeData = self.into();
+        let children = self
+            .shallow_child_nodes_iter::<ChildNode>()
+            .map(|child| child.clone_node(Some(true)))
+            .collect::<Result<Vec<_>>>()?;
+
+        clone.insert_nodes(node_data.env, children, &InsertPosition::Append)
+    }
+
+    /// Gives `clone`, a copy of this node, the same node document.
+    pub(crate) fn with_node_document<T>(
+        &self,
+        clone: Reference<T>,
+    ) -> Result<Reference<T>>
+    where
+        Node: From<Reference<T>>,
+    {
+        let node_data: NodeData = self.into();
+        let document = self.get_node_document()?;
+        Node::from(clone.clone(node_data.env)?)
+            .set_node_document(document.as_ref())?;
+
+        Ok(clone)
+    }
+
+    pub(crate) fn set_node_document(
+        &self,
+        document: Option<&Reference<Document>>,
+    ) -> Result<()> {
+        let node_data: NodeData = self.into();
+        let document = document
+            .map(|document| document.clone(node_data.env))
+            .transpose()?;
+
+        node_data
+            .owner_document
+            .set(document.map(OwnerDocument::Strong));
 
         Ok(())
     }
 
+    /// Keeps only a weak reference to the node document of `self`, the
+    /// template contents of an element, see `OwnerDocument::Weak`.
+    pub(crate) fn weaken_node_document(&self) {
+        let node_data: NodeData = self.into();
+        node_data.owner_document.borrow_mut(|owner_document| {
+            if let Some(OwnerDocument::Strong(document)) = owner_document {
+                *owner_document =
+                    Some(OwnerDocument::Weak(document.downgrade().into()));
+            }
+        });
+    }
+
+    /// The child positions leading from the root down to this node, which
+    /// compare in tree order.
+    pub(crate) fn get_tree_position(&self) -> Result<Vec<usize>> {
+        let node_data: NodeData = self.into();
+        let mut maybe_ctx = node_data.parent_context.cloned();
+        let mut position = vec![];
+
+        while let Some(ctx) = maybe_ctx {
+            position.push(ctx.position);
+
+            let parent_node_data: NodeData = (&ctx).try_into()?;
+            maybe_ctx = parent_node_data.parent_context.cloned();
+        }
+
+        position.reverse();
+        Ok(position)
+    }
+
+    /// The descendants of this node found under `key` in the element index,
+    /// in tree order. `None` if the index can't be used or would be slower,
+    /// in which case the tree has to be walked instead.
+    pub(crate) fn get_indexed_descendants(
+        &self,
+        key: &IndexKey,
+    ) -> Result<Option<IndexedDescendantsIterator>> {
+        let document = match self.get_connected_document()? {
+            Some(document) => document,
+            None => return Ok(None),
+        };
+
+        // ids and class names match case-insensitively in quirks mode
+        if matches!(document.quirks_mode, QuirksMode::Quirks)
+            || !document.element_index.is_selective(key)
+        {
+            return Ok(None);
+        }
+
+        let elements = document.element_index.get(document.env, key)?;
+
+        IndexedDescendantsIterator::new(self, elements).map(Some)
+    }
+
+    pub(crate) fn get_element_by_id(
+        &self,
+        id: String,
+    ) -> Result<Option<Reference<Element>>> {
+        if id.is_empty() {
+            return Ok(None);
+        }
+
+        let key = IndexKey::Id(id.clone());
+
+        if let Some(mut elements) = self.get_indexed_descendants(&key)? {
+            return Ok(elements.next());
+        }
+
+        Ok(self
+            .deep_child_nodes_iter()
+            .find(|element: &Reference<Element>| element.get_id() == id))
+    }
+
     fn sync_parent_context(&self) {
         let parent_node_data: NodeData = self.into();
         parent_node_data.child_nodes.borrow(|child_nodes| {
@@ -260,7 +575,7 @@ impl Node {
                     child_nodes.get(index).unwrap().into();
 
                 node_data.parent_context.borrow_mut(|parent_context| {
-                    if let Some(mut ctx) = parent_context.as_mut() {
+                    if let Some(ctx) = parent_context.as_mut() {
                         ctx.position = index;
                         ctx.node = self.into();
                     } else {
@@ -275,6 +590,121 @@ impl Node {
         });
     }
 
+    fn get_id(&self) -> usize {
+        match self {
+            Node::Comment(r) => r.id,
+            Node::DocumentType(r) => r.id,
+            Node::Document(r) => r.id,
+            Node::DocumentFragment(r) => r.id,
+            Node::Element(r) => r.id,
+            Node::Text(r) => r.id,
+        }
+    }
+
+    /// The top-most ancestor of this node, or the node itself.
+    pub(crate) fn get_root(
+        &self,
+        env: Env,
+    ) -> Result<Node> {
+        match self.parent_iterator::<ParentNode>().last() {
+            Some(root) => root?.upgrade(env),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// See https://dom.spec.whatwg.org/#dom-node-comparedocumentposition
+    pub(crate) fn compare_document_position(
+        &self,
+        env: Env,
+        other: &Node,
+    ) -> Result<u32> {
+        if self == other {
+            return Ok(0);
+        }
+
+        if self.get_root(env)? != other.get_root(env)? {
+            // any order, as long as it is consistent
+            let order = match other.get_id() < self.get_id() {
+                true => DocumentPosition::Preceding,
+                false => DocumentPosition::Following,
+            };
+
+            return Ok(DocumentPosition::Disconnected as u32
+                | DocumentPosition::ImplementationSpecific as u32
+                | order as u32);
+        }
+
+        let position = self.get_tree_position()?;
+        let other_position = other.get_tree_position()?;
+
+        let document_position = if position.starts_with(&other_position) {
+            DocumentPosition::Contains as u32
+                | DocumentPosition::Preceding as u32
+        } else if other_position.starts_with(&position) {
+            DocumentPosition::ContainedBy as u32
+                | DocumentPosition::Following as u32
+        } else if other_position < position {
+            DocumentPosition::Preceding as u32
+        } else {
+            DocumentPosition::Following as u32
+        };
+
+        Ok(document_position)
+    }
+
+    /// See https://dom.spec.whatwg.org/#concept-node-equals
+    pub(crate) fn is_equal_node(
+        &self,
+        other: &Node,
+    ) -> bool {
+        let is_equal = match (self, other) {
+            (Node::Comment(left), Node::Comment(right)) => {
+                left.data == right.data
+            },
+            (Node::DocumentType(left), Node::DocumentType(right)) => {
+                left.name == right.name
+                    && left.public_id == right.public_id
+                    && left.system_id == right.system_id
+            },
+            (Node::Document(_), Node::Document(_))
+            | (Node::DocumentFragment(_), Node::DocumentFragment(_)) => true,
+            (Node::Element(left), Node::Element(right)) => {
+                let left_attributes = &left.attributes_wrapper;
+                let right_attributes = &right.attributes_wrapper;
+
+                left.name == right.name
+                    && left_attributes.iter().len()
+                        == right_attributes.iter().len()
+                    && left_attributes.iter().all(|attribute| {
+                        right_attributes.iter().any(|other| {
+                            attribute.name.ns == other.name.ns
+                                && attribute.name.local == other.name.local
+                                && attribute.value == other.value
+                        })
+                    })
+            },
+            (Node::Text(left), Node::Text(right)) => left.data == right.data,
+            _ => false,
+        };
+
+        if !is_equal {
+            return false;
+        }
+
+        let children: Vec<ChildNode> =
+            self.shallow_child_nodes_iter().collect();
+        let other_children: Vec<ChildNode> =
+            other.shallow_child_nodes_iter().collect();
+
+        children.len() == other_children.len()
+            && children
+                .into_iter()
+                .zip(other_children)
+                .all(|(child, other)| {
+                    Node::from(child).is_equal_node(&Node::from(other))
+                })
+    }
+
     pub(crate) fn get_position(&self) -> Result<usize> {
         let node_data: NodeData = self.into();
         node_data
@@ -288,10 +718,10 @@ impl Node {
     pub(crate) fn get_node_name(&self) -> String {
         match self {
             Node::Comment(_) => "#comment".to_string(),
-            Node::DocumentType(_) => "html".to_string(),
+            Node::DocumentType(r) => r.name.clone(),
             Node::Document(_) => "#document".to_string(),
             Node::DocumentFragment(_) => "#document-fragment".to_string(),
-            Node::Element(r) => r.name.local.to_string().to_uppercase(),
+            Node::Element(r) => r.get_html_uppercased_qualified_name(),
             Node::Text(_) => "#text".to_string(),
         }
     }
@@ -361,14 +791,44 @@ impl Node {
         self.new_sibling_iterator(SiblingIteratorType::Next)
     }
 
+    /// Iterates the descendants matching `selectors`. With `use_index` the
+    /// candidates are taken from the document's element index when the
+    /// selectors allow it, which pays off unless only the first match is
+    /// needed and the candidates are many.
     pub(crate) fn selectors_iter(
         &self,
-        selectors: String,
+        selectors: Either<String, &CompiledSelector>,
+        use_index: bool,
     ) -> Result<SelectorsIterator> {
-        Ok(SelectorsIterator::new(
-            crate::Selectors::compile(selectors)?,
-            self.deep_child_nodes_iter(),
-        ))
+        let selectors = crate::Selectors::from_arg(selectors)?;
+
+        let maybe_indexed = match selectors.index_key() {
+            Some(key) if use_index || matches!(key, IndexKey::Id(_)) => {
+                self.get_indexed_descendants(&key)?
+            },
+            _ => None,
+        };
+
+        let matcher = SelectorsMatcher::new(selectors, self)?;
+        let ancestor_filter = match matcher.uses_ancestor_filter() {
+            true => Some(AncestorFilter::new(self)?),
+            false => None,
+        };
+
+        let candidates = match (maybe_indexed, ancestor_filter) {
+            (Some(elements), Some(filter)) => SelectorsCandidates::Indexed(
+                elements.with_ancestor_filter(filter),
+            ),
+            (Some(elements), None) => SelectorsCandidates::Indexed(elements),
+            (None, Some(filter)) => SelectorsCandidates::Tree(
+                self.deep_child_nodes_iter().with_ancestor_filter(filter),
+            ),
+            (None, None) => {
+                SelectorsCandidates::Tree(self.deep_child_nodes_iter())
+            },
+        };
+
+        Ok(SelectorsIterator::new(matcher, candidates))
     }
 
     pub(crate) fn try_get_child_node<T>(
@@ -425,50 +885,490 @@ impl Node {
         Ok(())
     }
 
-    pub(crate) fn prepend(
+    pub(crate) fn append_child(
         &self,
         env: Env,
-        node: Either<ChildNode, String>,
+        node: &Node,
     ) -> Result<()> {
-        let child_node: ChildNode = match node {
-            Either::A(child_node) => child_node,
-            Either::B(data) => {
-                let text = Text::new_reference(env, data)?;
-                text.into()
+        self.ensure_insertion_validity(env, std::slice::from_ref(node), None)?;
+
+        self.insert_nodes(env, node.to_child_nodes()?, &InsertPosition::Append)
+    }
+
+    pub(crate) fn insert_before(
+        &self,
+        env: Env,
+        new_node: &Node,
+        reference_node: &ChildNode,
+    ) -> Result<()> {
+        self.ensure_insertion_validity(
+            env,
+            std::slice::from_ref(new_node),
+            Some(reference_node),
+        )?;
+
+        let nodes = new_node.to_child_nodes()?;
+
+        let reference_node = if nodes.contains(reference_node) {
+            Node::from(reference_node.clone()).next_iterator()?.next()
+        } else {
+            Some(reference_node.clone())
+        };
+
+        self.insert_nodes_before(env, nodes, reference_node.as_ref())
+    }
+
+    pub(crate) fn remove_child(
+        &self,
+        env: Env,
+        child: &ChildNode,
+    ) -> Result<()> {
+        self.ensure_child(env, child)?;
+
+        self.remove_node(child)
+    }
+
+    /// The nodes inserted in place of `self`: a `DocumentFragment` is
+    /// replaced by its children.
+    pub(crate) fn to_child_nodes(&self) -> Result<Vec<ChildNode>> {
+        match self {
+            Node::DocumentFragment(_) => {
+                Ok(self.shallow_child_nodes_iter().collect())
             },
+            Node::Document(_) => Err(Error::new(
+                Status::InvalidArg,
+                "A Document cannot be inserted".to_string(),
+            )),
+            _ => Ok(vec![self.into()]),
+        }
+    }
+
+    /// Inserts `nodes` before `reference_node`, or appends them without one.
+    /// The position is looked up after `nodes` are removed from their
+    /// parents, as that may move `reference_node`.
+    fn insert_nodes_before(
+        &self,
+        env: Env,
+        nodes: Vec<ChildNode>,
+        reference_node: Option<&ChildNode>,
+    ) -> Result<()> {
+        for node in &nodes {
+            node.remove()?;
+        }
+
+        let position = match reference_node {
+            Some(reference_node) => InsertPosition::Position(
+                Node::from(reference_node.clone()).get_position()?,
+            ),
+            None => InsertPosition::Append,
         };
 
-        self.insert_node(env, child_node, &InsertPosition::Prepend)?;
+        self.insert_nodes(env, nodes, &position)
+    }
+
+    pub(crate) fn replace_child(
+        &self,
+        env: Env,
+        node: &Node,
+        child: &ChildNode,
+    ) -> Result<()> {
+        self.ensure_replacement_validity(
+            env,
+            std::slice::from_ref(node),
+            child,
+        )?;
+
+        let nodes = node.to_child_nodes()?;
+
+        let reference_node = Node::from(child.clone())
+            .next_iterator::<ChildNode>()?
+            .find(|sibling| !nodes.contains(sibling));
+
+        child.remove()?;
+
+        self.insert_nodes_before(env, nodes, reference_node.as_ref())
+    }
+
+    /// Throws a `NotFoundError` unless `child` is a child of `self`.
+    fn ensure_child(
+        &self,
+        env: Env,
+        child: &ChildNode,
+    ) -> Result<()> {
+        let parent = Node::from(child.clone()).parent_as_node(env)?;
+
+        if parent.as_ref() != Some(self) {
+            return Err(DomException::new(
+                DomExceptionName::NotFoundError,
+                "The node is not a child of this node",
+            )
+            .into_error(env));
+        }
 
         Ok(())
     }
 
-    pub(crate) fn append(
+    pub(crate) fn is_inclusive_ancestor_of(
+        &self,
+        env: Env,
+        node: &Node,
+    ) -> Result<bool> {
+        if self == node {
+            return Ok(true);
+        }
+
+        for parent in node.parent_iterator::<ParentNode>() {
+            if parent?.upgrade(env)? == *self {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// "Ensure pre-insertion validity" of `nodes` into `self` before `child`,
+    /// see https://dom.spec.whatwg.org/#concept-node-ensure-pre-insertion-validity
+    fn ensure_insertion_validity(
+        &self,
+        env: Env,
+        nodes: &[Node],
+        child: Option<&ChildNode>,
+    ) -> Result<()> {
+        self.ensure_validity(env, nodes, child, false)
+    }
+
+    /// The checks of "replace" `child` with `nodes`, see
+    /// https://dom.spec.whatwg.org/#concept-node-replace
+    fn ensure_replacement_validity(
+        &self,
+        env: Env,
+        nodes: &[Node],
+        child: &ChildNode,
+    ) -> Result<()> {
+        self.ensure_validity(env, nodes, Some(child), true)
+    }
+
+    fn ensure_validity(
         &self,
         env: Env,
-        node: Either<ChildNode, String>,
+        nodes: &[Node],
+        child: Option<&ChildNode>,
+        replacing: bool,
     ) -> Result<()> {
-        let child_node: ChildNode = match node {
-            Either::A(child_node) => child_node,
-            Either::B(data) => {
-                let text = Text::new_reference(env, data)?;
-                text.into()
+        let hierarchy_request_error = |message: &str| {
+            Err(DomException::new(
+                DomExceptionName::HierarchyRequestError,
+                message,
+            )
+            .into_error(env))
+        };
+
+        for node in nodes {
+            if matches!(node, Node::Document(_)) {
+                return hierarchy_request_error(
+                    "A Document cannot be inserted",
+                );
+            }
+
+            if node.is_inclusive_ancestor_of(env, self)? {
+                return hierarchy_request_error(
+                    "The new child is an ancestor of the parent",
+                );
+            }
+        }
+
+        if let Some(child) = child {
+            self.ensure_child(env, child)?;
+        }
+
+        let is_document = matches!(self, Node::Document(_));
+        let mut elements = 0;
+        let mut doctypes = 0;
+        let mut length = 0;
+
+        for node in nodes {
+            for child_node in node.to_child_nodes()? {
+                match child_node {
+                    ChildNode::Text(_) if is_document => {
+                        return hierarchy_request_error(
+                            "Text cannot be a child of a Document",
+                        );
+                    },
+                    ChildNode::DocumentType(_) if !is_document => {
+                        return hierarchy_request_error(
+                            "A DocumentType can only be a child of a Document",
+                        );
+                    },
+                    ChildNode::DocumentType(_) => doctypes += 1,
+                    ChildNode::Element(_) => elements += 1,
+                    _ => {},
+                }
+                length += 1;
+            }
+        }
+
+        if !is_document || elements + doctypes == 0 {
+            return Ok(());
+        }
+
+        if elements > 1 || doctypes > 1 || (doctypes == 1 && length > 1) {
+            return hierarchy_request_error(
+                "A Document can only have one element and one DocumentType",
+            );
+        }
+
+        let children: Vec<ChildNode> =
+            self.shallow_child_nodes_iter().collect();
+        let child_position = child
+            .and_then(|child| children.iter().position(|node| node == child));
+        let (preceding, following) = match child_position {
+            Some(position) => {
+                (&children[..position], &children[position + 1..])
             },
+            None => (&children[..], &children[children.len()..]),
+        };
+        let others = || {
+            children
+                .iter()
+                .filter(move |node| !replacing || Some(*node) != child)
         };
 
-        self.insert_node(env, child_node, &InsertPosition::Append)?;
+        let is_valid = if elements == 1 {
+            !others().any(|node| matches!(node, ChildNode::Element(_)))
+                && !matches!(child, Some(ChildNode::DocumentType(_)))
+                && !following
+                    .iter()
+                    .any(|node| matches!(node, ChildNode::DocumentType(_)))
+        } else {
+            !others().any(|node| matches!(node, ChildNode::DocumentType(_)))
+                && !preceding
+                    .iter()
+                    .any(|node| matches!(node, ChildNode::Element(_)))
+        };
+
+        if !is_valid {
+            return hierarchy_request_error(
+                "A Document can only have one element and one DocumentType",
+            );
+        }
 
         Ok(())
     }
+}
 
-    pub(crate) fn insert_before(
+impl Node {
+    fn convert_nodes(
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<Vec<Node>> {
+        nodes
+            .into_iter()
+            .map(|node| match node {
+                Either::A(node) => Ok(node),
+                Either::B(data) => {
+                    Text::new_reference(env, data).map(Into::into)
+                },
+            })
+            .collect()
+    }
+
+    /// The child nodes to insert for `nodes`. Like appending them to a
+    /// fragment one by one, a node passed more than once ends up at its last
+    /// position.
+    fn flatten_nodes(nodes: &[Node]) -> Result<Vec<ChildNode>> {
+        let mut child_nodes = vec![];
+        for node in nodes.iter().rev() {
+            for child_node in node.to_child_nodes()?.into_iter().rev() {
+                if !child_nodes.contains(&child_node) {
+                    child_nodes.push(child_node);
+                }
+            }
+        }
+        child_nodes.reverse();
+
+        Ok(child_nodes)
+    }
+
+    pub(crate) fn prepend(
+        &self,
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<()> {
+        let nodes = Self::convert_nodes(env, nodes)?;
+        let first_child = self.get_child_node(0);
+        self.ensure_insertion_validity(env, &nodes, first_child.as_ref())?;
+
+        let nodes = Self::flatten_nodes(&nodes)?;
+        self.insert_nodes(env, nodes, &InsertPosition::Prepend)
+    }
+
+    pub(crate) fn append(
+        &self,
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<()> {
+        let nodes = Self::convert_nodes(env, nodes)?;
+        self.ensure_insertion_validity(env, &nodes, None)?;
+
+        let nodes = Self::flatten_nodes(&nodes)?;
+        self.insert_nodes(env, nodes, &InsertPosition::Append)
+    }
+
+    pub(crate) fn before(
+        &self,
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<()> {
+        let parent = match self.parent_as_node(env)? {
+            Some(parent) => parent,
+            None => return Ok(()),
+        };
+        let nodes = Self::convert_nodes(env, nodes)?;
+        let flattened = Self::flatten_nodes(&nodes)?;
+
+        let this: ChildNode = self.into();
+        let viable_previous_sibling = self
+            .previous_iterator::<ChildNode>()?
+            .find(|sibling| !flattened.contains(sibling));
+        let viable_next_sibling = self
+            .next_iterator::<ChildNode>()?
+            .find(|sibling| !flattened.contains(sibling));
+
+        // the node the others end up in front of
+        let reference_node = match flattened.contains(&this) {
+            true => viable_next_sibling,
+            false => Some(this),
+        };
+        parent.ensure_insertion_validity(
+            env,
+            &nodes,
+            reference_node.as_ref(),
+        )?;
+
+        for node in &flattened {
+            node.remove()?;
+        }
+
+        let reference_node = match viable_previous_sibling {
+            Some(sibling) => Node::from(sibling).next_iterator()?.next(),
+            None => parent.get_child_node(0),
+        };
+
+        parent.insert_nodes_before(env, flattened, reference_node.as_ref())
+    }
+
+    pub(crate) fn after(
         &self,
         env: Env,
-        new_node: ChildNode,
-        reference_node: &Node,
+        nodes: Vec<Either<Node, String>>,
     ) -> Result<()> {
-        let position = reference_node.get_position()?;
+        let parent = match self.parent_as_node(env)? {
+            Some(parent) => parent,
+            None => return Ok(()),
+        };
+        let nodes = Self::convert_nodes(env, nodes)?;
+        let flattened = Self::flatten_nodes(&nodes)?;
+
+        let viable_next_sibling = self
+            .next_iterator::<ChildNode>()?
+            .find(|sibling| !flattened.contains(sibling));
+        parent.ensure_insertion_validity(
+            env,
+            &nodes,
+            viable_next_sibling.as_ref(),
+        )?;
 
-        self.insert_node(env, new_node, &InsertPosition::Position(position))
+        parent.insert_nodes_before(env, flattened, viable_next_sibling.as_ref())
+    }
+
+    pub(crate) fn replace_with(
+        &self,
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<()> {
+        let parent = match self.parent_as_node(env)? {
+            Some(parent) => parent,
+            None => return Ok(()),
+        };
+        let nodes = Self::convert_nodes(env, nodes)?;
+        let flattened = Self::flatten_nodes(&nodes)?;
+
+        let this: ChildNode = self.into();
+        let viable_next_sibling = self
+            .next_iterator::<ChildNode>()?
+            .find(|sibling| !flattened.contains(sibling));
+
+        // `self` stays in place, to be replaced, unless it is one of `nodes`
+        let is_replaced = !flattened.contains(&this);
+        match is_replaced {
+            true => parent.ensure_replacement_validity(env, &nodes, &this)?,
+            false => parent.ensure_insertion_validity(
+                env,
+                &nodes,
+                viable_next_sibling.as_ref(),
+            )?,
+        }
+
+        if is_replaced {
+            for node in &flattened {
+                node.remove()?;
+            }
+            parent.insert_nodes_before(env, flattened, Some(&this))?;
+            this.remove()
+        } else {
+            parent.insert_nodes_before(
+                env,
+                flattened,
+                viable_next_sibling.as_ref(),
+            )
+        }
+    }
+
+    pub(crate) fn replace_children(
+        &self,
+        env: Env,
+        nodes: Vec<Either<Node, String>>,
+    ) -> Result<()> {
+        let nodes = Self::convert_nodes(env, nodes)?;
+        self.ensure_insertion_validity(env, &nodes, None)?;
+
+        let nodes = Self::flatten_nodes(&nodes)?;
+
+        for node in &nodes {
+            node.remove()?;
+        }
+
+        while let Some(child_node) = self.get_child_node(0) {
+            child_node.remove()?;
+        }
+
+        self.insert_nodes(env, nodes, &InsertPosition::Append)
+    }
+
+    /// Inserts `nodes` at `position` relative to `self`, with the same checks
+    /// as the other insertion methods, see
+    /// https://dom.spec.whatwg.org/#insert-adjacent
+    pub(crate) fn insert_adjacent(
+        &self,
+        env: Env,
+        nodes: Vec<Node>,
+        position: &InsertPosition,
+    ) -> Result<()> {
+        let nodes = nodes.into_iter().map(Either::A).collect();
+
+        match position {
+            InsertPosition::Before => self.before(env, nodes),
+            InsertPosition::Prepend => self.prepend(env, nodes),
+            InsertPosition::Append => self.append(env, nodes),
+            InsertPosition::After => self.after(env, nodes),
+            InsertPosition::Position(position) => {
+                let nodes = Self::convert_nodes(env, nodes)?;
+                let child = self.get_child_node(*position);
+                self.ensure_insertion_validity(env, &nodes, child.as_ref())?;
+
+                let nodes = Self::flatten_nodes(&nodes)?;
+                self.insert_nodes_before(env, nodes, child.as_ref())
+            },
+        }
     }
 }
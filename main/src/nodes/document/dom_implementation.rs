@@ -0,0 +1,172 @@
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    Result,
+    Status,
+};
+
+use crate::{
+    about_blank,
+    names,
+    Document,
+    DocumentType,
+    LazyReference,
+    QuirksMode,
+    Text,
+    WeakReference,
+};
+
+/// Creates documents and doctypes without parsing, as returned by
+/// `document.implementation`, see
+/// https://dom.spec.whatwg.org/#interface-domimplementation
+#[napi(js_name = "DOMImplementation")]
+pub struct DomImplementation {
+    document: WeakReference<Document>,
+    env: Env,
+}
+
+impl DomImplementation {
+    /// Creates the implementation of `document`, which keeps it, see
+    /// `Document::get_implementation`.
+    pub(crate) fn create(
+        document: WeakReference<Document>,
+        env: Env,
+    ) -> Result<Reference<Self>> {
+        Self::into_reference(
+            Self {
+                document,
+                env,
+            },
+            env,
+        )
+    }
+
+    /// A document without a window or a URL of its own.
+    fn create_document_of_type(
+        &self,
+        content_type: &str,
+    ) -> Result<Reference<Document>> {
+        Document::new_reference(
+            self.env,
+            QuirksMode::NoQuirks,
+            None,
+            Default::default(),
+            about_blank(),
+            content_type.to_string(),
+            LazyReference::new(self.env),
+        )
+    }
+}
+
+#[napi]
+impl DomImplementation {
+    /// Implementations are only created by their document, like in
+    /// browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+
+    /// See https://dom.spec.whatwg.org/#dom-domimplementation-createdocumenttype
+    #[napi]
+    pub fn create_document_type(
+        &self,
+        name: String,
+        public_id: String,
+        system_id: String,
+    ) -> Result<Reference<DocumentType>> {
+        if !names::is_valid_doctype_name(&name) {
+            return Err(names::invalid_character_error(self.env, &name));
+        }
+
+        let document = self.document.upgrade(self.env)?;
+        document.adopt_created(DocumentType::new_reference(
+            self.env, name, public_id, system_id,
+        )?)
+    }
+
+    /// An XML document with a document element named `qualified_name`,
+    /// unless it is empty, see
+    /// https://dom.spec.whatwg.org/#dom-domimplementation-createdocument
+    #[napi]
+    pub fn create_document(
+        &self,
+        namespace: Option<String>,
+        qualified_name: Option<String>,
+        doctype: Option<&DocumentType>,
+    ) -> Result<Reference<Document>> {
+        let content_type = match namespace.as_deref() {
+            Some("http://www.w3.org/1999/xhtml") => "application/xhtml+xml",
+            Some("http://www.w3.org/2000/svg") => "image/svg+xml",
+            _ => "application/xml",
+        };
+        let document = self.create_document_of_type(content_type)?;
+
+        let element = match qualified_name.filter(|name| !name.is_empty()) {
+            Some(qualified_name) => {
+                Some(document.create_element_ns(namespace, qualified_name)?)
+            },
+            None => None,
+        };
+
+        if let Some(doctype) = doctype {
+            document
+                .as_node()
+                .append_child(self.env, &doctype.as_node())?;
+        }
+
+        if let Some(element) = element {
+            document.as_node().append_child(self.env, &element.into())?;
+        }
+
+        Ok(document)
+    }
+
+    /// An HTML document with a doctype, a head with a `title` if there is
+    /// one, and a body, see
+    /// https://dom.spec.whatwg.org/#dom-domimplementation-createhtmldocument
+    #[napi(js_name = "createHTMLDocument")]
+    pub fn create_html_document(
+        &self,
+        title: Option<String>,
+    ) -> Result<Reference<Document>> {
+        let document = self.create_document_of_type("text/html")?;
+        let document_node = document.as_node();
+
+        let doctype = document.adopt_created(DocumentType::new_reference(
+            self.env,
+            "html".to_string(),
+            String::new(),
+            String::new(),
+        )?)?;
+        document_node.append_child(self.env, &doctype.into())?;
+
+        let html = document.create_element("html".to_string())?;
+        document_node.append_child(self.env, &html.clone(self.env)?.into())?;
+
+        let head = document.create_element("head".to_string())?;
+        html.as_node()
+            .append_child(self.env, &head.clone(self.env)?.into())?;
+
+        if let Some(title) = title {
+            let element = document.create_element("title".to_string())?;
+            let text = document
+                .adopt_created(Text::new_reference(self.env, title)?)?;
+            element.as_node().append_child(self.env, &text.into())?;
+            head.as_node().append_child(self.env, &element.into())?;
+        }
+
+        let body = document.create_element("body".to_string())?;
+        html.as_node().append_child(self.env, &body.into())?;
+
+        Ok(document)
+    }
+
+    /// Always `true`, like in browsers, see
+    /// https://dom.spec.whatwg.org/#dom-domimplementation-hasfeature
+    #[napi]
+    pub fn has_feature(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use html5ever::{
+    local_name,
+    LocalName,
+};
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Result,
+};
+
+use crate::{
+    Element,
+    WeakReference,
+};
+
+/// The buckets an element is filed under, taken from its `id` and `class`
+/// attributes.
+#[derive(Default)]
+pub(crate) struct IndexedAttributes {
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl From<&Element> for IndexedAttributes {
+    fn from(element: &Element) -> Self {
+        let attributes = &element.attributes_wrapper;
+
+        Self {
+            id: attributes
+                .get_attribute(local_name!("id"))
+                .map(|attribute| attribute.value.to_string())
+                .filter(|id| !id.is_empty()),
+            classes: attributes
+                .get_attribute(local_name!("class"))
+                .map(|attribute| {
+                    attribute
+                        .value
+                        .split_ascii_whitespace()
+                        .map(ToString::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// What to look up in the index, see `Selectors::index_key`.
+pub(crate) enum IndexKey {
+    Id(String),
+    Class(String),
+    LocalName {
+        name: LocalName,
+        lower_name: LocalName,
+    },
+}
+
+/// The index is used for keys that at most one in this many elements are
+/// filed under, see `ElementIndex::is_selective`.
+const MAX_SHARE: usize = 8;
+
+// Weak, as the elements are kept alive by the tree they are connected to.
+type Bucket = HashMap<usize, WeakReference<Element>>;
+
+/// Elements connected to a document by `id`, class name and local name.
+///
+/// Buckets are unordered; callers sort the elements into tree order.
+#[derive(Default)]
+pub(crate) struct ElementIndex {
+    ids: HashMap<String, Bucket>,
+    classes: HashMap<String, Bucket>,
+    local_names: HashMap<LocalName, Bucket>,
+}
+
+impl ElementIndex {
+    pub(crate) fn insert(
+        &mut self,
+        element: &Reference<Element>,
+    ) {
+        let attributes = IndexedAttributes::from(&**element);
+        self.insert_attributes(element, &attributes);
+
+        Self::insert_into(
+            &mut self.local_names,
+            element.name.local.clone(),
+            element,
+        );
+    }
+
+    pub(crate) fn remove(
+        &mut self,
+        element: &Element,
+    ) {
+        self.remove_attributes(element.id, &element.into());
+
+        Self::remove_from(
+            &mut self.local_names,
+            &element.name.local,
+            element.id,
+        );
+    }
+
+    /// Moves `element` from the buckets of its `previous` attributes to the
+    /// ones of its current attributes.
+    pub(crate) fn update(
+        &mut self,
+        element: &Reference<Element>,
+        previous: &IndexedAttributes,
+    ) {
+        self.remove_attributes(element.id, previous);
+        self.insert_attributes(element, &(&**element).into())
+    }
+
+    /// Whether looking up `key` narrows the elements down enough to beat
+    /// walking the tree, as finding the elements in tree order costs more per
+    /// element than the walk does.
+    pub(crate) fn is_selective(
+        &self,
+        key: &IndexKey,
+    ) -> bool {
+        let len: usize = self.local_names.values().map(HashMap::len).sum();
+
+        self.weak_references(key).len() * MAX_SHARE <= len
+    }
+
+    pub(crate) fn get(
+        &self,
+        env: Env,
+        key: &IndexKey,
+    ) -> Result<Vec<Reference<Element>>> {
+        self.weak_references(key)
+            .into_iter()
+            .map(|element| element.upgrade(env))
+            .collect()
+    }
+
+    fn weak_references(
+        &self,
+        key: &IndexKey,
+    ) -> Vec<&WeakReference<Element>> {
+        match key {
+            IndexKey::Id(id) => Self::bucket(&self.ids, id),
+            IndexKey::Class(class) => Self::bucket(&self.classes, class),
+            IndexKey::LocalName {
+                name,
+                lower_name,
+            } => {
+                let mut elements = Self::bucket(&self.local_names, lower_name);
+                if name != lower_name {
+                    elements.extend(Self::bucket(&self.local_names, name));
+                }
+                elements
+            },
+        }
+    }
+
+    fn insert_attributes(
+        &mut self,
+        element: &Reference<Element>,
+        attributes: &IndexedAttributes,
+    ) {
+        if let Some(id) = &attributes.id {
+            Self::insert_into(&mut self.ids, id.clone(), element);
+        }
+
+        for class in &attributes.classes {
+            Self::insert_into(&mut self.classes, class.clone(), element);
+        }
+    }
+
+    fn remove_attributes(
+        &mut self,
+        element_id: usize,
+        attributes: &IndexedAttributes,
+    ) {
+        if let Some(id) = &attributes.id {
+            Self::remove_from(&mut self.ids, id, element_id);
+        }
+
+        for class in &attributes.classes {
+            Self::remove_from(&mut self.classes, class, element_id);
+        }
+    }
+
+    fn bucket<'a, K, Q>(
+        buckets: &'a HashMap<K, Bucket>,
+        key: &Q,
+    ) -> Vec<&'a WeakReference<Element>>
+    where
+        K: std::borrow::Borrow<Q> + std::hash::Hash + Eq,
+        Q: std::hash::Hash + Eq + ?Sized,
+    {
+        buckets
+            .get(key)
+            .map(|bucket| bucket.values().collect())
+            .unwrap_or_default()
+    }
+
+    fn insert_into<K>(
+        buckets: &mut HashMap<K, Bucket>,
+        key: K,
+        element: &Reference<Element>,
+    ) where
+        K: std::hash::Hash + Eq,
+    {
+        buckets
+            .entry(key)
+            .or_default()
+            .insert(element.id, element.downgrade().into());
+    }
+
+    fn remove_from<K, Q>(
+        buckets: &mut HashMap<K, Bucket>,
+        key: &Q,
+        element_id: usize,
+    ) where
+        K: std::borrow::Borrow<Q> + std::hash::Hash + Eq,
+        Q: std::hash::Hash + Eq + ?Sized,
+    {
+        if let Some(bucket) = buckets.get_mut(key) {
+            bucket.remove(&element_id);
+
+            if bucket.is_empty() {
+                buckets.remove(key);
+            }
+        }
+    }
+}
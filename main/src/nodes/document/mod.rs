@@ -0,0 +1,632 @@
+mod dom_implementation;
+mod element_index;
+
+pub use dom_implementation::DomImplementation;
+pub(crate) use element_index::{
+    ElementIndex,
+    IndexKey,
+    IndexedAttributes,
+};
+use html5ever::{
+    expanded_name,
+    local_name,
+    namespace_url,
+    ns,
+    Attribute,
+    LocalName,
+    QualName,
+};
+use napi::{
+    bindgen_prelude::Reference,
+    Either,
+    Error,
+    JsObject,
+    Result,
+};
+use url::Url;
+
+use crate::{
+    names::{
+        self,
+        NameKind,
+    },
+    Attr,
+    ChildNode,
+    Comment,
+    DocumentFragment,
+    DocumentType,
+    DomException,
+    DomExceptionName,
+    Element,
+    Html5everDom,
+    HtmlCollection,
+    HtmlCollectionFilter,
+    InsertPosition,
+    LazyReference,
+    Node,
+    NodeList,
+    QuirksMode,
+    Text,
+    WeakReference,
+    Window,
+};
+
+#[create_node(has_children)]
+pub struct Document {
+    pub(crate) quirks_mode: QuirksMode,
+    pub(crate) window: Option<WeakReference<Window>>,
+    pub(crate) element_index: ElementIndex,
+    pub(crate) url: Url,
+    /// `text/html` for HTML documents, and an XML type otherwise.
+    pub(crate) content_type: String,
+    pub(crate) lazy_implementation: LazyReference<DomImplementation>,
+}
+
+/// The URL of documents that aren't loaded from anywhere, see
+/// https://html.spec.whatwg.org/#about:blank
+pub(crate) fn about_blank() -> Url {
+    Url::parse("about:blank").expect("about:blank is a valid URL")
+}
+
+#[napi]
+impl Document {
+    #[napi(getter)]
+    pub fn get_doctype(&self) -> Option<Reference<DocumentType>> {
+        let node: Node = self.into();
+
+        node.try_get_child_node(0).ok().flatten()
+    }
+
+    /// The first element child, if any, see
+    /// https://dom.spec.whatwg.org/#dom-document-documentelement
+    #[napi(getter)]
+    pub fn get_document_element(&self) -> Option<Reference<Element>> {
+        self.as_node().shallow_child_nodes_iter().next()
+    }
+
+    /// Whether this is an HTML document, rather than an XML one, see
+    /// https://dom.spec.whatwg.org/#html-document
+    pub(crate) fn is_html(&self) -> bool {
+        self.content_type == "text/html"
+    }
+
+    #[napi(getter)]
+    pub fn get_content_type(&self) -> String {
+        self.content_type.clone()
+    }
+
+    /// The same object on every access, see
+    /// https://dom.spec.whatwg.org/#dom-document-implementation
+    #[napi(getter)]
+    pub fn get_implementation(
+        &mut self
+    ) -> Result<Reference<DomImplementation>> {
+        let document = self.cyclic_reference.get()?.downgrade().into();
+        let env = self.env;
+
+        self.lazy_implementation
+            .get_or_init(|| DomImplementation::create(document, env))
+    }
+
+    #[napi(getter, js_name = "URL")]
+    pub fn get_url(&self) -> String {
+        self.url.to_string()
+    }
+
+    #[napi(getter, js_name = "documentURI")]
+    pub fn get_document_uri(&self) -> String {
+        self.url.to_string()
+    }
+
+    /// The URL relative URLs in this document resolve against, from the
+    /// first `base` element with an `href`, see
+    /// https://html.spec.whatwg.org/#document-base-url
+    pub fn base_url(&self) -> Result<Url> {
+        let key = IndexKey::LocalName {
+            name: local_name!("base"),
+            lower_name: local_name!("base"),
+        };
+        let mut first_base: Option<(Vec<usize>, String)> = None;
+
+        for element in self.element_index.get(self.env, &key)? {
+            if element.name.ns != ns!(html) {
+                continue;
+            }
+
+            let href = match element
+                .attributes_wrapper
+                .get_attribute(local_name!("href"))
+            {
+                Some(href) => href.value.to_string(),
+                None => continue,
+            };
+            let position = element.as_node().get_tree_position()?;
+
+            if first_base
+                .as_ref()
+                .is_none_or(|(first, _)| position < *first)
+            {
+                first_base = Some((position, href));
+            }
+        }
+
+        Ok(first_base
+            .and_then(|(_, href)| self.url.join(&href).ok())
+            .unwrap_or_else(|| self.url.clone()))
+    }
+
+    /// Parses `url` relative to the base URL of this document, like links
+    /// are, see https://html.spec.whatwg.org/#encoding-parsing-a-url
+    pub fn resolve_url(
+        &self,
+        url: &str,
+    ) -> Result<Option<Url>> {
+        Ok(self.base_url()?.join(url).ok())
+    }
+
+    #[napi(getter)]
+    pub fn get_default_view(&self) -> Option<WeakReference<Window>> {
+        self.window.clone()
+    }
+
+    /// The document element if it is an `html` element, see
+    /// https://html.spec.whatwg.org/#the-html-element-2
+    fn get_html_element(&self) -> Option<Reference<Element>> {
+        self.get_document_element().filter(|element| {
+            element.name.expanded() == expanded_name!(html "html")
+        })
+    }
+
+    /// The first child of the `html` element with one of the local `names`.
+    fn get_html_child(
+        &self,
+        names: &[LocalName],
+    ) -> Option<Reference<Element>> {
+        self.get_html_element()?
+            .as_node()
+            .shallow_child_nodes_iter::<Reference<Element>>()
+            .find(|element| {
+                element.name.ns == ns!(html)
+                    && names.contains(&element.name.local)
+            })
+    }
+
+    /// See https://html.spec.whatwg.org/#dom-document-head
+    #[napi(getter)]
+    pub fn get_head(&self) -> Option<Reference<Element>> {
+        self.get_html_child(&[local_name!("head")])
+    }
+
+    /// See https://html.spec.whatwg.org/#dom-document-body
+    #[napi(getter)]
+    pub fn get_body(&self) -> Option<Reference<Element>> {
+        self.get_html_child(&[local_name!("body"), local_name!("frameset")])
+    }
+
+    /// The first `title` element in the document, see
+    /// https://html.spec.whatwg.org/#the-title-element-2
+    fn get_title_element(&self) -> Option<Reference<Element>> {
+        self.as_node()
+            .deep_child_nodes_iter::<Reference<Element>>()
+            .find(|element| {
+                element.name.expanded() == expanded_name!(html "title")
+            })
+    }
+
+    /// The text of the `title` element, with its whitespace collapsed.
+    #[napi(getter)]
+    pub fn get_title(&self) -> String {
+        let title = match self.get_title_element() {
+            Some(title) => title,
+            None => return String::new(),
+        };
+
+        let text = title
+            .as_node()
+            .shallow_child_nodes_iter::<Reference<Text>>()
+            .map(|text| text.data.clone())
+            .collect::<String>();
+
+        text.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Replaces the text of the `title` element, which is added to the head
+    /// if there is none. Does nothing without a head, see
+    /// https://html.spec.whatwg.org/#document.title
+    #[napi(setter)]
+    pub fn set_title(
+        &self,
+        title: String,
+    ) -> Result<()> {
+        if self.get_html_element().is_none() {
+            return Ok(());
+        }
+
+        let element = match (self.get_title_element(), self.get_head()) {
+            (Some(element), _) => element,
+            (None, Some(head)) => {
+                let element = self.create_element("title".to_string())?;
+                head.as_node().insert_nodes(
+                    self.env,
+                    vec![element.clone(self.env)?.into()],
+                    &InsertPosition::Append,
+                )?;
+                element
+            },
+            (None, None) => return Ok(()),
+        };
+
+        element
+            .as_node()
+            .replace_children(self.env, vec![Either::B(title)])
+    }
+
+    /// The `dir` of the `html` element, see
+    /// https://html.spec.whatwg.org/#dom-document-dir
+    #[napi(getter)]
+    pub fn get_dir(&self) -> String {
+        let dir = self
+            .get_html_element()
+            .and_then(|html| html.get_attribute("dir".to_string()))
+            .unwrap_or_default();
+
+        ["ltr", "rtl", "auto"]
+            .into_iter()
+            .find(|keyword| keyword.eq_ignore_ascii_case(&dir))
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[napi(setter)]
+    pub fn set_dir(
+        &self,
+        dir: String,
+    ) -> Result<()> {
+        match self.get_html_element() {
+            Some(mut html) => html.set_attribute("dir".to_string(), dir),
+            None => Ok(()),
+        }
+    }
+
+    /// The `lang` of the `html` element.
+    #[napi(getter)]
+    pub fn get_lang(&self) -> String {
+        self.get_html_element()
+            .and_then(|html| html.get_attribute("lang".to_string()))
+            .unwrap_or_default()
+    }
+
+    #[napi(setter)]
+    pub fn set_lang(
+        &self,
+        lang: String,
+    ) -> Result<()> {
+        match self.get_html_element() {
+            Some(mut html) => html.set_attribute("lang".to_string(), lang),
+            None => Ok(()),
+        }
+    }
+
+    /// Always `null`, as nothing is ever focused.
+    #[napi(getter)]
+    pub fn get_active_element(&self) -> Option<Reference<Element>> {
+        None
+    }
+
+    fn get_collection(
+        &self,
+        filter: HtmlCollectionFilter,
+    ) -> Result<JsObject> {
+        HtmlCollection::create(self.env, self.into(), filter)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_forms(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Forms)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_images(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Images)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_embeds(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Embeds)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_scripts(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Scripts)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_links(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Links)
+    }
+
+    #[napi(getter, ts_return_type = "HTMLCollection")]
+    pub fn get_anchors(&self) -> Result<JsObject> {
+        self.get_collection(HtmlCollectionFilter::Anchors)
+    }
+
+    /// The HTML elements with `name` as their `name` attribute.
+    #[napi(ts_return_type = "NodeList")]
+    pub fn get_elements_by_name(
+        &self,
+        name: String,
+    ) -> Result<JsObject> {
+        NodeList::create(
+            self.env,
+            self.into(),
+            Some(HtmlCollectionFilter::Name(name)),
+        )
+    }
+
+    #[napi(getter)]
+    pub fn get_text_content(&self) -> Option<String> {
+        None
+    }
+
+    /// Makes this document the node document of `node`, a node created for
+    /// it.
+    pub(crate) fn adopt_created<T>(
+        &self,
+        node: Reference<T>,
+    ) -> Result<Reference<T>>
+    where
+        Node: From<Reference<T>>,
+    {
+        let document = self.cyclic_reference.get()?;
+        Node::from(node.clone(self.env)?).set_node_document(Some(&document))?;
+
+        Ok(node)
+    }
+
+    /// Creates an element of this document named `name`, with template
+    /// contents if it is an HTML `template`.
+    fn create_element_named(
+        &self,
+        name: QualName,
+    ) -> Result<Reference<Element>> {
+        let template_contents = match name.expanded()
+            == expanded_name!(html "template")
+        {
+            true => Some(self.adopt_created(
+                DocumentFragment::new_reference(self.env, self.quirks_mode)?,
+            )?),
+            false => None,
+        };
+
+        self.adopt_created(Element::create(
+            self.env,
+            vec![],
+            name,
+            template_contents,
+        )?)
+    }
+
+    /// See https://dom.spec.whatwg.org/#dom-document-createelement
+    #[napi]
+    pub fn create_element(
+        &self,
+        name: String,
+    ) -> Result<Reference<Element>> {
+        if !names::is_valid_element_local_name(&name) {
+            return Err(names::invalid_character_error(self.env, &name));
+        }
+
+        let (name, namespace) = match self.is_html() {
+            // HTML documents only have lowercase element names
+            true => (name.to_ascii_lowercase(), ns!(html)),
+            false if self.content_type == "application/xhtml+xml" => {
+                (name, ns!(html))
+            },
+            false => (name, ns!()),
+        };
+
+        self.create_element_named(QualName::new(None, namespace, name.into()))
+    }
+
+    /// See https://dom.spec.whatwg.org/#dom-document-createelementns
+    #[napi(js_name = "createElementNS")]
+    pub fn create_element_ns(
+        &self,
+        namespace: Option<String>,
+        qualified_name: String,
+    ) -> Result<Reference<Element>> {
+        let name = names::validate_and_extract(
+            self.env,
+            namespace,
+            &qualified_name,
+            NameKind::Element,
+        )?;
+
+        self.create_element_named(name)
+    }
+
+    #[napi]
+    pub fn create_comment(
+        &self,
+        data: String,
+    ) -> Result<Reference<Comment>> {
+        self.adopt_created(Comment::new_reference(self.env, data)?)
+    }
+
+    /// An attribute that is not on an element yet, see
+    /// https://dom.spec.whatwg.org/#dom-document-createattribute
+    #[napi]
+    pub fn create_attribute(
+        &self,
+        local_name: String,
+    ) -> Result<Attr> {
+        if !names::is_valid_attribute_local_name(&local_name) {
+            return Err(names::invalid_character_error(self.env, &local_name));
+        }
+
+        let local_name = match self.is_html() {
+            true => local_name.to_ascii_lowercase(),
+            false => local_name,
+        };
+
+        Ok(self.create_attr(QualName::new(None, ns!(), local_name.into())))
+    }
+
+    #[napi(js_name = "createAttributeNS")]
+    pub fn create_attribute_ns(
+        &self,
+        namespace: Option<String>,
+        qualified_name: String,
+    ) -> Result<Attr> {
+        let name = names::validate_and_extract(
+            self.env,
+            namespace,
+            &qualified_name,
+            NameKind::Attribute,
+        )?;
+
+        Ok(self.create_attr(name))
+    }
+
+    fn create_attr(
+        &self,
+        name: QualName,
+    ) -> Attr {
+        let attribute = Attribute {
+            name,
+            value: Default::default(),
+        };
+
+        Attr::new(attribute, None, self.env)
+    }
+
+    #[napi]
+    pub fn create_text_node(
+        &mut self,
+        data: String,
+    ) -> Result<Reference<Text>> {
+        self.adopt_created(Text::new_reference(self.env, data)?)
+    }
+
+    #[napi]
+    pub fn create_document_fragment(
+        &self,
+        html: Option<String>,
+    ) -> Result<Reference<DocumentFragment>> {
+        let fragment = self.adopt_created(DocumentFragment::new_reference(
+            self.env,
+            self.quirks_mode,
+        )?)?;
+
+        Html5everDom::parse_and_append(
+            self.env,
+            fragment.clone(self.env)?.into(),
+            html.unwrap_or_default(),
+        )?;
+
+        Ok(fragment)
+    }
+
+    #[napi]
+    pub fn clone_node(
+        &self,
+        deep: Option<bool>,
+    ) -> Result<Reference<Self>> {
+        // without a window, like documents created by DOMImplementation
+        let clone = Self::new_reference(
+            self.env,
+            self.quirks_mode,
+            None,
+            Default::default(),
+            self.url.clone(),
+            self.content_type.clone(),
+            LazyReference::new(self.env),
+        )?;
+
+        if deep.unwrap_or(false) {
+            let clone_node: Node = clone.clone(self.env)?.into();
+            self.as_node().clone_children_into(&clone_node)?;
+            // for the template contents, which are not connected
+            clone.adopt(&clone_node)?;
+        }
+
+        Ok(clone)
+    }
+
+    #[napi(ts_args_type = "node: ChildNode | DocumentFragment, deep?: boolean")]
+    pub fn import_node(
+        &self,
+        node: Node,
+        deep: Option<bool>,
+    ) -> Result<Node> {
+        if let Node::Document(_) = node {
+            return Err(self.cannot_adopt_document());
+        }
+
+        let clone = node.clone_node(deep)?;
+
+        self.adopt(&clone)?;
+
+        Ok(clone)
+    }
+
+    #[napi(
+        ts_generic_types = "T extends ChildNode | DocumentFragment",
+        ts_args_type = "node: T",
+        ts_return_type = "T"
+    )]
+    pub fn adopt_node(
+        &self,
+        node: Node,
+    ) -> Result<Node> {
+        if let Node::Document(_) = node {
+            return Err(self.cannot_adopt_document());
+        }
+
+        if !matches!(node, Node::DocumentFragment(_)) {
+            ChildNode::from(&node).remove()?;
+        }
+
+        self.adopt(&node)?;
+
+        Ok(node)
+    }
+
+    fn cannot_adopt_document(&self) -> Error {
+        DomException::new(
+            DomExceptionName::NotSupportedError,
+            "A Document cannot be adopted or imported",
+        )
+        .into_error(self.env)
+    }
+
+    /// Makes this document the node document of `node`, a root, and of the
+    /// template contents in it, which also take on its quirks mode.
+    fn adopt(
+        &self,
+        node: &Node,
+    ) -> Result<()> {
+        let document = self.cyclic_reference.get()?;
+        node.set_node_document(Some(&document))?;
+
+        if let Node::DocumentFragment(fragment) = node {
+            fragment.clone(self.env)?.quirks_mode = self.quirks_mode;
+        }
+
+        let elements = match node {
+            Node::Element(element) => vec![element.clone(self.env)?],
+            _ => vec![],
+        };
+        let elements = elements.into_iter().chain(node.deep_child_nodes_iter());
+
+        for element in elements {
+            if let Some(contents) = &element.template_contents {
+                let contents: Node = contents.clone(self.env)?.into();
+                self.adopt(&contents)?;
+                contents.weaken_node_document();
+            }
+        }
+
+        Ok(())
+    }
+}
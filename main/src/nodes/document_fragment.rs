@@ -1,3 +1,8 @@
+use napi::{
+    bindgen_prelude::Reference,
+    Result,
+};
+
 use crate::QuirksMode;
 
 #[create_node(has_children)]
@@ -11,4 +16,20 @@ impl DocumentFragment {
     pub fn get_text_content(&self) -> Option<String> {
         None
     }
+
+    #[napi]
+    pub fn clone_node(
+        &self,
+        deep: Option<bool>,
+    ) -> Result<Reference<Self>> {
+        let clone = Self::new_reference(self.env, self.quirks_mode)?;
+        let clone = self.as_node().with_node_document(clone)?;
+
+        if deep.unwrap_or(false) {
+            self.as_node()
+                .clone_children_into(&clone.clone(self.env)?.into())?;
+        }
+
+        Ok(clone)
+    }
 }
@@ -27,11 +27,13 @@ impl DocumentType {
         &self,
         _deep: Option<bool>,
     ) -> Result<Reference<Self>> {
-        Self::new_reference(
+        let clone = Self::new_reference(
             self.env,
             self.name.clone(),
             self.public_id.clone(),
             self.system_id.clone(),
-        )
+        )?;
+
+        self.as_node().with_node_document(clone)
     }
 }
@@ -19,64 +19,235 @@ use crate::{
 
 #[napi]
 pub struct Attr {
+    // the name, and the value while the attribute is not on an element
     attribute: Attribute,
-    owner_element: WeakReference<Element>,
+    owner_element: Option<WeakReference<Element>>,
     env: Env,
 }
 
-#[napi]
 impl Attr {
-    fn get_prefix_and_name(&self) -> (Option<String>, String) {
-        let as_string: &str = &self.attribute.name.local;
+    pub(crate) fn new(
+        attribute: Attribute,
+        owner_element: Option<WeakReference<Element>>,
+        env: Env,
+    ) -> Self {
+        Self {
+            attribute,
+            owner_element,
+            env,
+        }
+    }
 
-        let mut split = as_string.split(':');
+    pub(crate) fn qual_name(&self) -> &QualName {
+        &self.attribute.name
+    }
 
-        match (split.next(), split.next()) {
-            (Some(prefix), Some(name)) => {
-                (Some(prefix.to_string()), name.to_string())
-            },
-            (Some(name), _) => (None, name.to_string()),
-            _ => panic!("Invalid attribute name"),
-        }
+    /// The element this attribute is on, unless it has been removed from it
+    /// since.
+    fn get_attached_element(&self) -> Result<Option<Reference<Element>>> {
+        let element = match &self.owner_element {
+            Some(element) => element.upgrade(self.env)?,
+            None => return Ok(None),
+        };
+
+        let name = self.qual_name();
+        let attached = element
+            .attributes_wrapper
+            .get_attribute_ns(&name.ns, &name.local)
+            .is_some();
+
+        Ok(attached.then_some(element))
     }
 
+    /// Puts this attribute, which is on no element, on `element`.
+    pub(crate) fn attach(
+        &mut self,
+        element: WeakReference<Element>,
+    ) {
+        self.owner_element = Some(element);
+    }
+
+    /// Takes this attribute off its element, keeping `value`, the one it had
+    /// there.
+    fn detach(
+        &mut self,
+        value: StrTendril,
+    ) {
+        self.attribute.value = value;
+        self.owner_element = None;
+    }
+
+    pub(crate) fn to_attribute(&self) -> Result<Attribute> {
+        Ok(Attribute {
+            name: self.attribute.name.clone(),
+            value: self.get_value()?.into(),
+        })
+    }
+}
+
+#[napi]
+impl Attr {
     #[napi(getter)]
     pub fn get_local_name(&self) -> String {
         self.attribute.name.local.to_string()
     }
 
+    /// The qualified name, with the prefix if there is one.
     #[napi(getter)]
     pub fn get_name(&self) -> String {
-        self.get_prefix_and_name().1
+        get_qualified_name(&self.attribute.name)
     }
 
-    #[napi(getter)]
-    pub fn get_namespace_uri(&self) -> String {
-        self.attribute.name.ns.to_string()
+    #[napi(getter, js_name = "namespaceURI")]
+    pub fn get_namespace_uri(&self) -> Option<String> {
+        let namespace = &self.attribute.name.ns;
+
+        (!namespace.is_empty()).then(|| namespace.to_string())
     }
 
     #[napi(getter)]
-    pub fn get_owner_document(
-        &self
-    ) -> Result<Option<WeakReference<Document>>> {
-        let element = self.get_owner_element().upgrade(self.env)?;
-
-        element.get_owner_document()
+    pub fn get_owner_document(&self) -> Result<Option<Reference<Document>>> {
+        match &self.owner_element {
+            Some(element) => element.upgrade(self.env)?.get_owner_document(),
+            None => Ok(None),
+        }
     }
 
     #[napi(getter)]
-    pub fn get_owner_element(&self) -> WeakReference<Element> {
-        self.owner_element.clone()
+    pub fn get_owner_element(&self) -> Result<Option<Reference<Element>>> {
+        self.get_attached_element()
     }
 
+    /// The prefix of an attribute created in a namespace. Attributes parsed
+    /// from HTML have none, but a colon in their local name still reads as
+    /// one, as it always has.
     #[napi(getter)]
     pub fn get_prefix(&self) -> Option<String> {
-        self.get_prefix_and_name().0
+        let name = &self.attribute.name;
+
+        match &name.prefix {
+            Some(prefix) => Some(prefix.to_string()),
+            None => name
+                .local
+                .split_once(':')
+                .map(|(prefix, _)| prefix.to_string()),
+        }
     }
 
     #[napi(getter)]
-    pub fn get_value(&self) -> String {
-        self.attribute.value.to_string()
+    pub fn get_value(&self) -> Result<String> {
+        let element = match self.get_attached_element()? {
+            Some(element) => element,
+            None => return Ok(self.attribute.value.to_string()),
+        };
+
+        let name = self.qual_name();
+
+        Ok(element
+            .attributes_wrapper
+            .get_attribute_ns(&name.ns, &name.local)
+            .map(|attribute| attribute.value.to_string())
+            .unwrap_or_default())
+    }
+
+    #[napi(setter)]
+    pub fn set_value(
+        &mut self,
+        value: String,
+    ) -> Result<()> {
+        self.attribute.value = value.into();
+
+        match self.get_attached_element()? {
+            Some(mut element) => {
+                element.set_attribute_node(self.attribute.clone())
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+/// The `Attr`s of the attributes of an element, created on first access so
+/// that an attribute is the same object every time.
+pub(crate) struct LazyAttrs {
+    attrs: Vec<Reference<Attr>>,
+    env: Env,
+}
+
+impl LazyAttrs {
+    pub(crate) fn new(env: Env) -> Self {
+        Self {
+            attrs: vec![],
+            env,
+        }
+    }
+
+    fn position(
+        &self,
+        name: &QualName,
+    ) -> Option<usize> {
+        self.attrs.iter().position(|attr| {
+            let attr_name = attr.qual_name();
+
+            attr_name.ns == name.ns && attr_name.local == name.local
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    pub(crate) fn get_or_init(
+        &mut self,
+        name: &QualName,
+        init: impl FnOnce() -> Result<Reference<Attr>>,
+    ) -> Result<Reference<Attr>> {
+        if let Some(index) = self.position(name) {
+            return self.attrs[index].clone(self.env);
+        }
+
+        let attr = init()?;
+        self.attrs.push(attr.clone(self.env)?);
+        Ok(attr)
+    }
+
+    /// Makes `attr` the `Attr` of its attribute, detaching the one it
+    /// replaces with `previous_value`.
+    pub(crate) fn replace(
+        &mut self,
+        attr: Reference<Attr>,
+        previous_value: Option<StrTendril>,
+    ) {
+        match self.position(attr.qual_name()) {
+            Some(index) => {
+                let mut previous =
+                    std::mem::replace(&mut self.attrs[index], attr);
+                previous.detach(previous_value.unwrap_or_default());
+            },
+            None => self.attrs.push(attr),
+        }
+    }
+
+    /// Detaches the `Attr`s of the attributes in `previous` that are no
+    /// longer in `current`, with the value they had.
+    pub(crate) fn detach_removed(
+        &mut self,
+        previous: &AttributesWrapper,
+        current: &AttributesWrapper,
+    ) {
+        self.attrs.retain_mut(|attr| {
+            let name = attr.qual_name();
+            if current.get_attribute_ns(&name.ns, &name.local).is_some() {
+                return true;
+            }
+
+            let value = previous
+                .get_attribute_ns(&name.ns, &name.local)
+                .map(|attribute| attribute.value.clone())
+                .unwrap_or_default();
+            attr.detach(value);
+
+            false
+        })
     }
 }
 
@@ -94,25 +265,49 @@ impl From<Vec<Attribute>> for AttributesWrapper {
 }
 
 impl AttributesWrapper {
-    pub(crate) fn get_attributes(
+    pub(crate) fn get_attribute(
+        &self,
+        name: LocalName,
+    ) -> Option<&Attribute> {
+        self.iter().find(|attribute| attribute.name.local == name)
+    }
+
+    /// The first attribute whose qualified name is `qualified_name`.
+    pub(crate) fn get_attribute_by_qualified_name(
+        &self,
+        qualified_name: &str,
+    ) -> Option<&Attribute> {
+        self.iter().find(|attribute| {
+            get_qualified_name(&attribute.name) == qualified_name
+        })
+    }
+
+    pub(crate) fn get_attribute_ns(
         &self,
-        r: Reference<Element>,
-    ) -> Vec<Attr> {
-        self.attrs
-            .iter()
-            .map(|attribute| Attr {
-                attribute: attribute.clone(),
-                owner_element: r.downgrade().into(),
-                env: r.env,
-            })
+        namespace: &Namespace,
+        local_name: &LocalName,
+    ) -> Option<&Attribute> {
+        self.iter().find(|attribute| {
+            attribute.name.ns == *namespace
+                && attribute.name.local == *local_name
+        })
+    }
+
+    pub(crate) fn get_qualified_names(&self) -> Vec<String> {
+        self.iter()
+            .map(|attribute| get_qualified_name(&attribute.name))
             .collect()
     }
 
-    pub(crate) fn get_attribute(
+    pub(crate) fn get(
         &self,
-        name: LocalName,
+        index: usize,
     ) -> Option<&Attribute> {
-        self.iter().find(|attribute| attribute.name.local == name)
+        self.attrs.get(index)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.attrs.len()
     }
 
     pub(crate) fn has_attribute(
@@ -142,13 +337,49 @@ impl AttributesWrapper {
         self.push(new_attribute);
     }
 
+    /// Changes the value of the attribute `name` where it is, or adds it
+    /// after the others.
     pub(crate) fn set_attribute(
         &mut self,
         name: LocalName,
         value: StrTendril,
     ) {
-        self.remove_attribute(name.clone());
-        self.add_attribute(name, value);
+        match self
+            .attrs
+            .iter_mut()
+            .find(|attribute| attribute.name.local == name)
+        {
+            Some(attribute) => attribute.value = value,
+            None => self.add_attribute(name, value),
+        }
+    }
+
+    /// Replaces the attribute with the same namespace and local name as
+    /// `attribute` where it is, or adds it after the others.
+    pub(crate) fn set_attribute_ns(
+        &mut self,
+        attribute: Attribute,
+    ) {
+        let existing = self.attrs.iter_mut().find(|existing| {
+            existing.name.ns == attribute.name.ns
+                && existing.name.local == attribute.name.local
+        });
+
+        match existing {
+            Some(existing) => *existing = attribute,
+            None => self.push(attribute),
+        }
+    }
+
+    pub(crate) fn remove_attribute_ns(
+        &mut self,
+        namespace: &Namespace,
+        local_name: &LocalName,
+    ) {
+        self.attrs.retain(|attribute| {
+            attribute.name.ns != *namespace
+                || attribute.name.local != *local_name
+        })
     }
 
     pub(crate) fn push(
@@ -158,7 +389,16 @@ impl AttributesWrapper {
         self.attrs.push(attribute)
     }
 
-    pub(crate) fn iter(&self) -> std::slice::Iter<Attribute> {
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, Attribute> {
         self.attrs.iter()
     }
 }
+
+/// The name of an attribute with its prefix, if any, see
+/// https://dom.spec.whatwg.org/#concept-attribute-qualified-name
+fn get_qualified_name(name: &QualName) -> String {
+    match &name.prefix {
+        Some(prefix) => format!("{}:{}", prefix, name.local),
+        None => name.local.to_string(),
+    }
+}
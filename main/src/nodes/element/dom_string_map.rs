@@ -0,0 +1,251 @@
+use css_case::{
+    to_camel_case,
+    to_kebab_case,
+};
+use napi::{
+    CallContext,
+    Env,
+    Error,
+    JsObject,
+    JsString,
+    JsUnknown,
+    Result,
+    Status,
+    ValueType,
+};
+
+use crate::{
+    proxy::{
+        ProxyHandler,
+        ProxyHandlerCell,
+    },
+    DomException,
+    DomExceptionName,
+    Element,
+    WeakReference,
+};
+
+thread_local! {
+    static HANDLER: ProxyHandlerCell = const { ProxyHandlerCell::new() };
+}
+
+static PROXY_HANDLER: ProxyHandler = ProxyHandler::new(
+    &HANDLER,
+    &[
+        ("get", get),
+        ("set", set),
+        ("has", has),
+        ("deleteProperty", delete_property),
+        ("ownKeys", own_keys),
+        ("getOwnPropertyDescriptor", get_own_property_descriptor),
+    ],
+);
+
+/// The `data-*` attributes of an element by their camelCase names, as
+/// returned by `dataset`, see
+/// https://html.spec.whatwg.org/#domstringmap
+#[napi(js_name = "DOMStringMap")]
+pub struct DomStringMap {
+    element: WeakReference<Element>,
+    env: Env,
+}
+
+impl DomStringMap {
+    /// Creates the map, wrapped in a `Proxy` for its named properties.
+    pub(crate) fn create(
+        env: Env,
+        element: WeakReference<Element>,
+    ) -> Result<JsObject> {
+        let map = Self {
+            element,
+            env,
+        }
+        .into_instance(env)?;
+
+        PROXY_HANDLER.wrap(env, map.as_object(env))
+    }
+
+    /// The camelCase names of the `data-*` attributes, in their order.
+    fn names(&self) -> Result<Vec<String>> {
+        let element = self.element.upgrade(self.env)?;
+
+        Ok(element
+            .get_attribute_names()
+            .into_iter()
+            .filter_map(|name| {
+                name.strip_prefix("data-")
+                    .filter(|name| {
+                        !name.contains(|c: char| c.is_ascii_uppercase())
+                    })
+                    .map(to_camel_case)
+            })
+            .collect())
+    }
+
+    fn get(
+        &self,
+        name: &str,
+    ) -> Result<Option<String>> {
+        if !self.names()?.iter().any(|n| n == name) {
+            return Ok(None);
+        }
+
+        let element = self.element.upgrade(self.env)?;
+
+        Ok(element.get_attribute(attribute_name(name)))
+    }
+
+    fn set(
+        &self,
+        name: &str,
+        value: String,
+    ) -> Result<()> {
+        let mut chars = name.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '-' && chars.peek().is_some_and(char::is_ascii_lowercase) {
+                return Err(DomException::new(
+                    DomExceptionName::SyntaxError,
+                    format!("'{}' is not a valid dataset name", name),
+                )
+                .into_error(self.env));
+            }
+        }
+
+        let mut element = self.element.upgrade(self.env)?;
+        element.set_attribute(attribute_name(name), value)
+    }
+
+    fn delete(
+        &self,
+        name: &str,
+    ) -> Result<()> {
+        if !self.names()?.iter().any(|n| n == name) {
+            return Ok(());
+        }
+
+        let mut element = self.element.upgrade(self.env)?;
+        element.remove_attribute(attribute_name(name))
+    }
+}
+
+#[napi]
+impl DomStringMap {
+    /// Maps are only created by the tree, like in browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+}
+
+/// The attribute that the camelCase `name` stands for.
+fn attribute_name(name: &str) -> String {
+    format!("data-{}", to_kebab_case(name))
+}
+
+/// The name `key` stands for, if it is a string rather than a symbol.
+fn as_name(key: &JsUnknown) -> Result<Option<String>> {
+    if key.get_type()? != ValueType::String {
+        return Ok(None);
+    }
+
+    let key = unsafe { key.cast::<JsString>() }.into_utf8()?;
+
+    key.into_owned().map(Some)
+}
+
+#[js_function(2)]
+fn get(ctx: CallContext) -> Result<JsUnknown> {
+    let map: &DomStringMap = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    if let Some(name) = as_name(&key)? {
+        if let Some(value) = map.get(&name)? {
+            return Ok(ctx.env.create_string(&value)?.into_unknown());
+        }
+    }
+
+    let target: JsObject = ctx.get(0)?;
+    target.get_property(key)
+}
+
+#[js_function(3)]
+fn set(ctx: CallContext) -> Result<bool> {
+    let map: &DomStringMap = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+    let value: JsUnknown = ctx.get(2)?;
+
+    match as_name(&key)? {
+        Some(name) => {
+            let value = value.coerce_to_string()?.into_utf8()?.into_owned()?;
+            map.set(&name, value)?;
+        },
+        None => {
+            let mut target: JsObject = ctx.get(0)?;
+            target.set_property(key, value)?;
+        },
+    }
+
+    Ok(true)
+}
+
+#[js_function(2)]
+fn has(ctx: CallContext) -> Result<bool> {
+    let map: &DomStringMap = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    if let Some(name) = as_name(&key)? {
+        if map.names()?.contains(&name) {
+            return Ok(true);
+        }
+    }
+
+    let target: JsObject = ctx.get(0)?;
+    target.has_property_js(key)
+}
+
+#[js_function(2)]
+fn delete_property(ctx: CallContext) -> Result<bool> {
+    let map: &DomStringMap = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    match as_name(&key)? {
+        Some(name) => map.delete(&name)?,
+        None => {
+            let mut target: JsObject = ctx.get(0)?;
+            target.delete_property(key)?;
+        },
+    }
+
+    Ok(true)
+}
+
+#[js_function(1)]
+fn own_keys(ctx: CallContext) -> Result<Vec<String>> {
+    let map: &DomStringMap = ctx.get(0)?;
+
+    map.names()
+}
+
+#[js_function(2)]
+fn get_own_property_descriptor(ctx: CallContext) -> Result<JsUnknown> {
+    let map: &DomStringMap = ctx.get(0)?;
+    let key: JsUnknown = ctx.get(1)?;
+
+    let value = match as_name(&key)? {
+        Some(name) => map.get(&name)?,
+        None => None,
+    };
+
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(ctx.env.get_undefined()?.into_unknown()),
+    };
+
+    let mut descriptor = ctx.env.create_object()?;
+    descriptor.set_named_property("value", value)?;
+    descriptor.set_named_property("writable", true)?;
+    descriptor.set_named_property("enumerable", true)?;
+    descriptor.set_named_property("configurable", true)?;
+
+    Ok(descriptor.into_unknown())
+}
@@ -0,0 +1,423 @@
+use html5ever::LocalName;
+use indexmap::IndexSet;
+use itertools::join;
+use napi::{
+    bindgen_prelude::Reference,
+    Env,
+    Error,
+    Result,
+    Status,
+};
+
+use crate::{
+    CyclicReference,
+    DomException,
+    DomExceptionName,
+    Element,
+    WeakReference,
+    WithDataInBrackets,
+};
+
+/// The link types `a`, `area` and `form` elements support, see
+/// https://html.spec.whatwg.org/#linkTypes
+pub(crate) const HYPERLINK_REL_TOKENS: &[&str] =
+    &["noopener", "noreferrer", "opener"];
+
+/// The link types `link` elements support.
+pub(crate) const LINK_REL_TOKENS: &[&str] = &[
+    "alternate",
+    "dns-prefetch",
+    "expect",
+    "icon",
+    "manifest",
+    "modulepreload",
+    "next",
+    "pingback",
+    "preconnect",
+    "prefetch",
+    "preload",
+    "search",
+    "stylesheet",
+];
+
+/// The keywords of the `sandbox` attribute of `iframe` elements, see
+/// https://html.spec.whatwg.org/#attr-iframe-sandbox
+pub(crate) const SANDBOX_TOKENS: &[&str] = &[
+    "allow-downloads",
+    "allow-forms",
+    "allow-modals",
+    "allow-orientation-lock",
+    "allow-pointer-lock",
+    "allow-popups",
+    "allow-popups-to-escape-sandbox",
+    "allow-presentation",
+    "allow-same-origin",
+    "allow-scripts",
+    "allow-top-navigation",
+    "allow-top-navigation-by-user-activation",
+    "allow-top-navigation-to-custom-protocols",
+];
+
+/// The tokens of an attribute of an element, like `class` for `classList`
+/// and `rel` for `relList`, see
+/// https://dom.spec.whatwg.org/#interface-domtokenlist
+#[napi(js_name = "DOMTokenList")]
+pub struct DomTokenList {
+    data: IndexSet<String>,
+    owner: WeakReference<Element>,
+    attribute: LocalName,
+    // the tokens `supports()` accepts, if the attribute defines any
+    supported_tokens: Option<&'static [&'static str]>,
+    env: Env,
+    cyclic_reference: CyclicReference<Self>,
+}
+
+impl WithDataInBrackets for DomTokenList {
+    #[inline]
+    fn raw_item(
+        &self,
+        index: usize,
+    ) -> Option<String> {
+        self.data.get_index(index).cloned()
+    }
+
+    #[inline]
+    fn get_reference(&self) -> Result<Reference<Self>> {
+        self.cyclic_reference.get()
+    }
+
+    #[inline]
+    fn get_env(&self) -> Env {
+        self.env
+    }
+}
+
+impl DomTokenList {
+    pub(crate) fn create(
+        owner: WeakReference<Element>,
+        env: Env,
+        attribute: LocalName,
+        supported_tokens: Option<&'static [&'static str]>,
+        initial_value: Option<String>,
+    ) -> Result<Reference<Self>> {
+        let r = CyclicReference::<Self>::new_cyclic(env, |cyclic_reference| {
+            Self::into_reference(
+                Self {
+                    owner,
+                    attribute,
+                    supported_tokens,
+                    env,
+                    data: initial_value.map(|s| as_set(&s)).unwrap_or_default(),
+                    cyclic_reference,
+                },
+                env,
+            )
+        })?;
+
+        r.clone(env)?.set_properties()?;
+        r.clone(env)
+    }
+
+    fn sync(
+        &self,
+        value: &str,
+    ) -> Result<()> {
+        self.set_properties()?;
+
+        let mut owner = self.owner.upgrade(self.env)?;
+        owner.update_attributes(|attributes| {
+            attributes.set_attribute(self.attribute.clone(), value.into())
+        })
+    }
+
+    /// Writes the tokens to the attribute after a change, see
+    /// https://dom.spec.whatwg.org/#concept-dtl-update
+    fn update(&self) -> Result<()> {
+        let has_attribute = self
+            .owner
+            .upgrade(self.env)?
+            .attributes_wrapper
+            .has_attribute(self.attribute.clone());
+
+        if !has_attribute && self.data.is_empty() {
+            return self.set_properties();
+        }
+
+        self.sync(&self.as_string())
+    }
+
+    pub(crate) fn clear(&mut self) -> Result<()> {
+        self.data.clear();
+        self.set_properties()
+    }
+
+    fn as_string(&self) -> String {
+        join(self.data.iter(), " ")
+    }
+}
+
+#[napi]
+impl DomTokenList {
+    /// Lists are only created by their element, like in browsers.
+    #[napi(constructor)]
+    pub fn new() -> Result<Self> {
+        Err(Error::new(Status::GenericFailure, "Illegal constructor"))
+    }
+
+    #[napi]
+    pub fn item(
+        &self,
+        index: i64,
+    ) -> Option<String> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.raw_item(index))
+    }
+
+    #[napi]
+    pub fn add(
+        &mut self,
+        token1: Option<String>,
+        token2: Option<String>,
+        token3: Option<String>,
+        token4: Option<String>,
+        token5: Option<String>,
+    ) -> Result<()> {
+        let tokens = [token1, token2, token3, token4, token5]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        for token in &tokens {
+            validate_token(self.env, token)?;
+        }
+
+        self.data.extend(tokens);
+
+        self.update()
+    }
+
+    #[napi]
+    pub fn remove(
+        &mut self,
+        token1: Option<String>,
+        token2: Option<String>,
+        token3: Option<String>,
+        token4: Option<String>,
+        token5: Option<String>,
+    ) -> Result<()> {
+        let tokens = [token1, token2, token3, token4, token5]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        for token in &tokens {
+            validate_token(self.env, token)?;
+        }
+
+        for token in &tokens {
+            self.data.shift_remove(token);
+        }
+
+        self.update()
+    }
+
+    /// Adds `token` if it is missing and removes it otherwise, unless
+    /// `force` says which to do. Returns whether it is there after.
+    #[napi]
+    pub fn toggle(
+        &mut self,
+        token: String,
+        force: Option<bool>,
+    ) -> Result<bool> {
+        validate_token(self.env, &token)?;
+
+        match (self.data.contains(&token), force) {
+            (false, None | Some(true)) => {
+                self.data.insert(token);
+                self.update()?;
+                Ok(true)
+            },
+            (true, None | Some(false)) => {
+                self.data.shift_remove(&token);
+                self.update()?;
+                Ok(false)
+            },
+            (contains, _) => Ok(contains),
+        }
+    }
+
+    /// Puts `new_token` where `token` is, unless `token` is missing. Returns
+    /// whether it was replaced.
+    #[napi]
+    pub fn replace(
+        &mut self,
+        token: String,
+        new_token: String,
+    ) -> Result<bool> {
+        validate_token(self.env, &token)?;
+        validate_token(self.env, &new_token)?;
+
+        if !self.data.contains(&token) {
+            return Ok(false);
+        }
+
+        // where both are there, the first one of them is replaced and the
+        // other one is dropped
+        self.data = self
+            .data
+            .drain(..)
+            .map(|t| match t == token {
+                true => new_token.clone(),
+                false => t,
+            })
+            .collect();
+
+        self.update()?;
+
+        Ok(true)
+    }
+
+    #[napi]
+    pub fn contains(
+        &self,
+        token: String,
+    ) -> bool {
+        self.data.contains(&token)
+    }
+
+    #[napi(getter)]
+    pub fn get_length(&self) -> u32 {
+        self.data.len().try_into().unwrap()
+    }
+
+    #[napi(getter)]
+    pub fn get_value(&self) -> Result<String> {
+        self.owner.upgrade(self.env).map(|owner| {
+            owner
+                .attributes_wrapper
+                .get_attribute(self.attribute.clone())
+                .map(|attribute| attribute.value.to_string())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether `token` is one of the tokens the attribute supports. Throws a
+    /// `TypeError` for attributes without supported tokens, like `class`.
+    #[napi]
+    pub fn supports(
+        &self,
+        token: String,
+    ) -> Result<bool> {
+        let supported_tokens = match self.supported_tokens {
+            Some(supported_tokens) => supported_tokens,
+            None => {
+                self.env.throw_type_error(
+                    &format!(
+                        "The '{}' attribute has no supported tokens",
+                        self.attribute
+                    ),
+                    None,
+                )?;
+
+                return Err(Error::from_status(Status::PendingException));
+            },
+        };
+
+        Ok(supported_tokens
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(&token)))
+    }
+
+    #[napi]
+    pub fn to_string(&self) -> Result<String> {
+        self.get_value()
+    }
+
+    #[napi(setter)]
+    pub fn set_value(
+        &mut self,
+        value: String,
+    ) -> Result<()> {
+        if self.get_value()? == value {
+            return Ok(());
+        }
+
+        self.data = as_set(&value);
+        self.sync(&value)
+    }
+}
+
+fn as_set(value: &str) -> IndexSet<String> {
+    value
+        .split_ascii_whitespace()
+        .map(|token| token.to_string())
+        .collect()
+}
+
+/// Throws a `SyntaxError` for an empty token and an `InvalidCharacterError`
+/// for one with whitespace, see
+/// https://dom.spec.whatwg.org/#interface-domtokenlist
+fn validate_token(
+    env: Env,
+    token: &str,
+) -> Result<()> {
+    if token.is_empty() {
+        return Err(DomException::new(
+            DomExceptionName::SyntaxError,
+            "The token must not be empty",
+        )
+        .into_error(env));
+    }
+
+    if token.contains(|c: char| c.is_ascii_whitespace()) {
+        return Err(DomException::new(
+            DomExceptionName::InvalidCharacterError,
+            format!("The token '{}' must not contain whitespace", token),
+        )
+        .into_error(env));
+    }
+
+    Ok(())
+}
+
+/// The token lists of an element, each created on its first access, like
+/// `LazyReference`.
+pub(crate) struct LazyTokenLists {
+    lists: Vec<Reference<DomTokenList>>,
+    env: Env,
+}
+
+impl LazyTokenLists {
+    pub(crate) fn new(env: Env) -> Self {
+        Self {
+            lists: vec![],
+            env,
+        }
+    }
+
+    /// The list of `attribute`, if it has been created.
+    pub(crate) fn get_mut(
+        &mut self,
+        attribute: &LocalName,
+    ) -> Option<&mut Reference<DomTokenList>> {
+        self.lists
+            .iter_mut()
+            .find(|list| list.attribute == *attribute)
+    }
+
+    pub(crate) fn get_or_init(
+        &mut self,
+        attribute: &LocalName,
+        init: impl FnOnce() -> Result<Reference<DomTokenList>>,
+    ) -> Result<Reference<DomTokenList>> {
+        let env = self.env;
+        if let Some(list) = self.get_mut(attribute) {
+            return list.clone(env);
+        }
+
+        let list = init()?;
+        self.lists.push(list.clone(self.env)?);
+        Ok(list)
+    }
+}
@@ -37,13 +37,38 @@ use crate::{
 
 pub struct ElementRef {
     inner: Reference<Element>,
+    // whether the node document is an HTML document, shared by the elements
+    // reached from this one, see `in_html_document`
+    is_html_document: bool,
+}
+
+impl ElementRef {
+    /// Sets whether the node document is an HTML document, which is worked
+    /// out once for all the elements a selector is matched against, rather
+    /// than by walking up to the root from each of them.
+    pub(crate) fn in_html_document(
+        mut self,
+        is_html_document: bool,
+    ) -> Self {
+        self.is_html_document = is_html_document;
+        self
+    }
+
+    /// An element in the same document as this one.
+    fn related(
+        &self,
+        inner: Reference<Element>,
+    ) -> Self {
+        ElementRef {
+            inner,
+            is_html_document: self.is_html_document,
+        }
+    }
 }
 
 impl Clone for ElementRef {
     fn clone(&self) -> Self {
-        Self {
-            inner: self.inner.clone(self.inner.env).unwrap(),
-        }
+        self.related(self.inner.clone(self.inner.env).unwrap())
     }
 }
 
@@ -74,6 +99,7 @@ impl From<Reference<Element>> for ElementRef {
     fn from(r: Reference<Element>) -> Self {
         ElementRef {
             inner: r,
+            is_html_document: true,
         }
     }
 }
@@ -108,7 +134,7 @@ impl selectors::Element for ElementRef {
     fn parent_element(&self) -> Option<Self> {
         self.get_parent_element()
             .unwrap()
-            .map(|r| r.upgrade(self.inner.env).unwrap().into())
+            .map(|r| self.related(r.upgrade(self.inner.env).unwrap()))
     }
 
     fn parent_node_is_shadow_root(&self) -> bool {
@@ -126,15 +152,17 @@ impl selectors::Element for ElementRef {
     fn prev_sibling_element(&self) -> Option<Self> {
         self.get_previous_element_sibling()
             .unwrap()
-            .map(|r| r.into())
+            .map(|r| self.related(r))
     }
 
     fn next_sibling_element(&self) -> Option<Self> {
-        self.get_next_element_sibling().unwrap().map(|r| r.into())
+        self.get_next_element_sibling()
+            .unwrap()
+            .map(|r| self.related(r))
     }
 
     fn is_html_element_in_html_document(&self) -> bool {
-        self.name.ns == ns!(html)
+        self.name.ns == ns!(html) && self.is_html_document
     }
 
     fn has_local_name(
@@ -166,22 +194,29 @@ impl selectors::Element for ElementRef {
             &<Self::Impl as SelectorImpl>::AttrValue,
         >,
     ) -> bool {
-        match ns {
-            NamespaceConstraint::Any => {
-                self.attributes_wrapper.iter().any(|attr| {
-                    attr.name.local == local_name.to_string()
-                        && operation.eval_str(&attr.value)
-                })
-            },
-
-            NamespaceConstraint::Specific(namespace_url) => {
-                self.attributes_wrapper.iter().any(|attr| {
-                    attr.name.ns == namespace_url.to_string()
-                        && attr.name.local == local_name.to_string()
-                        && operation.eval_str(&attr.value)
-                })
-            },
-        }
+        // `local_name` is already lowercased for HTML elements in HTML
+        // documents, whose attribute names are matched ASCII
+        // case-insensitively. Values are compared as `operation` says, which
+        // takes the `i` and `s` flags and the attributes HTML lists as
+        // case-insensitive into account.
+        let is_html = self.is_html_element_in_html_document();
+
+        self.attributes_wrapper.iter().any(|attr| {
+            let name_matches = if is_html {
+                attr.name.local.as_ref().eq_ignore_ascii_case(local_name)
+            } else {
+                *attr.name.local == **local_name
+            };
+
+            let namespace_matches = match ns {
+                NamespaceConstraint::Any => true,
+                NamespaceConstraint::Specific(namespace_url) => {
+                    *attr.name.ns == ***namespace_url
+                },
+            };
+
+            name_matches && namespace_matches && operation.eval_str(&attr.value)
+        })
     }
 
     fn match_non_ts_pseudo_class<F>(
@@ -270,6 +305,6 @@ impl selectors::Element for ElementRef {
     fn is_root(&self) -> bool {
         self.get_parent_node()
             .unwrap()
-            .map_or(false, |parent| matches!(parent, ParentNode::Document(_)))
+            .is_some_and(|parent| matches!(parent, ParentNode::Document(_)))
     }
 }
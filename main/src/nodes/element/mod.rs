@@ -1,30 +1,55 @@
 mod attributes;
-mod class_list;
+mod dom_string_map;
+mod dom_token_list;
 mod element_ref;
 
+pub use attributes::Attr;
 use attributes::{
-    Attr,
     AttributesWrapper,
+    LazyAttrs,
+};
+use dom_string_map::DomStringMap;
+pub use dom_token_list::DomTokenList;
+use dom_token_list::{
+    LazyTokenLists,
+    HYPERLINK_REL_TOKENS,
+    LINK_REL_TOKENS,
+    SANDBOX_TOKENS,
 };
-use class_list::ClassList;
 pub use element_ref::ElementRef;
 use html5ever::{
+    expanded_name,
+    local_name,
+    namespace_url,
+    ns,
+    Attribute,
     LocalName,
     QualName,
 };
 use napi::{
     bindgen_prelude::Reference,
+    Either,
+    Env,
+    JsObject,
     Result,
 };
+use url::Url;
 
 use crate::{
+    about_blank,
+    html_elements,
+    names,
     serialize,
     ChildNode,
+    CompiledSelector,
     DocumentFragment,
     Html5everDom,
+    IndexedAttributes,
     InsertPosition,
     LazyReference,
+    NamedNodeMap,
     Node,
+    NodeData,
     StyleDeclaration,
     Text,
 };
@@ -32,82 +57,239 @@ use crate::{
 #[create_node(has_children, is_child)]
 pub struct Element {
     pub(crate) attributes_wrapper: AttributesWrapper,
+    pub(crate) lazy_attrs: LazyAttrs,
 
     pub(crate) name: QualName,
 
-    pub(crate) lazy_class_list: LazyReference<ClassList>,
+    pub(crate) lazy_token_lists: LazyTokenLists,
     pub(crate) lazy_style: LazyReference<StyleDeclaration>,
     pub(crate) template_contents: Option<Reference<DocumentFragment>>,
 }
 
 #[napi]
 impl Element {
-    pub(crate) fn get_all_child_nodes(&self) -> Vec<ChildNode> {
-        if let Some(template) = self.template_contents.as_ref() {
-            let mut child_nodes = template.get_child_nodes();
-            child_nodes.extend(self.get_child_nodes());
-            child_nodes
-        } else {
-            self.get_child_nodes()
+    /// Creates an element with the interface of its name, like
+    /// `HTMLInputElement` for `input`.
+    pub(crate) fn create(
+        env: Env,
+        attributes: impl Into<AttributesWrapper>,
+        name: QualName,
+        template_contents: Option<Reference<DocumentFragment>>,
+    ) -> Result<Reference<Element>> {
+        if let Some(contents) = &template_contents {
+            contents.as_node().weaken_node_document();
         }
+
+        let element = Self::new_reference(
+            env,
+            attributes.into(),
+            LazyAttrs::new(env),
+            name,
+            LazyTokenLists::new(env),
+            LazyReference::new(env),
+            template_contents,
+        )?;
+
+        html_elements::set_interface(env, &element)?;
+
+        Ok(element)
     }
 
-    #[napi(getter)]
-    pub fn get_attributes(
+    /// Parses `url` relative to the base URL of the node document, see
+    /// https://html.spec.whatwg.org/#encoding-parsing-a-url
+    pub fn resolve_url(
         &self,
-        r: Reference<Element>,
-    ) -> Vec<Attr> {
-        self.attributes_wrapper.get_attributes(r)
+        url: &str,
+    ) -> Result<Option<Url>> {
+        match self.as_node().get_node_document()? {
+            Some(document) => document.resolve_url(url),
+            None => Ok(about_blank().join(url).ok()),
+        }
     }
 
-    #[napi]
-    pub fn get_attribute(
+    /// The value of the attribute `name` resolved as a URL, like the links
+    /// a crawler follows. `None` without the attribute or a valid URL.
+    pub fn resolve_attribute_url(
         &self,
-        name: String,
-    ) -> Option<String> {
-        self.attributes_wrapper
-            .get_attribute(LocalName::from(name.to_lowercase()))
-            .map(|attribute| attribute.value.to_string())
+        name: &str,
+    ) -> Result<Option<Url>> {
+        match self.get_attribute(name.to_string()) {
+            Some(value) => self.resolve_url(&value),
+            None => Ok(None),
+        }
     }
 
-    #[napi]
-    pub fn remove_attribute(
+    /// Mutates the attributes and keeps the element index of the document
+    /// this element is connected to up to date.
+    pub(crate) fn update_attributes<F>(
         &mut self,
-        name: String,
-    ) -> Result<()> {
-        if name == *"class" {
-            if let Some(class_list) = &mut self.lazy_class_list.get_mut() {
-                class_list.clear()?;
-            }
+        f: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut AttributesWrapper),
+    {
+        let previous = IndexedAttributes::from(&*self);
+        let previous_attributes = (!self.lazy_attrs.is_empty())
+            .then(|| self.attributes_wrapper.clone());
+
+        f(&mut self.attributes_wrapper);
+        NodeData::tree_changed();
+
+        if let Some(previous_attributes) = previous_attributes {
+            self.lazy_attrs
+                .detach_removed(&previous_attributes, &self.attributes_wrapper);
         }
 
-        if name == *"style" {
-            if let Some(style) = &mut self.lazy_style.get_mut() {
-                style.clear()?;
-            }
+        if let Some(mut document) = self.as_node().get_connected_document()? {
+            let element = self.cyclic_reference.get()?;
+            document.element_index.update(&element, &previous);
         }
 
-        self.attributes_wrapper.remove_attribute(name.into());
-
         Ok(())
     }
 
-    #[napi]
-    pub fn set_attribute(
+    /// The children in the markup of this element, which for a template are
+    /// the ones of its content, see
+    /// https://html.spec.whatwg.org/#serialising-html-fragments
+    pub(crate) fn get_serialized_child_nodes(&self) -> Vec<ChildNode> {
+        match &self.template_contents {
+            Some(contents) => {
+                contents.as_node().shallow_child_nodes_iter().collect()
+            },
+            None => self.as_node().shallow_child_nodes_iter().collect(),
+        }
+    }
+
+    #[napi(getter)]
+    pub fn get_content(&self) -> Result<Option<Reference<DocumentFragment>>> {
+        self.template_contents
+            .as_ref()
+            .map(|contents| contents.clone(self.env))
+            .transpose()
+    }
+
+    /// The `Attr` of `attribute`, which is on this element, the same object
+    /// every time.
+    pub(crate) fn get_attr(
         &mut self,
+        attribute: &Attribute,
+    ) -> Result<Reference<Attr>> {
+        let element = self.cyclic_reference.get_weak();
+        let env = self.env;
+
+        self.lazy_attrs.get_or_init(&attribute.name, || {
+            let attr = Attr::new(attribute.clone(), Some(element), env);
+
+            Attr::into_reference(attr, env)
+        })
+    }
+
+    /// Sets `attribute`, replacing the one with the same namespace and local
+    /// name.
+    pub(crate) fn set_attribute_node(
+        &mut self,
+        attribute: Attribute,
+    ) -> Result<()> {
+        if attribute.name.ns == ns!() {
+            return self.set_attribute_local(
+                attribute.name.local,
+                attribute.value.to_string(),
+            );
+        }
+
+        self.update_attributes(|attributes| {
+            attributes.set_attribute_ns(attribute)
+        })
+    }
+
+    /// Removes the attribute `name`, like `set_attribute_node` sets it.
+    pub(crate) fn remove_attribute_node(
+        &mut self,
+        name: &QualName,
+    ) -> Result<()> {
+        if name.ns == ns!() {
+            return self.remove_attribute_local(name.local.clone());
+        }
+
+        self.update_attributes(|attributes| {
+            attributes.remove_attribute_ns(&name.ns, &name.local)
+        })
+    }
+
+    /// The local name, with the prefix if there is one.
+    pub(crate) fn get_qualified_name(&self) -> String {
+        match &self.name.prefix {
+            Some(prefix) => format!("{}:{}", prefix, self.name.local),
+            None => self.name.local.to_string(),
+        }
+    }
+
+    /// The qualified name, uppercased for HTML elements in HTML documents,
+    /// see https://dom.spec.whatwg.org/#element-html-uppercased-qualified-name
+    pub(crate) fn get_html_uppercased_qualified_name(&self) -> String {
+        let qualified_name = self.get_qualified_name();
+
+        match self.is_html_element_in_html_document() {
+            true => qualified_name.to_ascii_uppercase(),
+            false => qualified_name,
+        }
+    }
+
+    /// Whether this is an element in the HTML namespace whose node document
+    /// is an HTML document, rather than an XML one, see
+    /// https://dom.spec.whatwg.org/#html-document
+    pub(crate) fn is_html_element_in_html_document(&self) -> bool {
+        let is_html_document = self
+            .as_node()
+            .get_node_document()
+            .ok()
+            .flatten()
+            .is_none_or(|document| document.is_html());
+
+        self.name.ns == ns!(html) && is_html_document
+    }
+
+    /// The attribute name `name` is matched as. HTML elements in an HTML
+    /// document match names ASCII case-insensitively, see
+    /// https://dom.spec.whatwg.org/#concept-element-attributes-get-by-name
+    pub(crate) fn normalize_attribute_name(
+        &self,
         name: String,
+    ) -> String {
+        match self.is_html_element_in_html_document() {
+            true => name.to_ascii_lowercase(),
+            false => name,
+        }
+    }
+
+    /// Throws an `InvalidCharacterError` unless `name` can be the local name
+    /// of an attribute, see
+    /// https://dom.spec.whatwg.org/#valid-attribute-local-name
+    fn validate_attribute_name(
+        &self,
+        name: &str,
+    ) -> Result<()> {
+        match names::is_valid_attribute_local_name(name) {
+            true => Ok(()),
+            false => Err(names::invalid_character_error(self.env, name)),
+        }
+    }
+
+    /// Sets the attribute `name` in no namespace, keeping the token lists and
+    /// `style` in sync with it.
+    fn set_attribute_local(
+        &mut self,
+        name: LocalName,
         value: String,
     ) -> Result<()> {
-        if name == *"class" {
-            if let Some(class_list) = &mut self.lazy_class_list.get_mut() {
-                // attribute is set in ClassList::set_value
-                class_list.set_value(value)?;
+        if let Some(token_list) = self.lazy_token_lists.get_mut(&name) {
+            // attribute is set in DomTokenList::set_value
+            token_list.set_value(value)?;
 
-                return Ok(());
-            }
+            return Ok(());
         }
 
-        if name == *"style" {
+        if name == local_name!("style") {
             if let Some(style) = &mut self.lazy_style.get_mut() {
                 // attribute is set in StyleDeclaration::set_css_text
                 style.set_css_text(value)?;
@@ -116,10 +298,65 @@ impl Element {
             }
         }
 
+        self.update_attributes(|attributes| {
+            attributes.set_attribute(name, value.into())
+        })
+    }
+
+    /// Removes the attribute `name` in no namespace, like
+    /// `set_attribute_local` sets it.
+    fn remove_attribute_local(
+        &mut self,
+        name: LocalName,
+    ) -> Result<()> {
+        if let Some(token_list) = self.lazy_token_lists.get_mut(&name) {
+            token_list.clear()?;
+        }
+
+        if name == local_name!("style") {
+            if let Some(style) = &mut self.lazy_style.get_mut() {
+                style.clear()?;
+            }
+        }
+
+        self.update_attributes(|attributes| attributes.remove_attribute(name))
+    }
+
+    #[napi(getter, ts_return_type = "NamedNodeMap")]
+    pub fn get_attributes(&self) -> Result<JsObject> {
+        NamedNodeMap::create(self.env, self.cyclic_reference.get_weak())
+    }
+
+    #[napi]
+    pub fn get_attribute(
+        &self,
+        name: String,
+    ) -> Option<String> {
         self.attributes_wrapper
-            .set_attribute(LocalName::from(name), value.into());
+            .get_attribute(self.normalize_attribute_name(name).into())
+            .map(|attribute| attribute.value.to_string())
+    }
 
-        Ok(())
+    #[napi]
+    pub fn remove_attribute(
+        &mut self,
+        name: String,
+    ) -> Result<()> {
+        let name = self.normalize_attribute_name(name);
+
+        self.remove_attribute_local(name.into())
+    }
+
+    #[napi]
+    pub fn set_attribute(
+        &mut self,
+        name: String,
+        value: String,
+    ) -> Result<()> {
+        self.validate_attribute_name(&name)?;
+        let name = self.normalize_attribute_name(name);
+
+        self.set_attribute_local(name.into(), value)
     }
 
     #[napi]
@@ -127,18 +364,123 @@ impl Element {
         &self,
         name: String,
     ) -> bool {
-        self.attributes_wrapper.has_attribute(name.into())
+        self.attributes_wrapper
+            .has_attribute(self.normalize_attribute_name(name).into())
+    }
+
+    /// Adds the attribute `name` if it is missing and removes it otherwise,
+    /// unless `force` says which to do. Returns whether it is there after.
+    #[napi]
+    pub fn toggle_attribute(
+        &mut self,
+        name: String,
+        force: Option<bool>,
+    ) -> Result<bool> {
+        self.validate_attribute_name(&name)?;
+        let name = self.normalize_attribute_name(name);
+        let has_attribute = self.has_attribute(name.clone());
+
+        match (has_attribute, force) {
+            (false, None | Some(true)) => {
+                self.set_attribute_local(name.into(), String::new())?;
+                Ok(true)
+            },
+            (true, None | Some(false)) => {
+                self.remove_attribute_local(name.into())?;
+                Ok(false)
+            },
+            (has_attribute, _) => Ok(has_attribute),
+        }
+    }
+
+    #[napi]
+    pub fn get_attribute_names(&self) -> Vec<String> {
+        self.attributes_wrapper.get_qualified_names()
+    }
+
+    #[napi]
+    pub fn has_attributes(&self) -> bool {
+        self.attributes_wrapper.len() > 0
+    }
+
+    #[napi(getter, ts_return_type = "DOMStringMap")]
+    pub fn get_dataset(&self) -> Result<JsObject> {
+        DomStringMap::create(self.env, self.cyclic_reference.get_weak())
+    }
+
+    /// The token list of `attribute`, created on its first access.
+    fn get_token_list(
+        &mut self,
+        element: Reference<Element>,
+        attribute: LocalName,
+        supported_tokens: Option<&'static [&'static str]>,
+    ) -> Result<Reference<DomTokenList>> {
+        let initial_value = self
+            .attributes_wrapper
+            .get_attribute(attribute.clone())
+            .map(|attribute| attribute.value.to_string());
+
+        self.lazy_token_lists.get_or_init(&attribute, || {
+            DomTokenList::create(
+                element.downgrade().into(),
+                self.env,
+                attribute.clone(),
+                supported_tokens,
+                initial_value,
+            )
+        })
     }
 
     #[napi(getter)]
     pub fn get_class_list(
         &mut self,
         element: Reference<Element>,
-    ) -> Result<Reference<ClassList>> {
-        let initial_value = self.get_attribute("class".to_string());
-        self.lazy_class_list.get_or_init(|| {
-            ClassList::new(element.downgrade().into(), self.env, initial_value)
-        })
+    ) -> Result<Reference<DomTokenList>> {
+        self.get_token_list(element, local_name!("class"), None)
+    }
+
+    /// The link types of `a`, `area`, `form` and `link` elements.
+    #[napi(getter)]
+    pub fn get_rel_list(
+        &mut self,
+        element: Reference<Element>,
+    ) -> Result<Option<Reference<DomTokenList>>> {
+        let supported_tokens = match self.name.expanded() {
+            expanded_name!(html "a")
+            | expanded_name!(html "area")
+            | expanded_name!(html "form") => HYPERLINK_REL_TOKENS,
+            expanded_name!(html "link") => LINK_REL_TOKENS,
+            _ => return Ok(None),
+        };
+
+        self.get_token_list(element, local_name!("rel"), Some(supported_tokens))
+            .map(Some)
+    }
+
+    /// The restrictions lifted for the content of `iframe` elements.
+    #[napi(getter)]
+    pub fn get_sandbox(
+        &mut self,
+        element: Reference<Element>,
+    ) -> Result<Option<Reference<DomTokenList>>> {
+        if self.name.expanded() != expanded_name!(html "iframe") {
+            return Ok(None);
+        }
+
+        self.get_token_list(
+            element,
+            local_name!("sandbox"),
+            Some(SANDBOX_TOKENS),
+        )
+        .map(Some)
+    }
+
+    #[napi(getter)]
+    pub fn get_part(
+        &mut self,
+        element: Reference<Element>,
+    ) -> Result<Reference<DomTokenList>> {
+        self.get_token_list(element, LocalName::from("part"), None)
     }
 
     #[napi(getter)]
@@ -161,6 +503,23 @@ impl Element {
         self.get_node_name()
     }
 
+    #[napi(getter, js_name = "namespaceURI")]
+    pub fn get_namespace_uri(&self) -> Option<String> {
+        let namespace = &self.name.ns;
+
+        (!namespace.is_empty()).then(|| namespace.to_string())
+    }
+
+    #[napi(getter)]
+    pub fn get_prefix(&self) -> Option<String> {
+        self.name.prefix.as_ref().map(ToString::to_string)
+    }
+
+    #[napi(getter)]
+    pub fn get_local_name(&self) -> String {
+        self.name.local.to_string()
+    }
+
     #[napi(getter, js_name = "innerHTML")]
     pub fn get_inner_html(&self) -> String {
         serialize(
@@ -176,11 +535,18 @@ impl Element {
         &self,
         html: String,
     ) -> Result<()> {
-        while let Some(child) = self.get_first_child() {
-            self.remove_child(child)?;
+        let parent: Node = match &self.template_contents {
+            Some(contents) => contents.clone(self.env)?.into(),
+            None => self.into(),
+        };
+
+        while let Some(child) = parent.get_child_node(0) {
+            child.remove()?;
         }
 
-        Html5everDom::parse_and_append(self.env, self.into(), html)
+        let child_nodes =
+            Html5everDom::parse_fragment_nodes(self.env, &self.into(), html)?;
+        parent.insert_nodes(self.env, child_nodes, &InsertPosition::Append)
     }
 
     #[napi(getter, js_name = "outerHTML")]
@@ -214,23 +580,19 @@ impl Element {
 
         Html5everDom::parse_and_append(self.env, node.clone(), html)?;
 
-        let self_node: Node = self.into();
-        let position = InsertPosition::Position(self_node.get_position()?);
-
-        for child_node in node.shallow_child_nodes_iter().rev() {
-            parent.insert_node(self.env, child_node, &position)?;
-        }
-
-        self.remove()?;
+        let child_nodes = node
+            .shallow_child_nodes_iter()
+            .map(|child_node: ChildNode| Either::A(child_node.into()))
+            .collect();
 
-        Ok(())
+        self.as_node().replace_with(self.env, child_nodes)
     }
 
     #[napi(getter)]
     pub fn get_text_content(&self) -> Option<String> {
         let text = self
-            .get_all_child_nodes()
-            .into_iter()
+            .as_node()
+            .shallow_child_nodes_iter()
             .filter_map(|child| {
                 if let ChildNode::Text(text) = child {
                     text.get_text_content()
@@ -290,22 +652,24 @@ impl Element {
         &self,
         deep: Option<bool>,
     ) -> Result<Reference<Element>> {
-        let deep = deep.unwrap_or(false);
+        // the contents of a template are only copied in a deep clone, but
+        // the copy always gets a fragment of its own
+        let template_contents = match &self.template_contents {
+            Some(contents) => Some(contents.clone_node(deep)?),
+            None => None,
+        };
 
-        let clone = Self::new_reference(
+        let clone = Self::create(
             self.env,
             self.attributes_wrapper.clone(),
             self.name.clone(),
-            LazyReference::new(self.env),
-            LazyReference::new(self.env),
-            None, // TODO should this be cloned?
+            template_contents,
         )?;
+        let clone = self.as_node().with_node_document(clone)?;
 
-        if deep {
-            for child in self.get_child_nodes() {
-                let child_clone = child.clone_node(Some(true))?;
-                clone.append_child(child_clone)?;
-            }
+        if deep.unwrap_or(false) {
+            self.as_node()
+                .clone_children_into(&clone.clone(self.env)?.into())?;
         }
 
         Ok(clone)
@@ -317,8 +681,11 @@ impl Element {
         position: InsertPosition,
         element: &Element,
     ) -> Result<Reference<Element>> {
-        self.as_node()
-            .insert_node(self.env, element.into(), &position)?;
+        self.as_node().insert_adjacent(
+            self.env,
+            vec![element.into()],
+            &position,
+        )?;
 
         element.cyclic_reference.get()
     }
@@ -329,16 +696,14 @@ impl Element {
         position: InsertPosition,
         html: String,
     ) -> Result<()> {
-        let node: Node = self.into();
-
         let fragment =
             Html5everDom::create_document_fragment(self.env, html, None)?;
 
-        let child_nodes = fragment.get_child_nodes();
-
-        node.insert_nodes(self.env, child_nodes, &position)?;
-
-        Ok(())
+        self.as_node().insert_adjacent(
+            self.env,
+            vec![fragment.as_node()],
+            &position,
+        )
     }
 
     #[napi]
@@ -347,21 +712,22 @@ impl Element {
         position: InsertPosition,
         text: String,
     ) -> Result<()> {
-        let node: Node = self.into();
-
         let text_node = Text::new_reference(self.env, text)?;
-        node.insert_node(self.env, text_node.into(), &position)?;
 
-        Ok(())
+        self.as_node().insert_adjacent(
+            self.env,
+            vec![text_node.into()],
+            &position,
+        )
     }
 
     #[napi]
     pub fn matches(
         &self,
-        selectors: String,
+        selectors: Either<String, &CompiledSelector>,
     ) -> Result<bool> {
-        let selectors = crate::Selectors::compile(selectors)?;
+        let selectors = crate::Selectors::from_arg(selectors)?;
         let element_ref: ElementRef = self.cyclic_reference.get()?.into();
-        selectors.matches(&element_ref)
+        selectors.matches(element_ref)
     }
 }
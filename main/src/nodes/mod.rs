@@ -7,12 +7,23 @@ mod text;
 mod window;
 
 pub use comment::Comment;
-pub use document::Document;
+pub(crate) use document::{
+    about_blank,
+    IndexKey,
+    IndexedAttributes,
+};
+pub use document::{
+    Document,
+    DomImplementation,
+};
 pub use document_fragment::DocumentFragment;
 pub use document_type::DocumentType;
 pub use element::{
+    Attr,
+    DomTokenList,
     Element,
     ElementRef,
 };
 pub use text::Text;
+pub(crate) use window::define_globals;
 pub use window::Window;
@@ -18,6 +18,8 @@ impl Text {
 
     #[napi]
     pub fn clone_node(&self) -> Result<Reference<Self>> {
-        Self::new_reference(self.env, self.data.clone())
+        let clone = Self::new_reference(self.env, self.data.clone())?;
+
+        self.as_node().with_node_document(clone)
     }
 }
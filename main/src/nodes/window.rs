@@ -31,3 +31,35 @@ impl Window {
         self.document_reference.clone(self.env)
     }
 }
+
+/// Exposes `globals` as properties of every window, like the globals of the
+/// same names in browsers.
+pub(crate) fn define_globals(
+    exports: &napi::JsObject,
+    globals: &[(&str, napi::JsFunction)],
+) -> Result<()> {
+    use napi::{
+        JsFunction,
+        JsObject,
+        Property,
+        PropertyAttributes,
+    };
+
+    let window: JsFunction = exports.get_named_property("Window")?;
+    let mut prototype: JsObject =
+        window.coerce_to_object()?.get_named_property("prototype")?;
+
+    let properties = globals
+        .iter()
+        .map(|(name, constructor)| {
+            Ok(Property::new(name)?
+                .with_value(constructor)
+                .with_property_attributes(
+                    PropertyAttributes::Writable
+                        | PropertyAttributes::Configurable,
+                ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    prototype.define_properties(&properties)
+}
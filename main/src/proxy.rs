@@ -0,0 +1,79 @@
+//! Objects handed to JavaScript wrapped in a `Proxy`, for the properties
+//! napi classes can't have, like indices and names only known at runtime.
+
+use std::{
+    cell::OnceCell,
+    mem::ManuallyDrop,
+    thread::LocalKey,
+};
+
+use napi::{
+    Callback,
+    Env,
+    JsFunction,
+    JsObject,
+    Ref,
+    Result,
+};
+
+/// Holds a handler once it is created. It lives as long as the env of the
+/// thread, so it is never released.
+pub(crate) type ProxyHandlerCell = OnceCell<ManuallyDrop<Ref<()>>>;
+
+/// A `Proxy` handler with its traps, shared by all the proxies of a kind.
+pub(crate) struct ProxyHandler {
+    cell: &'static LocalKey<ProxyHandlerCell>,
+    traps: &'static [(&'static str, Callback)],
+}
+
+impl ProxyHandler {
+    pub(crate) const fn new(
+        cell: &'static LocalKey<ProxyHandlerCell>,
+        traps: &'static [(&'static str, Callback)],
+    ) -> Self {
+        Self {
+            cell,
+            traps,
+        }
+    }
+
+    pub(crate) fn wrap(
+        &self,
+        env: Env,
+        target: JsObject,
+    ) -> Result<JsObject> {
+        let handler = self.get_handler(env)?;
+        let proxy: JsFunction =
+            env.get_global()?.get_named_property("Proxy")?;
+
+        proxy.new_instance(&[target, handler])
+    }
+
+    fn get_handler(
+        &self,
+        env: Env,
+    ) -> Result<JsObject> {
+        let handler = self.cell.with(|handler| {
+            handler
+                .get()
+                .map(|handler| env.get_reference_value(handler))
+                .transpose()
+        })?;
+
+        if let Some(handler) = handler {
+            return Ok(handler);
+        }
+
+        let mut handler = env.create_object()?;
+        for (name, trap) in self.traps {
+            handler
+                .set_named_property(name, env.create_function(name, *trap)?)?;
+        }
+
+        let reference = env.create_reference(&handler)?;
+        self.cell
+            .with(|handler| handler.set(ManuallyDrop::new(reference)).ok());
+
+        Ok(handler)
+    }
+}
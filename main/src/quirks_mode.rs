@@ -1,5 +1,3 @@
-use napi::bindgen_prelude::ToNapiValue;
-
 #[napi]
 pub enum QuirksMode {
     Quirks,
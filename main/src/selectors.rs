@@ -1,20 +1,32 @@
 use std::{
+    cell::RefCell,
     fmt::Write,
     ops::Deref,
 };
 
 use cssparser::{
     serialize_identifier,
+    BasicParseErrorKind,
     CowRcStr,
     ParseError,
+    ParseErrorKind,
     SourceLocation,
     ToCss,
+    Token,
 };
+use html5ever::local_name;
+use indexmap::IndexMap;
 use napi::{
+    bindgen_prelude::Reference,
     Either,
     Result,
 };
+use precomputed_hash::PrecomputedHash;
 use selectors::{
+    bloom::{
+        BloomFilter,
+        BLOOM_HASH_MASK,
+    },
     matching::{
         matches_selector,
         MatchingContext,
@@ -22,6 +34,8 @@ use selectors::{
         QuirksMode,
     },
     parser::{
+        AncestorHashes,
+        Component,
         NonTSPseudoClass,
         SelectorParseErrorKind,
     },
@@ -32,8 +46,11 @@ use selectors::{
 use crate::{
     Document,
     DocumentFragment,
+    Element,
     ElementRef,
+    IndexKey,
     Node,
+    NodeData,
     WeakReference,
 };
 
@@ -67,12 +84,28 @@ impl AsRef<str> for AttrValue {
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct StringValue(String);
 
-impl ToString for StringValue {
-    fn to_string(&self) -> String {
-        self.0.clone()
+impl std::fmt::Display for StringValue {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PrecomputedHash for StringValue {
+    fn precomputed_hash(&self) -> u32 {
+        hash_str(&self.0)
     }
 }
 
+/// FNV-1a, used for both selectors and elements in the bloom filter.
+fn hash_str(value: &str) -> u32 {
+    value.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
 impl ToCss for StringValue {
     fn to_css<W>(
         &self,
@@ -241,70 +274,609 @@ impl<'i> selectors::parser::Parser<'i> for Parser {
     }
 }
 
+/// The selectors crate packs each part of the specificity into 10 bits.
+const SPECIFICITY_MASK: u32 = (1 << 10) - 1;
+
+/// How many compiled selector lists are kept around by `Selectors::compile`.
+const SELECTORS_CACHE_CAPACITY: usize = 256;
+
+thread_local! {
+    // Least recently used entries are at the front, so evicting is a matter of
+    // dropping index 0.
+    static SELECTORS_CACHE: RefCell<IndexMap<String, Selectors>> =
+        RefCell::new(IndexMap::with_capacity(SELECTORS_CACHE_CAPACITY));
+}
+
 impl Selectors {
     pub fn compile(css: String) -> napi::Result<Selectors> {
-        let mut parser = cssparser::ParserInput::new(css.as_str());
-        match SelectorList::parse(
-            &Parser,
-            &mut cssparser::Parser::new(&mut parser),
-        ) {
-            Ok(list) => {
-                Ok(Selectors(list.0.into_iter().map(Selector).collect()))
+        let cached = SELECTORS_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let index = cache.get_index_of(&css)?;
+            let last = cache.len() - 1;
+            cache.move_index(index, last);
+            cache
+                .get_index(last)
+                .map(|(_, selectors)| selectors.clone())
+        });
+
+        if let Some(selectors) = cached {
+            return Ok(selectors);
+        }
+
+        let selectors = Self::parse(&css)?;
+
+        SELECTORS_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.len() >= SELECTORS_CACHE_CAPACITY {
+                cache.shift_remove_index(0);
+            }
+            cache.insert(css, selectors.clone());
+        });
+
+        Ok(selectors)
+    }
+
+    pub(crate) fn from_arg(
+        selectors: Either<String, &CompiledSelector>
+    ) -> napi::Result<Selectors> {
+        match selectors {
+            Either::A(css) => Self::compile(css),
+            Either::B(compiled) => Ok(compiled.selectors.clone()),
+        }
+    }
+
+    fn parse(css: &str) -> napi::Result<Selectors> {
+        Self::try_parse(css).map_err(|err| {
+            napi::Error::from_reason(format!(
+                "Failed to parse selector: {}",
+                err
+            ))
+        })
+    }
+
+    fn try_parse(
+        css: &str
+    ) -> std::result::Result<Selectors, SelectorParseError> {
+        let mut parser = cssparser::ParserInput::new(css);
+        SelectorList::parse(&Parser, &mut cssparser::Parser::new(&mut parser))
+            .map(|list| Selectors(list.0.into_iter().map(Selector).collect()))
+            .map_err(Into::into)
+    }
+
+    /// A document index lookup that yields every element these selectors can
+    /// match, if there is one. The elements found still have to be matched.
+    pub(crate) fn index_key(&self) -> Option<IndexKey> {
+        match self.0.as_slice() {
+            [selector] => selector.index_key(),
+            _ => None,
+        }
+    }
+
+    /// The quirks mode of the document or fragment `node` belongs to.
+    fn get_quirks_mode(node: &Node) -> Result<QuirksMode> {
+        let maybe_parent = match node {
+            Node::Document(document) => return Ok(document.quirks_mode.into()),
+            Node::DocumentFragment(fragment) => {
+                return Ok(fragment.quirks_mode.into())
             },
-            Err(e) => {
-                let reason = format!("Failed to parse selector: {:?}", e);
-                Err(napi::Error::from_reason(reason))
+            _ => {
+                node.parent_iterator::<Either<
+                    WeakReference<Document>,
+                    WeakReference<DocumentFragment>,
+                >>()
+                .try_next()?
             },
-        }
+        };
+
+        let env = NodeData::from(node).env;
+
+        Ok(match maybe_parent {
+            Some(Either::A(weak_document)) => {
+                weak_document.upgrade(env)?.quirks_mode.into()
+            },
+            Some(Either::B(weak_fragment)) => {
+                weak_fragment.upgrade(env)?.quirks_mode.into()
+            },
+            None => match node.get_node_document()? {
+                Some(document) => document.quirks_mode.into(),
+                None => QuirksMode::NoQuirks,
+            },
+        })
+    }
+
+    /// Whether `node` is in an HTML document, rather than an XML one, which
+    /// decides if HTML elements match names case-insensitively.
+    fn is_html_document(node: &Node) -> Result<bool> {
+        Ok(node
+            .get_node_document()?
+            .is_none_or(|document| document.is_html()))
     }
 
     pub fn matches(
         &self,
-        element: &ElementRef,
+        element: ElementRef,
     ) -> Result<bool> {
-        let node: Node = element.into();
-
-        let quirks_mode =
-            node.parent_iterator::<Either<
-                WeakReference<Document>,
-                WeakReference<DocumentFragment>,
-            >>()
-            .try_next()?
-            .map_or(QuirksMode::NoQuirks, |parent| match parent {
-                Either::A(weak_document) => {
-                    let document = weak_document.upgrade(element.env).unwrap();
-                    document.quirks_mode.into()
-                },
-                Either::B(weak_fragment) => {
-                    let fragment = weak_fragment.upgrade(element.env).unwrap();
-                    fragment.quirks_mode.into()
-                },
-            });
+        let node: Node = (&element).into();
+        let quirks_mode = Self::get_quirks_mode(&node)?;
+        let element = element.in_html_document(Self::is_html_document(&node)?);
 
         let m = self
             .0
             .iter()
-            .any(|selector| selector.matches(element, quirks_mode));
+            .any(|selector| selector.matches(&element, None, quirks_mode));
         Ok(m)
     }
 }
 
+/// Matches `Selectors` against the descendants of a node, working out the
+/// quirks mode, whether the document is an HTML document and what to look for
+/// in the ancestor bloom filter once.
+pub struct SelectorsMatcher {
+    selectors: Selectors,
+    ancestor_hashes: Vec<AncestorHashes>,
+    quirks_mode: QuirksMode,
+    is_html_document: bool,
+}
+
+impl SelectorsMatcher {
+    pub fn new(
+        selectors: Selectors,
+        root: &Node,
+    ) -> Result<Self> {
+        let quirks_mode = Selectors::get_quirks_mode(root)?;
+        let ancestor_hashes = selectors
+            .0
+            .iter()
+            .map(|selector| AncestorHashes::new(&selector.0, quirks_mode))
+            .collect();
+
+        Ok(Self {
+            selectors,
+            ancestor_hashes,
+            quirks_mode,
+            is_html_document: Selectors::is_html_document(root)?,
+        })
+    }
+
+    /// Prepares a descendant of the root to be passed to `matches`.
+    pub fn element_ref(
+        &self,
+        element: impl Into<ElementRef>,
+    ) -> ElementRef {
+        element.into().in_html_document(self.is_html_document)
+    }
+
+    /// Whether any of the selectors have ancestors to look for in the bloom
+    /// filter, as it is only worth keeping up to date then.
+    pub fn uses_ancestor_filter(&self) -> bool {
+        self.ancestor_hashes
+            .iter()
+            .any(|hashes| hashes.packed_hashes[0] != 0)
+    }
+
+    /// `element` comes from `element_ref`, and `bloom_filter` has to hold its
+    /// ancestors, see `AncestorFilter`.
+    pub fn matches(
+        &self,
+        element: &ElementRef,
+        bloom_filter: Option<&BloomFilter>,
+    ) -> bool {
+        self.selectors.0.iter().zip(&self.ancestor_hashes).any(
+            |(selector, hashes)| {
+                let ancestors = bloom_filter.map(|filter| (filter, hashes));
+                selector.matches(element, ancestors, self.quirks_mode)
+            },
+        )
+    }
+}
+
+/// A bloom filter of the local names, namespaces, ids and class names of the
+/// ancestors of the element being matched, letting descendant and child
+/// selectors that can't match be rejected without walking up the tree.
+pub struct AncestorFilter {
+    bloom_filter: Box<BloomFilter>,
+    hashes: Vec<u32>,
+    // how many of `hashes` each pushed element added
+    pushed: Vec<usize>,
+}
+
+impl AncestorFilter {
+    /// Creates a filter for the descendants of `root`, holding `root` and its
+    /// ancestors.
+    pub fn new(root: &Node) -> Result<Self> {
+        let mut bloom_filter = Box::new(BloomFilter::new());
+        let env = NodeData::from(root).env;
+
+        let mut insert = |element: &Element| {
+            Self::each_hash(element, |hash| bloom_filter.insert_hash(hash))
+        };
+
+        if let Node::Element(element) = root {
+            insert(element);
+        }
+
+        for parent in root.parent_iterator::<WeakReference<Element>>() {
+            let element = parent?.upgrade(env)?;
+            insert(&element);
+        }
+
+        Ok(Self {
+            bloom_filter,
+            hashes: vec![],
+            pushed: vec![],
+        })
+    }
+
+    pub fn bloom_filter(&self) -> &BloomFilter {
+        &self.bloom_filter
+    }
+
+    /// How many elements have been pushed and not popped.
+    pub fn depth(&self) -> usize {
+        self.pushed.len()
+    }
+
+    pub fn push(
+        &mut self,
+        element: &Reference<Element>,
+    ) {
+        let len = self.hashes.len();
+
+        Self::each_hash(element, |hash| {
+            self.bloom_filter.insert_hash(hash);
+            self.hashes.push(hash);
+        });
+
+        self.pushed.push(self.hashes.len() - len);
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(count) = self.pushed.pop() {
+            for _ in 0..count {
+                let hash = self.hashes.pop().unwrap();
+                self.bloom_filter.remove_hash(hash);
+            }
+        }
+    }
+
+    fn each_hash<F>(
+        element: &Element,
+        mut f: F,
+    ) where
+        F: FnMut(u32),
+    {
+        let mut hash = |value: &str| f(hash_str(value) & BLOOM_HASH_MASK);
+
+        hash(&element.name.local);
+        hash(&element.name.ns);
+
+        let attributes = &element.attributes_wrapper;
+
+        if let Some(id) = attributes.get_attribute(local_name!("id")) {
+            if !id.value.is_empty() {
+                hash(&id.value);
+            }
+        }
+
+        if let Some(class) = attributes.get_attribute(local_name!("class")) {
+            class.value.split_ascii_whitespace().for_each(hash);
+        }
+    }
+}
+
 impl Selector {
+    /// The specificity as `[ids, classes, types]`, see
+    /// https://drafts.csswg.org/selectors/#specificity-rules
+    pub fn specificity(&self) -> [u32; 3] {
+        let specificity = self.0.specificity();
+
+        [
+            specificity >> 20,
+            (specificity >> 10) & SPECIFICITY_MASK,
+            specificity & SPECIFICITY_MASK,
+        ]
+    }
+
+    /// Picks the most selective of the `#id`, `.class` and type selectors in
+    /// the rightmost compound selector.
+    fn index_key(&self) -> Option<IndexKey> {
+        let mut key = None;
+
+        for component in self.0.iter() {
+            match component {
+                Component::ID(id) => return Some(IndexKey::Id(id.to_string())),
+                Component::Class(class) => {
+                    key = Some(IndexKey::Class(class.to_string()))
+                },
+                Component::LocalName(local_name) if key.is_none() => {
+                    key = Some(IndexKey::LocalName {
+                        name: local_name.name.as_str().into(),
+                        lower_name: local_name.lower_name.as_str().into(),
+                    })
+                },
+                _ => {},
+            }
+        }
+
+        key
+    }
+
     pub fn matches(
         &self,
         element: &ElementRef,
+        ancestors: Option<(&BloomFilter, &AncestorHashes)>,
         quirks_mode: QuirksMode,
     ) -> bool {
-        let mut context =
-            MatchingContext::new(MatchingMode::Normal, None, None, quirks_mode);
+        let mut context = MatchingContext::new(
+            MatchingMode::Normal,
+            ancestors.map(|(bloom_filter, _)| bloom_filter),
+            None,
+            quirks_mode,
+        );
 
         matches_selector(
             &self.0,
             0,
-            None,
+            ancestors.map(|(_, hashes)| hashes),
             element,
             &mut context,
             &mut |_, _| {},
         )
     }
 }
+
+/// A selector list that has been parsed once and can be passed to `matches`,
+/// `querySelector` and `querySelectorAll` instead of a string.
+#[napi]
+pub struct CompiledSelector {
+    selectors: Selectors,
+    source: String,
+}
+
+#[napi]
+impl CompiledSelector {
+    #[napi(getter)]
+    pub fn get_selector_text(&self) -> String {
+        self.source.clone()
+    }
+}
+
+/// A single selector of a selector list passed to `parseSelector`.
+#[napi(object)]
+pub struct SelectorInfo {
+    /// The selector serialized in its canonical form.
+    pub text: String,
+    /// `[ids, classes, types]`
+    pub specificity: Vec<u32>,
+}
+
+#[napi(object)]
+pub struct SelectorParseError {
+    pub message: String,
+    /// 1-based line of the error within the selector text.
+    pub line: u32,
+    /// 1-based column of the error within the selector text.
+    pub column: u32,
+}
+
+impl std::fmt::Display for SelectorParseError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl<'i> From<ParseError<'i, SelectorParseErrorKind<'i>>>
+    for SelectorParseError
+{
+    fn from(err: ParseError<'i, SelectorParseErrorKind<'i>>) -> Self {
+        use SelectorParseErrorKind::*;
+
+        let token = |token: &Token| format!("\"{}\"", token.to_css_string());
+
+        let message = match err.kind {
+            ParseErrorKind::Basic(BasicParseErrorKind::UnexpectedToken(t)) => {
+                format!("Unexpected token {}", token(&t))
+            },
+            ParseErrorKind::Basic(BasicParseErrorKind::EndOfInput) => {
+                "Unexpected end of input".to_string()
+            },
+            ParseErrorKind::Basic(BasicParseErrorKind::AtRuleInvalid(name)) => {
+                format!("Unexpected at-rule \"@{}\"", name)
+            },
+            ParseErrorKind::Basic(BasicParseErrorKind::AtRuleBodyInvalid) => {
+                "Invalid at-rule body".to_string()
+            },
+            ParseErrorKind::Basic(
+                BasicParseErrorKind::QualifiedRuleInvalid,
+            ) => "Invalid qualified rule".to_string(),
+            ParseErrorKind::Custom(kind) => match kind {
+                NoQualifiedNameInAttributeSelector(t) => {
+                    format!("Expected an attribute name, found {}", token(&t))
+                },
+                EmptySelector => "Empty selector".to_string(),
+                DanglingCombinator => {
+                    "Combinator is not followed by a selector".to_string()
+                },
+                NonCompoundSelector => {
+                    "Expected a compound selector".to_string()
+                },
+                NonPseudoElementAfterSlotted => {
+                    "Only pseudo-elements may follow ::slotted()".to_string()
+                },
+                InvalidPseudoElementAfterSlotted => {
+                    "Invalid pseudo-element after ::slotted()".to_string()
+                },
+                InvalidPseudoElementInsideWhere => {
+                    "Pseudo-elements are not allowed inside :is() or :where()"
+                        .to_string()
+                },
+                InvalidState => "Pseudo-class not allowed here".to_string(),
+                UnexpectedTokenInAttributeSelector(t) => {
+                    format!(
+                        "Unexpected token {} in attribute selector",
+                        token(&t)
+                    )
+                },
+                PseudoElementExpectedColon(t) => {
+                    format!(
+                        "Expected \":\" before pseudo-element, found {}",
+                        token(&t)
+                    )
+                },
+                PseudoElementExpectedIdent(t) => {
+                    format!(
+                        "Expected a pseudo-element name, found {}",
+                        token(&t)
+                    )
+                },
+                NoIdentForPseudo(t) => {
+                    format!("Expected a pseudo-class name, found {}", token(&t))
+                },
+                UnsupportedPseudoClassOrElement(name) => {
+                    format!(
+                        "Unsupported pseudo-class or pseudo-element \"{}\"",
+                        name
+                    )
+                },
+                UnexpectedIdent(name) => {
+                    format!("Unexpected identifier \"{}\"", name)
+                },
+                ExpectedNamespace(prefix) => {
+                    format!("Undeclared namespace prefix \"{}\"", prefix)
+                },
+                ExpectedBarInAttr(t) => {
+                    format!(
+                        "Expected \"|\" in attribute selector, found {}",
+                        token(&t)
+                    )
+                },
+                BadValueInAttr(t) => {
+                    format!("Invalid attribute selector value {}", token(&t))
+                },
+                InvalidQualNameInAttr(t) => {
+                    format!("Invalid attribute name {}", token(&t))
+                },
+                ExplicitNamespaceUnexpectedToken(t) => {
+                    format!("Unexpected token {} after namespace", token(&t))
+                },
+                ClassNeedsIdent(t) => {
+                    format!("Expected a class name, found {}", token(&t))
+                },
+            },
+        };
+
+        SelectorParseError {
+            message,
+            line: err.location.line + 1,
+            column: err.location.column,
+        }
+    }
+}
+
+#[napi(object)]
+pub struct SelectorParseResult {
+    pub selectors: Vec<SelectorInfo>,
+    pub error: Option<SelectorParseError>,
+}
+
+/// Parses a selector list without matching anything, returning the
+/// specificity and canonical serialization of each selector or where parsing
+/// failed.
+#[napi]
+pub fn parse_selector(selectors: String) -> SelectorParseResult {
+    match Selectors::try_parse(&selectors) {
+        Ok(list) => SelectorParseResult {
+            selectors: list
+                .0
+                .iter()
+                .map(|selector| SelectorInfo {
+                    text: selector.0.to_css_string(),
+                    specificity: selector.specificity().to_vec(),
+                })
+                .collect(),
+            error: None,
+        },
+        Err(error) => SelectorParseResult {
+            selectors: vec![],
+            error: Some(error),
+        },
+    }
+}
+
+#[napi]
+pub fn compile_selector(selectors: String) -> Result<CompiledSelector> {
+    Ok(CompiledSelector {
+        selectors: Selectors::compile(selectors.clone())?,
+        source: selectors,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cache_len() -> usize {
+        SELECTORS_CACHE.with(|cache| cache.borrow().len())
+    }
+
+    fn is_cached(css: &str) -> bool {
+        SELECTORS_CACHE.with(|cache| cache.borrow().contains_key(css))
+    }
+
+    #[test]
+    fn test_compile_evicts_least_recently_used() {
+        for i in 0..SELECTORS_CACHE_CAPACITY {
+            Selectors::compile(format!(".c{}", i)).unwrap();
+        }
+        assert_eq!(cache_len(), SELECTORS_CACHE_CAPACITY);
+
+        // touch the oldest entry so that ".c1" becomes the eviction candidate
+        Selectors::compile(".c0".to_string()).unwrap();
+        Selectors::compile("div > p".to_string()).unwrap();
+
+        assert_eq!(cache_len(), SELECTORS_CACHE_CAPACITY);
+        assert!(is_cached(".c0"));
+        assert!(!is_cached(".c1"));
+        assert!(is_cached("div > p"));
+    }
+
+    #[test]
+    fn test_compile_selector_keeps_source() {
+        let compiled = compile_selector("a[href]".to_string()).unwrap();
+        assert_eq!(compiled.get_selector_text(), "a[href]");
+        assert!(compile_selector("a[".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_parse_selector() {
+        let result = parse_selector("#a .b > P, a:hover:not(.c)".to_string());
+        assert!(result.error.is_none());
+        assert_eq!(result.selectors[0].text, "#a .b > P");
+        assert_eq!(result.selectors[0].specificity, vec![1, 1, 1]);
+        assert_eq!(result.selectors[1].text, "a:hover:not(.c)");
+        assert_eq!(result.selectors[1].specificity, vec![0, 2, 1]);
+
+        let error = parse_selector("div,\n  p >".to_string()).error.unwrap();
+        assert_eq!(error.message, "Combinator is not followed by a selector");
+        assert_eq!((error.line, error.column), (2, 6));
+
+        let error = parse_selector("a:nope".to_string()).error.unwrap();
+        assert_eq!(
+            error.message,
+            "Unsupported pseudo-class or pseudo-element \"nope\""
+        );
+    }
+
+    #[test]
+    fn test_compile_does_not_cache_errors() {
+        assert!(Selectors::compile("div >".to_string()).is_err());
+        assert!(!is_cached("div >"));
+    }
+}
@@ -36,7 +36,7 @@ impl Serialize for Node {
             TraversalScope::ChildrenOnly(_) => {
                 if let Node::Element(element) = &self {
                     element
-                        .get_all_child_nodes()
+                        .get_serialized_child_nodes()
                         .iter()
                         .cloned()
                         .map(SerializeOp::Open)
@@ -72,7 +72,7 @@ impl Serialize for Node {
                         ));
 
                         element
-                            .get_all_child_nodes()
+                            .get_serialized_child_nodes()
                             .into_iter()
                             .rev()
                             .for_each(|child_node| {
@@ -91,11 +91,9 @@ impl StyleDeclaration {
 
         let mut owner = self.owner.upgrade(self.env)?;
 
-        owner
-            .attributes_wrapper
-            .set_attribute("style".into(), value.into());
-
-        Ok(())
+        owner.update_attributes(|attributes| {
+            attributes.set_attribute("style".into(), value.into())
+        })
     }
 
     pub(crate) fn clear(&mut self) -> Result<()> {
@@ -183,7 +181,7 @@ impl StyleDeclaration {
         priority: Option<String>,
     ) -> Result<()> {
         let important =
-            priority.map_or(false, |priority| priority == "important");
+            priority.is_some_and(|priority| priority == "important");
 
         match self.get_data_mut(&property) {
             Some(data) => {
@@ -0,0 +1,187 @@
+//! DOM methods taking any number of arguments, like `before(...nodes)`.
+//!
+//! `#[napi]` methods only receive as many arguments as they declare, so these
+//! are defined on the class prototypes when the module is loaded instead.
+
+use std::ptr;
+
+use napi::{
+    bindgen_prelude::FromNapiValue,
+    check_status,
+    sys,
+    Callback,
+    Either,
+    Env,
+    JsError,
+    JsFunction,
+    JsObject,
+    Property,
+    PropertyAttributes,
+    Result,
+};
+
+use crate::{
+    ChildNode,
+    Node,
+    ParentNode,
+};
+
+type NodesOrStrings = Vec<Either<Node, String>>;
+
+/// Returns `this` and all arguments of a call.
+unsafe fn get_call_info(
+    env: sys::napi_env,
+    info: sys::napi_callback_info,
+) -> Result<(sys::napi_value, Vec<sys::napi_value>)> {
+    let mut argc = 0;
+    let mut this = ptr::null_mut();
+
+    check_status!(sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        ptr::null_mut(),
+        &mut this,
+        ptr::null_mut(),
+    ))?;
+
+    let mut args = vec![ptr::null_mut(); argc];
+
+    check_status!(sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        args.as_mut_ptr(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+    ))?;
+
+    Ok((this, args))
+}
+
+macro_rules! variadic_method {
+    ($name:ident, $this:ty, $f:expr) => {
+        unsafe extern "C" fn $name(
+            raw_env: sys::napi_env,
+            info: sys::napi_callback_info,
+        ) -> sys::napi_value {
+            let result =
+                get_call_info(raw_env, info).and_then(|(this, args)| {
+                    let env = Env::from_raw(raw_env);
+                    let this = <$this>::from_napi_value(raw_env, this)?;
+                    let args = args
+                        .into_iter()
+                        .map(|arg| FromNapiValue::from_napi_value(raw_env, arg))
+                        .collect::<Result<NodesOrStrings>>()?;
+
+                    $f(env, this, args)
+                });
+
+            if let Err(err) = result {
+                JsError::from(err).throw_into(raw_env);
+            }
+
+            // `undefined`
+            ptr::null_mut()
+        }
+    };
+}
+
+variadic_method!(before, ChildNode, |env, this: ChildNode, nodes| {
+    Node::from(this).before(env, nodes)
+});
+
+variadic_method!(after, ChildNode, |env, this: ChildNode, nodes| {
+    Node::from(this).after(env, nodes)
+});
+
+variadic_method!(replace_with, ChildNode, |env, this: ChildNode, nodes| {
+    Node::from(this).replace_with(env, nodes)
+});
+
+variadic_method!(prepend, ParentNode, |env, this: ParentNode, nodes| {
+    this.upgrade(env)?.prepend(env, nodes)
+});
+
+variadic_method!(append, ParentNode, |env, this: ParentNode, nodes| {
+    this.upgrade(env)?.append(env, nodes)
+});
+
+variadic_method!(
+    replace_children,
+    ParentNode,
+    |env, this: ParentNode, nodes| {
+        this.upgrade(env)?.replace_children(env, nodes)
+    }
+);
+
+const CHILD_NODE_METHODS: &[(&str, Callback)] = &[
+    ("before", before),
+    ("after", after),
+    ("replaceWith", replace_with),
+];
+
+const PARENT_NODE_METHODS: &[(&str, Callback)] = &[
+    ("prepend", prepend),
+    ("append", append),
+    ("replaceChildren", replace_children),
+];
+
+fn define_methods(
+    exports: &JsObject,
+    class: &str,
+    methods: &[(&str, Callback)],
+) -> Result<()> {
+    let constructor: JsFunction = exports.get_named_property(class)?;
+    let mut prototype: JsObject = constructor
+        .coerce_to_object()?
+        .get_named_property("prototype")?;
+
+    let properties = methods
+        .iter()
+        .map(|(name, callback)| {
+            Ok(Property::new(name)?
+                .with_method(*callback)
+                .with_property_attributes(
+                    PropertyAttributes::Writable
+                        | PropertyAttributes::Enumerable
+                        | PropertyAttributes::Configurable,
+                ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    prototype.define_properties(&properties)
+}
+
+// Test binaries aren't loaded by Node.js, so they can't link the module
+// registration, but still check everything it sets up.
+#[cfg_attr(not(test), module_exports)]
+#[cfg_attr(test, allow(dead_code))]
+fn init(
+    exports: JsObject,
+    env: Env,
+) -> Result<()> {
+    for class in ["Comment", "DocumentType", "Element", "Text"] {
+        define_methods(&exports, class, CHILD_NODE_METHODS)?;
+    }
+
+    for class in ["Document", "DocumentFragment", "Element"] {
+        define_methods(&exports, class, PARENT_NODE_METHODS)?;
+    }
+
+    crate::collections::define_array_methods(env, &exports)?;
+    let node = crate::node_wrappers::define_node_interface(env, &exports)?;
+    let interfaces = crate::html_elements::define_interfaces(env, &exports)?;
+
+    let mut globals = vec![("Node", node)];
+    for class in crate::node_wrappers::NODE_CLASSES
+        .into_iter()
+        .chain(["DOMParser", "XMLSerializer"])
+    {
+        globals.push((class, exports.get_named_property(class)?));
+    }
+    globals.extend(interfaces);
+    crate::nodes::define_globals(&exports, &globals)?;
+
+    Ok(())
+}
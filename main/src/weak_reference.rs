@@ -55,4 +55,12 @@ where
             )
         })
     }
+
+    /// Like `upgrade`, but `None` once the value has been garbage collected.
+    pub fn try_upgrade(
+        &self,
+        env: Env,
+    ) -> Result<Option<Reference<T>>> {
+        self.0.upgrade(env)
+    }
 }
@@ -0,0 +1,307 @@
+//! Serializes nodes as XML, declaring the namespaces they are in, see
+//! https://w3c.github.io/DOM-Parsing/#xml-serialization
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    rc::Rc,
+};
+
+use html5ever::{
+    local_name,
+    namespace_url,
+    ns,
+    Namespace,
+    Prefix,
+    QualName,
+};
+use napi::Result;
+
+use crate::{
+    ChildNode,
+    Element,
+    Node,
+};
+
+/// See https://w3c.github.io/DOM-Parsing/#the-xmlserializer-interface
+#[napi(js_name = "XMLSerializer")]
+pub struct XmlSerializer {}
+
+#[napi]
+impl XmlSerializer {
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    #[napi]
+    pub fn serialize_to_string(
+        &self,
+        node: Node,
+    ) -> Result<String> {
+        Ok(serialize_xml(node))
+    }
+}
+
+/// The namespaces in scope of an element.
+#[derive(Clone)]
+struct Scope {
+    default_namespace: Namespace,
+    prefixes: Rc<HashMap<Prefix, Namespace>>,
+}
+
+enum SerializeOp {
+    Open(ChildNode, Scope),
+    Close(String),
+}
+
+pub fn serialize_xml(node: Node) -> String {
+    let scope = Scope {
+        default_namespace: ns!(),
+        prefixes: Default::default(),
+    };
+
+    let mut ops: VecDeque<SerializeOp> = match &node {
+        Node::Document(_) | Node::DocumentFragment(_) => node
+            .shallow_child_nodes_iter()
+            .map(|child_node| SerializeOp::Open(child_node, scope.clone()))
+            .collect(),
+        _ => VecDeque::from([SerializeOp::Open((&node).into(), scope)]),
+    };
+
+    let mut output = String::new();
+    let mut prefix_index = 1;
+
+    while let Some(op) = ops.pop_front() {
+        match op {
+            SerializeOp::Open(node, scope) => match &node {
+                ChildNode::Comment(comment) => {
+                    output.push_str("<!--");
+                    output.push_str(&comment.data);
+                    output.push_str("-->");
+                },
+                ChildNode::DocumentType(doc_type) => {
+                    output.push_str("<!DOCTYPE ");
+                    output.push_str(&doc_type.name);
+                    if !doc_type.public_id.is_empty() {
+                        output.push_str(" PUBLIC \"");
+                        output.push_str(&doc_type.public_id);
+                        output.push('"');
+                    } else if !doc_type.system_id.is_empty() {
+                        output.push_str(" SYSTEM");
+                    }
+                    if !doc_type.system_id.is_empty() {
+                        output.push_str(" \"");
+                        output.push_str(&doc_type.system_id);
+                        output.push('"');
+                    }
+                    output.push('>');
+                },
+                ChildNode::Element(element) => {
+                    let (name, scope) = write_start_tag(
+                        &mut output,
+                        element,
+                        scope,
+                        &mut prefix_index,
+                    );
+                    let child_nodes = element.get_serialized_child_nodes();
+
+                    if child_nodes.is_empty()
+                        && (element.name.ns != ns!(html)
+                            || is_void(&element.name))
+                    {
+                        output.push_str(match element.name.ns == ns!(html) {
+                            true => " />",
+                            false => "/>",
+                        });
+                        continue;
+                    }
+
+                    output.push('>');
+                    ops.push_front(SerializeOp::Close(name));
+                    child_nodes.into_iter().rev().for_each(|child_node| {
+                        ops.push_front(SerializeOp::Open(
+                            child_node,
+                            scope.clone(),
+                        ))
+                    });
+                },
+                ChildNode::Text(text) => escape(&mut output, &text.data, false),
+            },
+            SerializeOp::Close(name) => {
+                output.push_str("</");
+                output.push_str(&name);
+                output.push('>');
+            },
+        }
+    }
+
+    output
+}
+
+/// Writes `<name` and the attributes of `element`, with declarations for
+/// the namespaces that are not in `scope` yet. Returns the qualified name
+/// and the scope of the children.
+fn write_start_tag(
+    output: &mut String,
+    element: &Element,
+    mut scope: Scope,
+    prefix_index: &mut usize,
+) -> (String, Scope) {
+    let attributes = element.attributes_wrapper.iter();
+
+    for attribute in attributes.clone() {
+        if attribute.name.ns != ns!(xmlns) {
+            continue;
+        }
+        let namespace = Namespace::from(&attribute.value[..]);
+        match attribute.name.prefix {
+            None => scope.default_namespace = namespace,
+            Some(_) => {
+                Rc::make_mut(&mut scope.prefixes)
+                    .insert(Prefix::from(&attribute.name.local[..]), namespace);
+            },
+        }
+    }
+
+    let name = &element.name;
+    let mut declarations = Vec::new();
+
+    let qualified_name = match &name.prefix {
+        Some(prefix) => {
+            if prefix != "xml" && scope.prefixes.get(prefix) != Some(&name.ns) {
+                Rc::make_mut(&mut scope.prefixes)
+                    .insert(prefix.clone(), name.ns.clone());
+                declarations.push((format!("xmlns:{}", prefix), &name.ns));
+            }
+            format!("{}:{}", prefix, name.local)
+        },
+        None => {
+            if scope.default_namespace != name.ns {
+                scope.default_namespace = name.ns.clone();
+                declarations.push(("xmlns".to_string(), &name.ns));
+            }
+            name.local.to_string()
+        },
+    };
+
+    output.push('<');
+    output.push_str(&qualified_name);
+
+    for (name, namespace) in declarations {
+        write_attribute(output, &name, namespace);
+    }
+
+    for attribute in attributes {
+        let name = &attribute.name;
+        let attribute_name = match &name.ns {
+            &ns!() => name.local.to_string(),
+            &ns!(xmlns) => match name.prefix {
+                None => "xmlns".to_string(),
+                Some(_) => format!("xmlns:{}", name.local),
+            },
+            &ns!(xml) => format!("xml:{}", name.local),
+            namespace => {
+                let prefix = attribute_prefix(
+                    output,
+                    &mut scope,
+                    name,
+                    namespace,
+                    prefix_index,
+                );
+                format!("{}:{}", prefix, name.local)
+            },
+        };
+
+        write_attribute(output, &attribute_name, &attribute.value);
+    }
+
+    (qualified_name, scope)
+}
+
+/// The prefix of an attribute in `namespace`, declaring it unless it is in
+/// `scope` already. Attributes without one get a generated prefix.
+fn attribute_prefix(
+    output: &mut String,
+    scope: &mut Scope,
+    name: &QualName,
+    namespace: &Namespace,
+    prefix_index: &mut usize,
+) -> Prefix {
+    if let Some(prefix) = &name.prefix {
+        if scope.prefixes.get(prefix) == Some(namespace) {
+            return prefix.clone();
+        }
+    } else if let Some((prefix, _)) =
+        scope.prefixes.iter().find(|(_, ns)| *ns == namespace)
+    {
+        return prefix.clone();
+    }
+
+    let prefix = name.prefix.clone().unwrap_or_else(|| {
+        let prefix = Prefix::from(format!("ns{}", prefix_index));
+        *prefix_index += 1;
+        prefix
+    });
+
+    Rc::make_mut(&mut scope.prefixes).insert(prefix.clone(), namespace.clone());
+    write_attribute(output, &format!("xmlns:{}", prefix), namespace);
+
+    prefix
+}
+
+fn write_attribute(
+    output: &mut String,
+    name: &str,
+    value: &str,
+) {
+    output.push(' ');
+    output.push_str(name);
+    output.push_str("=\"");
+    escape(output, value, true);
+    output.push('"');
+}
+
+fn escape(
+    output: &mut String,
+    data: &str,
+    in_attribute: bool,
+) {
+    for c in data.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' if in_attribute => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+}
+
+/// See https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+fn is_void(name: &QualName) -> bool {
+    matches!(
+        name.local,
+        local_name!("area")
+            | local_name!("base")
+            | local_name!("basefont")
+            | local_name!("bgsound")
+            | local_name!("br")
+            | local_name!("col")
+            | local_name!("embed")
+            | local_name!("frame")
+            | local_name!("hr")
+            | local_name!("img")
+            | local_name!("input")
+            | local_name!("keygen")
+            | local_name!("link")
+            | local_name!("meta")
+            | local_name!("param")
+            | local_name!("source")
+            | local_name!("track")
+            | local_name!("wbr")
+    )
+}
@@ -1,3 +1,6 @@
+import v8 from "node:v8";
+import vm from "node:vm";
+
 import tap from "tap";
 
 import {
@@ -9,6 +12,17 @@ import {
     Comment,
     Text,
     DocumentType,
+    CompiledSelector,
+    compileSelector,
+    parseSelector,
+    NodeList,
+    HTMLCollection,
+    NamedNodeMap,
+    DOMStringMap,
+    DOMTokenList,
+    DOMImplementation,
+    DOMParser,
+    XMLSerializer,
 } from "../index.js";
 
 /**
@@ -211,7 +225,7 @@ test("Text node", (t) => {
     let text = dom.window.document.createTextNode("Hello, world");
     t.equal(text.parentElement, null);
     t.equal(text.parentNode, null);
-    t.equal(text.ownerDocument, null);
+    t.equal(text.ownerDocument, document);
 
     let text2 = dom.window.document.body.appendChild(text);
     t.equal(text2, text, "text2 is text");
@@ -1057,6 +1071,25 @@ test("insertAdjacentElement()", (t) => {
             '<div id="foo">Hello, World</div><span></span>',
         );
     });
+
+    t.test("cycles", (tt) => {
+        let { div, document } = createData();
+        const html = document.body.innerHTML;
+
+        tt.throws(() => div.insertAdjacentElement("beforeend", div), {
+            name: "HierarchyRequestError",
+        });
+        tt.throws(
+            () => div.insertAdjacentElement("afterbegin", document.body),
+            { name: "HierarchyRequestError" },
+        );
+        tt.throws(
+            () => div.insertAdjacentElement("afterend", document.body),
+            { name: "HierarchyRequestError" },
+        );
+        tt.equal(document.body.innerHTML, html);
+        tt.equal(div.parentNode, document.body);
+    });
 });
 
 test("insertAdjacentText", (t) => {
@@ -1191,7 +1224,12 @@ test("<template> tag + serialize (in different ways)", (t) => {
         document.getElementById("foo")?.outerHTML,
         '<template id="foo"><div>bar</div></template>',
     );
-    t.equal(document.getElementById("foo")?.textContent, "bar");
+    // the content is not part of the children of the template
+    t.equal(document.getElementById("foo")?.textContent, "");
+    t.equal(
+        document.getElementById("foo")?.content.firstChild.textContent,
+        "bar",
+    );
     t.equal(
         dom.serialize(),
         '<html><head><template id="foo"><div>bar</div></template></head><body></body></html>',
@@ -1211,3 +1249,1683 @@ test(".defaultView in Document", (t) => {
 
     t.equal(document.defaultView, dom.window);
 });
+
+test("compileSelector()", (t) => {
+    let dom = new Html5EverDom(`
+    <div id="foo">
+      <div id="bar" class="baz">First</div>
+    </div>
+    <div class="baz">Second</div>
+  `);
+    let { document } = dom.window;
+
+    const selector = compileSelector("div.baz");
+    t.ok(selector instanceof CompiledSelector);
+    t.equal(selector.selectorText, "div.baz");
+
+    const all = document.querySelectorAll(selector);
+    t.equal(all.length, 2);
+    t.equal(all[0].id, "bar");
+    t.equal(document.querySelector(selector)?.id, "bar");
+    t.equal(document.getElementById("foo")?.querySelectorAll(selector).length, 1);
+
+    t.equal(all[1].matches(selector), true);
+    t.equal(document.getElementById("foo")?.matches(selector), false);
+
+    t.throws(() => compileSelector("div >"));
+
+    // strings keep working and give the same result when repeated (cached)
+    t.equal(document.querySelectorAll("div.baz").length, 2);
+    t.equal(document.querySelectorAll("div.baz").length, 2);
+});
+
+test("parseSelector()", (t) => {
+    const { selectors, error } = parseSelector("#a  .b>p, DIV:hover");
+    t.equal(error, undefined);
+    t.same(selectors, [
+        { text: "#a .b > p", specificity: [1, 1, 1] },
+        { text: "DIV:hover", specificity: [0, 1, 1] },
+    ]);
+
+    const invalid = parseSelector("div,\n  p >");
+    t.same(invalid.selectors, []);
+    t.same(invalid.error, {
+        message: "Combinator is not followed by a selector",
+        line: 2,
+        column: 6,
+    });
+
+    t.throws(() => compileSelector("div >"), {
+        message:
+            "Failed to parse selector: Combinator is not followed by a selector (line 1, column 6)",
+    });
+});
+
+test("indexed lookups follow mutations", (t) => {
+    let dom = new Html5EverDom(`<!DOCTYPE html>
+    <div id="a" class="x">
+      <p class="x y">One</p>
+    </div>
+    <p id="b">Two</p>
+  `);
+    let { document } = dom.window;
+    const a = document.getElementById("a");
+    const b = document.getElementById("b");
+
+    t.equal(document.getElementById(""), null);
+    t.equal(document.querySelectorAll(".x").length, 2);
+    t.equal(a?.querySelectorAll(".x").length, 1);
+    t.same(
+        document.querySelectorAll("p").map((p) => p.textContent),
+        ["One", "Two"],
+    );
+
+    b?.setAttribute("id", "c");
+    t.equal(document.getElementById("b"), null);
+    t.equal(document.getElementById("c"), b);
+    t.equal(document.querySelector("#c"), b);
+
+    b?.classList.add("x");
+    t.equal(document.querySelectorAll(".x").length, 3);
+    b?.removeAttribute("class");
+    t.equal(document.querySelectorAll(".x").length, 2);
+
+    // tree order, not insertion order
+    a?.prepend(b);
+    t.same(
+        document.querySelectorAll("p").map((p) => p.textContent),
+        ["Two", "One"],
+    );
+    t.equal(a?.querySelector("#c"), b);
+
+    a?.remove();
+    t.equal(document.getElementById("a"), null);
+    t.equal(document.getElementById("c"), null);
+    t.equal(document.querySelectorAll("p").length, 0);
+    t.equal(a?.querySelectorAll(".x").length, 1);
+    t.equal(a?.getElementById("c"), b);
+
+    document.body.innerHTML = `<span class="x">Three</span>`;
+    t.equal(document.querySelectorAll("span.x").length, 1);
+    t.equal(document.querySelectorAll(".x").length, 1);
+
+    const div = document.createElement("div");
+    div.id = "d";
+    t.equal(document.getElementById("d"), null);
+    document.body.appendChild(div);
+    t.equal(document.getElementById("d"), div);
+});
+
+test("descendant selectors outside of the document index", (t) => {
+    let { document } = new Html5EverDom().window;
+    const fragment = document.createDocumentFragment(`
+    <section id="s" class="a">
+      <div class="b"><p>One</p></div>
+      <p>Two</p>
+    </section>
+    <div class="b"><p>Three</p></div>
+    <article><p>Four</p><div><span><p>Five</p></span></div></article>
+  `);
+    const text = (elements) => elements.map((e) => e.textContent);
+
+    t.same(text(fragment.querySelectorAll("section p")), ["One", "Two"]);
+    t.same(text(fragment.querySelectorAll(".b p")), ["One", "Three"]);
+    t.same(text(fragment.querySelectorAll("#s > p")), ["Two"]);
+    t.same(text(fragment.querySelectorAll("article div p")), ["Five"]);
+    t.same(text(fragment.querySelectorAll("section div, article > p")), [
+        "One",
+        "Four",
+    ]);
+
+    // ancestors above the element queried from count as well
+    const div = fragment.querySelector("section div");
+    t.same(text(div.querySelectorAll("section p")), ["One"]);
+    t.same(text(div.querySelectorAll(".a > .b > p")), ["One"]);
+    t.same(text(div.querySelectorAll("article p")), []);
+});
+
+test("descendant selectors through the document index", (t) => {
+    // enough other elements for the index to be used for the keys below
+    const filler = "<i></i>".repeat(50);
+    let { document } = new Html5EverDom(`<!DOCTYPE html>
+    <section id="s" class="a">
+      <div class="b"><p class="p">One</p></div>
+      <p class="p">Two</p>
+    </section>
+    <div class="b"><p class="p">Three</p></div>
+    <article><p class="p">Four</p><div><span><p class="p">Five</p></span></div></article>
+    ${filler}
+  `).window;
+    const text = (elements) => elements.map((e) => e.textContent);
+
+    t.same(text(document.querySelectorAll("section .p")), ["One", "Two"]);
+    t.same(text(document.querySelectorAll(".b .p")), ["One", "Three"]);
+    t.same(text(document.querySelectorAll("#s > .p")), ["Two"]);
+    t.same(text(document.querySelectorAll("article div .p")), ["Five"]);
+    t.same(text(document.querySelectorAll("nav .p")), []);
+    t.same(text(document.querySelectorAll(".p")), [
+        "One",
+        "Two",
+        "Three",
+        "Four",
+        "Five",
+    ]);
+
+    const div = document.querySelector("section div");
+    t.same(text(div.querySelectorAll("section .p")), ["One"]);
+    t.same(text(div.querySelectorAll(".a > .b > .p")), ["One"]);
+    t.same(text(div.querySelectorAll("article .p")), []);
+
+    // most elements are spans, which the tree is walked for instead
+    document.body.innerHTML = "<span><span></span></span>".repeat(20);
+    t.equal(document.querySelectorAll("span span").length, 20);
+    t.end();
+});
+
+test("case sensitivity of attribute selectors", (t) => {
+    let { document } = new Html5EverDom(`<!DOCTYPE html>
+    <input type="text" lang="en-US" data-x="abc" title="Hello">
+    <svg viewBox="0 0 1 1"></svg>
+  `).window;
+    const count = (selectors) => document.querySelectorAll(selectors).length;
+
+    // values of the attributes HTML lists as case-insensitive
+    t.equal(count("[type=TEXT]"), 1);
+    t.equal(count("[lang|=EN]"), 1);
+    t.equal(count("[data-x=ABC]"), 0);
+    t.equal(count("[title=hello]"), 0);
+
+    // the `i` and `s` flags
+    t.equal(count("[data-x=ABC i]"), 1);
+    t.equal(count("[title=hello I]"), 1);
+    t.equal(count("[type=TEXT s]"), 0);
+
+    // names, which only for HTML elements are case-insensitive
+    t.equal(count("[TYPE=text]"), 1);
+    t.equal(count("svg[viewBox]"), 1);
+    t.equal(count("svg[viewbox]"), 0);
+
+    const div = document.createElement("div");
+    div.setAttribute("DATA-Y", "1");
+    document.body.appendChild(div);
+    t.equal(count("[data-y]"), 1);
+
+    // in an XML document, names are case-sensitive for HTML elements too
+    const xml = new DOMParser().parseFromString(
+        `<html xmlns="http://www.w3.org/1999/xhtml"><body><DIV Data-X="1"/><div/></body></html>`,
+        "application/xhtml+xml",
+    );
+    const first = xml.querySelector("body").firstChild;
+    t.equal(first.namespaceURI, "http://www.w3.org/1999/xhtml");
+    t.equal(xml.querySelector("DIV"), first);
+    t.equal(xml.querySelectorAll("div").length, 1);
+    t.notOk(first.matches("div"));
+    t.equal(xml.querySelectorAll("[Data-X]").length, 1);
+    t.equal(xml.querySelectorAll("[data-x]").length, 0);
+});
+
+test("ids and classes are case-insensitive in quirks mode", (t) => {
+    const quirks = new Html5EverDom(`<p id="Foo" class="Bar">x</p>`);
+    const noQuirks = new Html5EverDom(
+        `<!DOCTYPE html><p id="Foo" class="Bar">x</p>`,
+    );
+
+    t.equal(quirks.quirksMode, QuirksMode.Quirks);
+    for (const selectors of ["#foo", ".bar", "#FOO.BAR"]) {
+        t.equal(quirks.window.document.querySelectorAll(selectors).length, 1);
+        t.equal(noQuirks.window.document.querySelectorAll(selectors).length, 0);
+    }
+
+    t.ok(quirks.window.document.querySelector("p")?.matches(".bar"));
+    t.notOk(noQuirks.window.document.querySelector("p")?.matches(".bar"));
+
+    // attribute selectors are unaffected
+    t.equal(quirks.window.document.querySelectorAll("[class=bar]").length, 0);
+});
+
+test("before(), after() and replaceWith()", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><a></a><b></b><i></i></div>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const [a, b, i] = parent.children;
+    const html = () => parent.innerHTML;
+
+    b.before("x", a, document.createElement("p"));
+    t.equal(html(), "x<a></a><p></p><b></b><i></i>");
+
+    b.after(i, "y");
+    t.equal(html(), "x<a></a><p></p><b></b><i></i>y");
+
+    // the node itself is one of the arguments
+    b.before(b, a);
+    t.equal(html(), "x<p></p><b></b><a></a><i></i>y");
+    b.after(a, b);
+    t.equal(html(), "x<p></p><a></a><b></b><i></i>y");
+
+    b.replaceWith("z", b, i);
+    t.equal(html(), "x<p></p><a></a>z<b></b><i></i>y");
+    parent.firstChild.replaceWith();
+    t.equal(html(), "<p></p><a></a>z<b></b><i></i>y");
+    a.replaceWith(i);
+    t.equal(html(), "<p></p><i></i>z<b></b>y");
+    t.equal(a.parentNode, null);
+
+    // no-ops without a parent
+    a.before(b);
+    a.after(b);
+    a.replaceWith(b);
+    t.equal(html(), "<p></p><i></i>z<b></b>y");
+});
+
+test("replaceChild() and replaceChildren()", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><a></a><b></b><i></i></div>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const [a, b, i] = parent.children;
+    const html = () => parent.innerHTML;
+
+    const p = document.createElement("p");
+    t.equal(parent.replaceChild(p, b), b);
+    t.equal(html(), "<a></a><p></p><i></i>");
+    t.equal(b.parentNode, null);
+
+    // replacing with the next sibling, and with itself
+    parent.replaceChild(i, p);
+    t.equal(html(), "<a></a><i></i>");
+    parent.replaceChild(a, a);
+    t.equal(html(), "<a></a><i></i>");
+
+    t.throws(() => parent.replaceChild(p, b));
+
+    parent.replaceChildren(i, "text", b);
+    t.equal(html(), "<i></i>text<b></b>");
+    t.equal(a.parentNode, null);
+
+    parent.replaceChildren();
+    t.equal(html(), "");
+
+    document.body.replaceChildren(a);
+    t.equal(document.body.innerHTML, "<a></a>");
+});
+
+test("insertBefore() with a node before the reference node", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><a></a><b></b><i></i></div>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const [a, b, i] = parent.children;
+
+    parent.insertBefore(a, i);
+    t.equal(parent.innerHTML, "<b></b><a></a><i></i>");
+    parent.insertBefore(i, i);
+    t.equal(parent.innerHTML, "<b></b><a></a><i></i>");
+});
+
+test("append() and prepend() with several nodes and fragments", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><b></b></div><!--c-->`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const html = () => parent.innerHTML;
+
+    const fragment = document.createDocumentFragment();
+    fragment.append("x", document.createElement("i"));
+    const comment = parent.nextSibling;
+
+    parent.append(fragment, "y", comment);
+    t.equal(html(), "<b></b>x<i></i>y<!--c-->");
+    t.equal(fragment.childNodes.length, 0);
+
+    parent.prepend("z", comment, parent.firstChild);
+    t.equal(html(), "z<!--c--><b></b>x<i></i>y");
+
+    parent.append(comment, parent.firstChild, comment);
+    t.equal(html(), "<b></b>x<i></i>yz<!--c-->");
+
+    parent.append();
+    parent.prepend();
+    t.equal(html(), "<b></b>x<i></i>yz<!--c-->");
+
+    t.throws(() => parent.append(document));
+});
+
+test("appendChild() and insertBefore() with a fragment", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><b></b></div>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const b = parent.firstChild;
+
+    const fragment = document.createDocumentFragment();
+    fragment.append(document.createElement("i"), "x");
+    t.equal(parent.appendChild(fragment), fragment);
+    t.equal(parent.innerHTML, "<b></b><i></i>x");
+    t.equal(fragment.firstChild, null);
+
+    fragment.append(document.createElement("p"), "y");
+    t.equal(parent.insertBefore(fragment, b), fragment);
+    t.equal(parent.innerHTML, "<p></p>y<b></b><i></i>x");
+    t.equal(fragment.firstChild, null);
+
+    parent.appendChild(fragment);
+    t.equal(parent.innerHTML, "<p></p>y<b></b><i></i>x");
+});
+
+test("hierarchy errors", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><b><i></i></b></div><p></p>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const b = parent.firstChild;
+    const i = b.firstChild;
+    const p = document.querySelector("p");
+
+    const throwsDomException = (fn, name, code) => {
+        try {
+            fn();
+            t.fail(`expected a ${name}`);
+        } catch (err) {
+            t.ok(err instanceof Error);
+            t.equal(err.name, name);
+            t.equal(err.code, code);
+        }
+    };
+    const hierarchyRequestError = (fn) =>
+        throwsDomException(fn, "HierarchyRequestError", 3);
+    const notFoundError = (fn) => throwsDomException(fn, "NotFoundError", 8);
+
+    // cycles
+    hierarchyRequestError(() => i.appendChild(parent));
+    hierarchyRequestError(() => b.appendChild(b));
+    hierarchyRequestError(() => i.append(b));
+    hierarchyRequestError(() => i.before(parent));
+    hierarchyRequestError(() => b.replaceChild(parent, i));
+    const fragment = document.createDocumentFragment();
+    hierarchyRequestError(() => fragment.appendChild(fragment));
+    t.equal(parent.outerHTML, '<div id="parent"><b><i></i></b></div>');
+
+    // foreign reference nodes
+    notFoundError(() => parent.insertBefore(document.createElement("a"), i));
+    notFoundError(() => parent.replaceChild(document.createElement("a"), p));
+    notFoundError(() => parent.removeChild(p));
+    t.equal(document.querySelector("p"), p);
+
+    // documents
+    hierarchyRequestError(() => parent.appendChild(document));
+    hierarchyRequestError(() =>
+        document.appendChild(document.createElement("html")),
+    );
+    hierarchyRequestError(() => document.append("text"));
+    hierarchyRequestError(() => document.appendChild(document.doctype));
+    hierarchyRequestError(() => parent.appendChild(document.doctype));
+    hierarchyRequestError(() =>
+        document.insertBefore(document.doctype, document.documentElement),
+    );
+    hierarchyRequestError(() =>
+        document.replaceChild(document.createElement("html"), document.doctype),
+    );
+    t.equal(document.childNodes.length, 2);
+
+    // valid replacements
+    const html = document.createElement("html");
+    document.replaceChild(html, document.documentElement);
+    t.equal(document.documentElement, html);
+    const doctype = document.doctype;
+    doctype.remove();
+    document.prepend(doctype);
+    t.equal(document.firstChild, doctype);
+    hierarchyRequestError(() => document.documentElement.before(doctype));
+});
+
+test("contains(), isSameNode() and isEqualNode()", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" class="x"><b>text</b><!--c--></div>` +
+            `<div class="x" id="a"><b>text</b><!--c--></div>`,
+    ).window;
+    const [first, second] = document.querySelectorAll("div");
+    const b = first.firstChild;
+
+    t.equal(first.contains(first), true);
+    t.equal(first.contains(b.firstChild), true);
+    t.equal(document.contains(b), true);
+    t.equal(b.contains(first), false);
+    t.equal(first.contains(second), false);
+    t.equal(first.contains(null), false);
+
+    t.equal(first.isSameNode(first), true);
+    t.equal(first.isSameNode(second), false);
+    t.equal(first.isSameNode(null), false);
+
+    t.equal(first.isEqualNode(second), true);
+    t.equal(first.isEqualNode(first.cloneNode(true)), true);
+    t.equal(first.isEqualNode(first.cloneNode(false)), false);
+    t.equal(first.isEqualNode(null), false);
+    t.equal(document.doctype.isEqualNode(document.doctype.cloneNode()), true);
+
+    second.setAttribute("id", "b");
+    t.equal(first.isEqualNode(second), false);
+    second.setAttribute("id", "a");
+    second.lastChild.remove();
+    t.equal(first.isEqualNode(second), false);
+    second.append("text");
+    t.equal(first.isEqualNode(second), false);
+    t.equal(b.firstChild.isEqualNode(second.lastChild), true);
+});
+
+test("compareDocumentPosition()", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div><b><i></i></b><p></p></div>`,
+    ).window;
+    const div = document.querySelector("div");
+    const [b, i, p] = div.querySelectorAll("b, i, p");
+
+    t.equal(div.DOCUMENT_POSITION_DISCONNECTED, 1);
+    t.equal(div.DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC, 32);
+
+    t.equal(i.compareDocumentPosition(i), 0);
+    t.equal(i.compareDocumentPosition(p), i.DOCUMENT_POSITION_FOLLOWING);
+    t.equal(p.compareDocumentPosition(i), p.DOCUMENT_POSITION_PRECEDING);
+    t.equal(
+        i.compareDocumentPosition(div),
+        i.DOCUMENT_POSITION_CONTAINS | i.DOCUMENT_POSITION_PRECEDING,
+    );
+    t.equal(
+        document.compareDocumentPosition(b),
+        b.DOCUMENT_POSITION_CONTAINED_BY | b.DOCUMENT_POSITION_FOLLOWING,
+    );
+
+    const detached = document.createElement("a");
+    const position = detached.compareDocumentPosition(b);
+    const order = b.DOCUMENT_POSITION_PRECEDING | b.DOCUMENT_POSITION_FOLLOWING;
+    t.equal(
+        position & ~order,
+        b.DOCUMENT_POSITION_DISCONNECTED |
+            b.DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC,
+    );
+    t.equal(b.compareDocumentPosition(detached) ^ position, order);
+});
+
+test("ownerDocument, isConnected and getRootNode() of detached nodes", (t) => {
+    let { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="parent"><b><i></i></b></div>`,
+    ).window;
+    const parent = document.getElementById("parent");
+    const b = parent.firstChild;
+    const i = b.firstChild;
+
+    t.equal(document.ownerDocument, null);
+    t.equal(document.isConnected, true);
+    t.equal(document.getRootNode(), document);
+    t.equal(i.ownerDocument, document);
+    t.equal(i.isConnected, true);
+    t.equal(i.getRootNode(), document);
+
+    b.remove();
+    t.equal(b.ownerDocument, document);
+    t.equal(i.ownerDocument, document);
+    t.equal(i.isConnected, false);
+    t.equal(i.getRootNode(), b);
+    t.equal(b.getRootNode(), b);
+
+    const div = document.createElement("div");
+    t.equal(div.ownerDocument, document);
+    t.equal(div.isConnected, false);
+    div.append("text", b);
+    t.equal(div.lastChild.firstChild.ownerDocument, document);
+    t.equal(div.firstChild.ownerDocument, document);
+    t.equal(div.cloneNode(true).lastChild.ownerDocument, document);
+
+    div.innerHTML = "<p>parsed</p><template><a></a></template>";
+    t.equal(div.firstChild.ownerDocument, document);
+    t.equal(div.firstChild.getRootNode(), div);
+
+    const fragment = document.createDocumentFragment();
+    fragment.append(div);
+    t.equal(div.getRootNode(), fragment);
+    t.equal(fragment.ownerDocument, document);
+    t.equal(fragment.isConnected, false);
+    document.body.append(fragment);
+    t.equal(div.isConnected, true);
+    t.equal(div.getRootNode(), document);
+});
+
+tap.test("detached nodes keep their node document alive", async (t) => {
+    v8.setFlagsFromString("--expose-gc");
+    const gc = vm.runInNewContext("gc");
+
+    // nothing but the nodes refers to the document once this returns
+    const create = () => {
+        const { document } = new Html5EverDom(
+            `<!DOCTYPE html><p></p><template><b></b></template>`,
+        ).window;
+        const removed = document.querySelector("p");
+        removed.remove();
+        const { content } = document.querySelector("template");
+
+        return { created: document.createElement("div"), removed, content };
+    };
+    const { created, removed, content } = create();
+
+    for (let i = 0; i < 5; i++) {
+        gc();
+        await new Promise((resolve) => setTimeout(resolve, 0));
+    }
+
+    t.equal(created.ownerDocument?.nodeType, 9);
+    t.equal(removed.ownerDocument, created.ownerDocument);
+    t.equal(content.ownerDocument, created.ownerDocument);
+    t.equal(
+        created.ownerDocument.body.innerHTML,
+        "<template><b></b></template>",
+    );
+});
+
+test("quirks mode of detached elements", (t) => {
+    let { document } = new Html5EverDom(`<div></div>`).window;
+    const div = document.createElement("div");
+    div.innerHTML = `<p id="Foo" class="Bar"></p>`;
+
+    t.ok(div.querySelector("#foo"));
+    t.ok(div.querySelector(".bar"));
+});
+
+test("importNode() and adoptNode()", (t) => {
+    const source = new Html5EverDom(
+        `<!DOCTYPE html><div id="a"><p class="Foo">text</p></div>` +
+            `<template><i class="Foo"></i></template>`,
+    ).window.document;
+    const { document } = new Html5EverDom(`<div></div>`).window;
+    const div = source.getElementById("a");
+
+    const shallow = document.importNode(div);
+    t.equal(shallow.ownerDocument, document);
+    t.equal(shallow.outerHTML, '<div id="a"></div>');
+
+    const deep = document.importNode(div, true);
+    t.equal(deep.ownerDocument, document);
+    t.equal(deep.firstChild.ownerDocument, document);
+    t.equal(deep.outerHTML, div.outerHTML);
+    t.equal(div.parentNode, source.body);
+    // the target document is in quirks mode
+    t.ok(deep.querySelector(".foo"));
+    t.notOk(div.querySelector(".foo"));
+
+    const fragment = source.createDocumentFragment();
+    fragment.append("x", source.createElement("b"));
+    const importedFragment = document.importNode(fragment, true);
+    t.equal(importedFragment.ownerDocument, document);
+    t.equal(importedFragment.childNodes.length, 2);
+    t.equal(fragment.childNodes.length, 2);
+
+    const adopted = document.adoptNode(div);
+    t.equal(adopted, div);
+    t.equal(div.parentNode, null);
+    t.equal(source.getElementById("a"), null);
+    t.equal(div.ownerDocument, document);
+    t.ok(div.querySelector(".foo"));
+    document.body.append(div);
+    t.equal(document.getElementById("a"), div);
+
+    const template = source.querySelector("template");
+    document.adoptNode(template);
+    t.equal(template.ownerDocument, document);
+    t.equal(template.innerHTML, '<i class="Foo"></i>');
+
+    for (const method of ["importNode", "adoptNode"]) {
+        try {
+            document[method](source);
+            t.fail("expected a NotSupportedError");
+        } catch (err) {
+            t.equal(err.name, "NotSupportedError");
+            t.equal(err.code, 9);
+        }
+    }
+});
+
+test("cloneNode() of templates, fragments and documents", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><template id="t"><b class="x">content</b></template>` +
+            `<p id="a">text</p>`,
+    ).window;
+    const template = document.getElementById("t");
+
+    const shallowTemplate = template.cloneNode();
+    t.equal(shallowTemplate.innerHTML, "");
+    const deepTemplate = template.cloneNode(true);
+    t.equal(deepTemplate.outerHTML, template.outerHTML);
+    t.equal(deepTemplate.ownerDocument, document);
+
+    const fragment = document.createDocumentFragment();
+    fragment.append("x", document.createElement("b"));
+    t.equal(fragment.cloneNode().childNodes.length, 0);
+    const fragmentClone = fragment.cloneNode(true);
+    t.equal(fragmentClone.childNodes.length, 2);
+    t.equal(fragmentClone.ownerDocument, document);
+    t.ok(fragmentClone.isEqualNode(fragment));
+
+    const shallowDocument = document.cloneNode();
+    t.equal(shallowDocument.childNodes.length, 0);
+    t.equal(shallowDocument.defaultView, null);
+
+    const clone = document.cloneNode(true);
+    t.ok(clone.isEqualNode(document));
+    t.equal(clone.doctype.name, "html");
+    t.equal(clone.doctype.ownerDocument, clone);
+    t.equal(clone.getElementById("a").textContent, "text");
+    t.equal(document.getElementById("a").ownerDocument, document);
+    const clonedTemplate = clone.getElementById("t");
+    t.equal(clonedTemplate.innerHTML, '<b class="x">content</b>');
+
+    clone.body.innerHTML = "";
+    t.equal(document.getElementById("a").textContent, "text");
+});
+
+test("template content", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><template id="t"><b class="x">content</b></template>`,
+    ).window;
+    const template = document.getElementById("t");
+    const { content } = template;
+
+    t.equal(content.nodeType, content.DOCUMENT_FRAGMENT_NODE);
+    t.equal(template.content, content);
+    t.equal(content.ownerDocument, document);
+    t.equal(content.firstChild.outerHTML, '<b class="x">content</b>');
+    t.equal(document.createElement("div").content, null);
+
+    // kept apart from the children of the template
+    t.equal(template.childNodes.length, 0);
+    t.equal(template.firstChild, null);
+    t.equal(template.textContent, "");
+    t.equal(template.querySelector("b"), null);
+    t.equal(document.querySelector(".x"), null);
+    t.equal(document.getElementsByClassName("x").length, 0);
+    t.equal(content.querySelector(".x"), content.firstChild);
+
+    template.innerHTML = "<tr><td>cell</td></tr>";
+    t.equal(template.childNodes.length, 0);
+    t.equal(content.firstChild.nodeName, "TR");
+    t.equal(template.innerHTML, "<tr><td>cell</td></tr>");
+    t.equal(
+        template.outerHTML,
+        '<template id="t"><tr><td>cell</td></tr></template>',
+    );
+
+    content.append(document.createElement("i"));
+    t.equal(template.innerHTML, "<tr><td>cell</td></tr><i></i>");
+    template.innerHTML = "";
+    t.equal(content.childNodes.length, 0);
+
+    const created = document.createElement("template");
+    created.innerHTML = "<p>x</p>";
+    t.equal(created.content.firstChild.ownerDocument, document);
+    t.equal(created.childNodes.length, 0);
+});
+
+test("live NodeList and HTMLCollection", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="root"><p class="a">1</p>text<p name="second" class="a b">2</p></div>`,
+    ).window;
+    const root = document.getElementById("root");
+    const { childNodes, children } = root;
+    const paragraphs = document.getElementsByTagName("p");
+    const as = document.getElementsByClassName("a");
+
+    t.ok(childNodes instanceof NodeList);
+    t.ok(children instanceof HTMLCollection);
+    t.ok(paragraphs instanceof HTMLCollection);
+    t.throws(() => new NodeList());
+
+    t.equal(childNodes.length, 3);
+    t.equal(childNodes[1].textContent, "text");
+    t.equal(childNodes.item(1), childNodes[1]);
+    t.equal(childNodes[3], undefined);
+    t.equal(childNodes.item(3), null);
+    t.ok(2 in childNodes);
+    t.notOk(3 in childNodes);
+    t.equal(children.length, 2);
+    t.equal(children[1].textContent, "2");
+    t.equal(paragraphs.length, 2);
+    t.equal(as.length, 2);
+
+    t.equal(children.namedItem("second"), children[1]);
+    t.equal(paragraphs.namedItem("root"), null);
+    t.equal(document.body.children.namedItem("root"), root);
+    t.equal(children.namedItem(""), null);
+
+    // reflect changes to the tree
+    root.append(document.createElement("p"));
+    root.firstChild.remove();
+    t.equal(childNodes.length, 3);
+    t.equal(childNodes[0].textContent, "text");
+    t.equal(children.length, 2);
+    t.equal(paragraphs.length, 2);
+    t.equal(as.length, 1);
+    root.lastChild.className = "a";
+    t.equal(as.length, 2);
+    root.lastChild.removeAttribute("class");
+    t.equal(as.length, 1);
+    const detached = document.createElement("div");
+    const detachedParagraphs = detached.getElementsByTagName("p");
+    t.equal(detachedParagraphs.length, 0);
+    detached.innerHTML = "<p></p><p></p>";
+    t.equal(detachedParagraphs.length, 2);
+    t.equal(detachedParagraphs[1], detached.lastChild);
+    t.equal(paragraphs.item, paragraphs.item);
+
+    // iteration
+    t.same(
+        [...childNodes].map((node) => node.nodeName),
+        ["#text", "P", "P"],
+    );
+    t.same(
+        Array.from(paragraphs, (p) => p.textContent),
+        ["2", ""],
+    );
+    const names = [];
+    childNodes.forEach((node, index) => names.push([index, node.nodeName]));
+    t.same(names, [
+        [0, "#text"],
+        [1, "P"],
+        [2, "P"],
+    ]);
+    t.same([...childNodes.keys()], [0, 1, 2]);
+    t.equal(Array.prototype.indexOf.call(children, root.lastChild), 1);
+});
+
+test("attributes NamedNodeMap", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" class="x" title="t"></div><p></p>`,
+    ).window;
+    const div = document.getElementById("a");
+    const p = document.querySelector("p");
+    const { attributes } = div;
+
+    t.ok(attributes instanceof NamedNodeMap);
+    t.equal(attributes.length, 3);
+    t.equal(attributes[1].name, "class");
+    t.equal(attributes.item(2).value, "t");
+    t.equal(attributes[3], undefined);
+    t.same(
+        [...attributes].map((attr) => attr.name),
+        ["id", "class", "title"],
+    );
+
+    const title = attributes.getNamedItem("TITLE");
+    t.equal(title.ownerElement, div);
+    t.equal(attributes.getNamedItemNS(null, "title").value, "t");
+    t.equal(attributes.getNamedItem("missing"), null);
+
+    // values are written through to the element
+    title.value = "changed";
+    t.equal(div.getAttribute("title"), "changed");
+    t.equal(attributes[2].name, "title", "keeps its position");
+    div.setAttribute("title", "again");
+    t.equal(title.value, "again");
+    attributes.getNamedItem("class").value = "y z";
+    t.equal(div.classList.contains("z"), true);
+    div.setAttribute("data-new", "1");
+    t.equal(attributes.length, 4);
+
+    // removed attributes keep their value
+    const removed = attributes.removeNamedItem("title");
+    t.equal(removed.value, "again");
+    t.equal(removed.ownerElement, null);
+    t.equal(div.hasAttribute("title"), false);
+    t.throws(() => attributes.removeNamedItem("title"), {
+        name: "NotFoundError",
+    });
+
+    // and can be put on another element
+    t.equal(p.attributes.setNamedItem(removed), null);
+    t.equal(p.getAttribute("title"), "again");
+    t.equal(removed.ownerElement, p);
+    removed.value = "on p";
+    t.equal(p.getAttribute("title"), "on p");
+    t.throws(() => attributes.setNamedItem(removed), {
+        name: "InUseAttributeError",
+    });
+
+    const id = p.attributes.setNamedItem(attributes.removeNamedItem("id"));
+    t.equal(id, null);
+    const replaced = div.attributes.setNamedItem(
+        p.attributes.removeNamedItem("title"),
+    );
+    t.equal(replaced, null);
+    t.equal(div.getAttribute("title"), "on p");
+    t.equal(p.id, "a");
+});
+
+test("an attribute is the same Attr every time", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" title="t"></div><p title="p"></p>`,
+    ).window;
+    const div = document.getElementById("a");
+    const p = document.querySelector("p");
+    const id = div.attributes[0];
+
+    t.equal(div.attributes[0], id);
+    t.equal(div.attributes.item(0), id);
+    t.equal(div.attributes.getNamedItem("id"), id);
+    t.equal(div.attributes.getNamedItemNS(null, "id"), id);
+    t.equal(div.attributes.removeNamedItem("id"), id);
+    t.equal(id.ownerElement, null);
+
+    // a removed attribute keeps its last value, and coming back is a new one
+    const title = div.attributes.getNamedItem("title");
+    div.setAttribute("title", "changed");
+    div.removeAttribute("title");
+    t.equal(title.value, "changed");
+    t.equal(title.ownerElement, null);
+    div.setAttribute("title", "back");
+    t.not(div.attributes.getNamedItem("title"), title);
+
+    // setting an attribute returns the Attr it replaces
+    const replaced = p.attributes.getNamedItem("title");
+    t.equal(p.attributes.setNamedItem(title), replaced);
+    t.equal(replaced.ownerElement, null);
+    t.equal(replaced.value, "p");
+    t.equal(p.attributes.getNamedItem("title"), title);
+    t.equal(p.getAttribute("title"), "changed");
+    t.equal(p.attributes.setNamedItem(title), title);
+    t.throws(() => div.attributes.setNamedItem(title), {
+        name: "InUseAttributeError",
+    });
+});
+
+test("toggleAttribute(), getAttributeNames() and hasAttributes()", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" title="t"></div>`,
+    ).window;
+    const div = document.getElementById("a");
+
+    t.same(div.getAttributeNames(), ["id", "title"]);
+    t.equal(div.hasAttributes(), true);
+    t.equal(document.createElement("p").hasAttributes(), false);
+
+    t.equal(div.toggleAttribute("hidden"), true);
+    t.equal(div.getAttribute("hidden"), "");
+    t.equal(div.toggleAttribute("HIDDEN"), false);
+    t.equal(div.hasAttribute("hidden"), false);
+    t.equal(div.toggleAttribute("hidden", false), false);
+    t.equal(div.hasAttribute("hidden"), false);
+    t.equal(div.toggleAttribute("hidden", true), true);
+    t.equal(div.toggleAttribute("hidden", true), true);
+    t.same(div.getAttributeNames(), ["id", "title", "hidden"]);
+});
+
+test("dataset", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" data-foo-bar="1" data-x="2" title="t"></div>`,
+    ).window;
+    const div = document.getElementById("a");
+    const { dataset } = div;
+
+    t.ok(dataset instanceof DOMStringMap);
+    t.equal(dataset.fooBar, "1");
+    t.equal(dataset.x, "2");
+    t.equal(dataset.title, undefined);
+    t.equal(dataset["foo-bar"], undefined);
+    t.ok("fooBar" in dataset);
+    t.notOk("foo-bar" in dataset);
+    t.same(Object.keys(dataset), ["fooBar", "x"]);
+    t.same({ ...dataset }, { fooBar: "1", x: "2" });
+
+    dataset.someValue = 3;
+    t.equal(div.getAttribute("data-some-value"), "3");
+    div.setAttribute("data-later", "4");
+    t.equal(dataset.later, "4");
+
+    delete dataset.x;
+    t.equal(div.hasAttribute("data-x"), false);
+    t.same(Object.keys(dataset), ["fooBar", "someValue", "later"]);
+
+    t.throws(
+        () => {
+            dataset["no-dashes"] = "";
+        },
+        { name: "SyntaxError" },
+    );
+});
+
+test("attribute names are validated and lowercased", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a"></div><svg viewBox="0 0 1 1"></svg>`,
+    ).window;
+    const div = document.getElementById("a");
+
+    div.setAttribute("DATA-X", "1");
+    t.equal(div.getAttribute("data-x"), "1");
+    t.equal(div.getAttribute("Data-X"), "1");
+    t.equal(div.hasAttribute("data-X"), true);
+    t.same(div.getAttributeNames(), ["id", "data-x"]);
+    t.equal(div.attributes.getNamedItem("DATA-x").value, "1");
+
+    div.removeAttribute("DATA-X");
+    t.equal(div.hasAttribute("data-x"), false);
+
+    div.setAttribute("CLASS", "foo");
+    t.equal(div.className, "foo");
+    t.ok(div.classList.contains("foo"));
+
+    for (const name of ["", "a b", "a=b", "a/b", "a>b", "\0"]) {
+        t.throws(() => div.setAttribute(name, ""), {
+            name: "InvalidCharacterError",
+            code: 5,
+        });
+        t.throws(() => div.toggleAttribute(name), {
+            name: "InvalidCharacterError",
+        });
+    }
+    t.same(div.getAttributeNames(), ["id", "class"]);
+
+    const svg = document.querySelector("svg");
+    t.equal(svg.getAttribute("viewBox"), "0 0 1 1");
+    t.equal(svg.getAttribute("viewbox"), null);
+    svg.setAttribute("preserveAspectRatio", "none");
+    t.same(svg.getAttributeNames(), ["viewBox", "preserveAspectRatio"]);
+
+    // HTML elements in an XML document keep the case of attribute names
+    const xml = document.implementation.createDocument(
+        "http://www.w3.org/1999/xhtml",
+        "html",
+        null,
+    );
+    const html = xml.documentElement;
+    html.setAttribute("viewBox", "0 0 1 1");
+    t.equal(html.getAttribute("viewBox"), "0 0 1 1");
+    t.equal(html.getAttribute("viewbox"), null);
+    t.same(html.getAttributeNames(), ["viewBox"]);
+});
+
+test("DOMTokenList for relList, sandbox and part", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><a rel="noopener  author"></a><link rel="stylesheet"><iframe sandbox="allow-scripts"></iframe><div part="a b"></div>`,
+    ).window;
+    const a = document.querySelector("a");
+    const link = document.querySelector("link");
+    const iframe = document.querySelector("iframe");
+    const div = document.querySelector("div");
+
+    t.ok(a.relList instanceof DOMTokenList);
+    t.ok(div.classList instanceof DOMTokenList);
+    t.equal(a.relList.length, 2);
+    t.equal(a.relList[1], "author");
+    t.equal(a.relList.contains("noopener"), true);
+    a.relList.add("noreferrer");
+    t.equal(a.getAttribute("rel"), "noopener author noreferrer");
+    a.setAttribute("rel", "opener");
+    t.equal(a.relList.length, 1);
+    t.equal(a.relList.contains("opener"), true);
+
+    t.equal(a.relList.supports("NoReferrer"), true);
+    t.equal(a.relList.supports("stylesheet"), false);
+    t.equal(link.relList.supports("stylesheet"), true);
+    t.equal(document.createElement("form").relList.supports("opener"), true);
+    t.equal(document.createElement("area").relList.length, 0);
+    t.equal(div.relList, null);
+
+    t.equal(iframe.sandbox.contains("allow-scripts"), true);
+    iframe.sandbox.toggle("allow-forms");
+    t.equal(iframe.getAttribute("sandbox"), "allow-scripts allow-forms");
+    t.equal(iframe.sandbox.supports("allow-popups"), true);
+    t.equal(iframe.sandbox.supports("allow-everything"), false);
+    t.equal(div.sandbox, null);
+
+    t.same([div.part[0], div.part[1]], ["a", "b"]);
+    div.part.remove("a");
+    t.equal(div.getAttribute("part"), "b");
+    div.removeAttribute("part");
+    t.equal(div.part.length, 0);
+    t.throws(() => div.part.supports("a"), TypeError);
+    t.throws(() => div.classList.supports("a"), TypeError);
+
+    t.throws(() => new DOMTokenList(), /Illegal constructor/);
+});
+
+// ported from the DOMTokenList tests of web-platform-tests
+test("DOMTokenList semantics", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><div id="a" class="  a  b "></div>`,
+    ).window;
+    const div = document.getElementById("a");
+    const { classList } = div;
+
+    t.test("value round-trips until mutated", (t) => {
+        t.equal(classList.value, "  a  b ");
+        t.equal(classList.toString(), "  a  b ");
+        classList.value = " c\td ";
+        t.equal(div.getAttribute("class"), " c\td ");
+        t.equal(classList.length, 2);
+        classList.add("c");
+        t.equal(classList.value, "c d");
+        classList.value = "  a  b ";
+        t.end();
+    });
+
+    t.test("replace()", (t) => {
+        const p = document.createElement("p");
+        p.className = "a b c";
+        t.equal(p.classList.replace("b", "d"), true);
+        t.equal(p.className, "a d c");
+        t.equal(p.classList.replace("x", "y"), false);
+        t.equal(p.className, "a d c");
+        t.equal(p.classList.replace("a", "c"), true);
+        t.equal(p.className, "c d");
+        t.equal(p.classList.replace("d", "c"), true);
+        t.equal(p.className, "c");
+        t.equal(p.classList.replace("c", "c"), true);
+        t.equal(p.className, "c");
+
+        p.className = " a  a ";
+        t.equal(p.classList.replace("x", "y"), false);
+        t.equal(p.className, " a  a ");
+
+        t.throws(() => p.classList.replace("", "a"), { name: "SyntaxError" });
+        t.throws(() => p.classList.replace("a", ""), { name: "SyntaxError" });
+        t.throws(() => p.classList.replace("a", "b c"), {
+            name: "InvalidCharacterError",
+            code: 5,
+        });
+        t.throws(() => p.classList.replace("a b", "c"), {
+            name: "InvalidCharacterError",
+        });
+        t.end();
+    });
+
+    t.test("errors", (t) => {
+        const p = document.createElement("p");
+        t.throws(() => p.classList.add("a", ""), {
+            name: "SyntaxError",
+            code: 12,
+        });
+        t.throws(() => p.classList.remove("a\tb"), {
+            name: "InvalidCharacterError",
+        });
+        t.throws(() => p.classList.toggle(""), { name: "SyntaxError" });
+        t.throws(() => p.classList.toggle(" "), {
+            name: "InvalidCharacterError",
+        });
+        t.equal(p.hasAttribute("class"), false);
+        t.throws(() => p.classList.supports("a"), TypeError);
+        t.end();
+    });
+
+    t.test("no attribute is added without tokens", (t) => {
+        const p = document.createElement("p");
+        p.classList.remove("a");
+        p.classList.add();
+        t.equal(p.classList.toggle("a", false), false);
+        t.equal(p.hasAttribute("class"), false);
+
+        p.className = " a  b ";
+        p.classList.add();
+        t.equal(p.className, "a b");
+        t.end();
+    });
+
+    t.test("toggle() with force", (t) => {
+        const p = document.createElement("p");
+        p.className = "a  a";
+        t.equal(p.classList.toggle("a", true), true);
+        t.equal(p.className, "a  a");
+        t.equal(p.classList.toggle("b", false), false);
+        t.equal(p.className, "a  a");
+        t.end();
+    });
+
+    t.test("iteration", (t) => {
+        const p = document.createElement("p");
+        p.className = "a b c";
+        t.same([...p.classList], ["a", "b", "c"]);
+        t.same([...p.classList.keys()], [0, 1, 2]);
+        t.same([...p.classList.values()], ["a", "b", "c"]);
+        t.same(
+            [...p.classList.entries()],
+            [
+                [0, "a"],
+                [1, "b"],
+                [2, "c"],
+            ],
+        );
+
+        const seen = [];
+        p.classList.forEach(function (value, key, list) {
+            seen.push([value, key, list === p.classList, this]);
+        }, "this");
+        t.same(seen, [
+            ["a", 0, true, "this"],
+            ["b", 1, true, "this"],
+            ["c", 2, true, "this"],
+        ]);
+        t.end();
+    });
+
+    t.end();
+});
+
+test("HTML element interfaces", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><form id="f">` +
+            `<input id="i" maxlength="5" size="3" value="x">` +
+            `<input type="image">` +
+            `<select id="s"><option>a<option value="b" selected>B</select>` +
+            `<textarea>t</textarea>` +
+            `</form>` +
+            `<a id="a" href="https://u:p@example.com:8080/p?q#h">link</a>` +
+            `<img src="https://example.org/a.png" crossorigin>` +
+            `<table><tr><td colspan="5000"></td></tr></table>` +
+            `<foo-bar></foo-bar><blink></blink>`,
+    ).window;
+    const interfaceOf = (element) =>
+        Object.getPrototypeOf(element).constructor.name;
+
+    t.test("prototypes", (t) => {
+        const input = document.getElementById("i");
+        t.equal(interfaceOf(input), "HTMLInputElement");
+        t.ok(input instanceof Element);
+        t.ok(input instanceof Object.getPrototypeOf(input).constructor);
+        t.equal(
+            interfaceOf(document.querySelector("td")),
+            "HTMLTableCellElement",
+        );
+        t.equal(interfaceOf(document.querySelector("foo-bar")), "HTMLElement");
+        t.equal(
+            interfaceOf(document.querySelector("blink")),
+            "HTMLUnknownElement",
+        );
+        t.equal(
+            interfaceOf(document.createElement("INPUT")),
+            "HTMLInputElement",
+        );
+        t.equal(interfaceOf(input.cloneNode()), "HTMLInputElement");
+
+        const HTMLInputElement = Object.getPrototypeOf(input).constructor;
+        const HTMLElement = Object.getPrototypeOf(HTMLInputElement);
+        t.equal(HTMLElement.name, "HTMLElement");
+        t.equal(Object.getPrototypeOf(HTMLElement), Element);
+        t.throws(() => new HTMLInputElement(), /Illegal constructor/);
+        t.end();
+    });
+
+    t.test("string, enumerated and number reflections", (t) => {
+        const input = document.getElementById("i");
+        t.equal(input.value, "x");
+        t.equal(input.type, "text");
+        input.type = "CHECKBOX";
+        t.equal(input.type, "checkbox");
+        input.type = "bogus";
+        t.equal(input.type, "text");
+        t.equal(input.getAttribute("type"), "bogus");
+
+        t.equal(input.maxLength, 5);
+        t.equal(input.minLength, -1);
+        t.equal(input.size, 3);
+        t.throws(() => (input.size = 0), { name: "IndexSizeError", code: 1 });
+        t.throws(() => (input.maxLength = -1), { name: "IndexSizeError" });
+        input.maxLength = 10;
+        t.equal(input.getAttribute("maxlength"), "10");
+
+        t.equal(input.hidden, false);
+        input.hidden = true;
+        t.equal(input.getAttribute("hidden"), "");
+        input.hidden = false;
+        t.equal(input.hasAttribute("hidden"), false);
+
+        t.equal(document.querySelector("td").colSpan, 1000);
+        document.querySelector("td").colSpan = 0;
+        t.equal(document.querySelector("td").colSpan, 1);
+        t.end();
+    });
+
+    t.test("URLs", (t) => {
+        const a = document.getElementById("a");
+        t.equal(a.href, "https://u:p@example.com:8080/p?q#h");
+        t.equal(a.protocol, "https:");
+        t.equal(a.username, "u");
+        t.equal(a.password, "p");
+        t.equal(a.host, "example.com:8080");
+        t.equal(a.hostname, "example.com");
+        t.equal(a.port, "8080");
+        t.equal(a.pathname, "/p");
+        t.equal(a.search, "?q");
+        t.equal(a.hash, "#h");
+        t.equal(a.origin, "https://example.com:8080");
+        t.equal(a.text, "link");
+        a.hash = "z";
+        t.equal(a.getAttribute("href"), "https://u:p@example.com:8080/p?q#z");
+
+        const img = document.querySelector("img");
+        t.equal(img.src, "https://example.org/a.png");
+        t.equal(img.crossOrigin, "anonymous");
+        img.crossOrigin = null;
+        t.equal(img.hasAttribute("crossorigin"), false);
+        t.equal(img.crossOrigin, null);
+        t.end();
+    });
+
+    t.test("forms", (t) => {
+        const form = document.getElementById("f");
+        t.equal(form.elements.length, 3);
+        t.equal(form.length, 3);
+
+        const select = document.getElementById("s");
+        t.equal(select.type, "select-one");
+        t.equal(select.options.length, 2);
+        t.equal(select.value, "b");
+        t.equal(select.selectedIndex, 1);
+        select.value = "a";
+        t.equal(select.selectedIndex, 0);
+        t.equal(select.options[0].selected, true);
+        t.equal(select.options[1].selected, false);
+
+        t.equal(document.querySelector("textarea").value, "t");
+        t.end();
+    });
+
+    t.end();
+});
+
+test("document URL and baseURI", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><head><base href="/sub/"><base href="/other/"></head>` +
+            `<a href="x?y">link</a><img src="//cdn.example.org/i.png">` +
+            `<form></form><form action="go"></form>`,
+        "https://example.com/dir/page.html",
+    ).window;
+
+    t.equal(document.URL, "https://example.com/dir/page.html");
+    t.equal(document.documentURI, "https://example.com/dir/page.html");
+    t.equal(document.baseURI, "https://example.com/sub/");
+    t.equal(document.body.baseURI, "https://example.com/sub/");
+
+    t.equal(document.querySelector("a").href, "https://example.com/sub/x?y");
+    t.equal(document.querySelector("img").src, "https://cdn.example.org/i.png");
+
+    const [form, formWithAction] = document.querySelectorAll("form");
+    t.equal(form.action, "https://example.com/dir/page.html");
+    t.equal(formWithAction.action, "https://example.com/sub/go");
+    t.equal(document.querySelector("base").href, "https://example.com/sub/");
+
+    document.querySelector("base").remove();
+    t.equal(document.baseURI, "https://example.com/other/");
+    t.equal(document.querySelector("a").href, "https://example.com/other/x?y");
+
+    const a = document.createElement("a");
+    a.href = "z";
+    t.equal(a.href, "https://example.com/other/z");
+
+    const blank = new Html5EverDom(`<a href="x"></a>`).window.document;
+    t.equal(blank.URL, "about:blank");
+    t.equal(blank.baseURI, "about:blank");
+    t.equal(blank.querySelector("a").href, "x");
+
+    t.throws(() => new Html5EverDom("", "not a url"), TypeError);
+    t.end();
+});
+
+test("document metadata accessors", (t) => {
+    const { document } = new Html5EverDom(
+        `<!DOCTYPE html><html dir="RTL" lang="sv">` +
+            `<head><title>  Hello \n  world </title></head><!--c--><body>` +
+            `<form name="f"></form><form></form><img name="x">` +
+            `<a href="/a">a</a><a name="n">n</a><area href="/b">` +
+            `<script></script><embed><svg><title>svg</title></svg>` +
+            `<input name="x"></body></html>`,
+    ).window;
+
+    t.test("title", (t) => {
+        t.equal(document.title, "Hello world");
+        document.title = "New";
+        t.equal(document.title, "New");
+        t.equal(document.head.innerHTML, "<title>New</title>");
+
+        const { document: untitled } = new Html5EverDom("").window;
+        t.equal(untitled.title, "");
+        untitled.title = "Added";
+        t.equal(untitled.head.innerHTML, "<title>Added</title>");
+        untitled.head.remove();
+        untitled.title = "Nowhere";
+        t.equal(untitled.title, "");
+        t.end();
+    });
+
+    t.test("collections", (t) => {
+        t.equal(document.forms.length, 2);
+        t.equal(document.images.length, 1);
+        t.same(
+            [...document.links].map((link) => link.tagName),
+            ["A", "AREA"],
+        );
+        t.equal(document.anchors.length, 1);
+        t.equal(document.anchors[0].name, "n");
+        t.equal(document.scripts.length, 1);
+        t.equal(document.embeds.length, 1);
+
+        const named = document.getElementsByName("x");
+        t.ok(named instanceof NodeList);
+        t.same(
+            [...named].map((element) => element.tagName),
+            ["IMG", "INPUT"],
+        );
+        document.body.appendChild(document.createElement("img")).name = "x";
+        t.equal(named.length, 3);
+        t.equal(document.images.length, 2);
+        t.end();
+    });
+
+    t.test("dir, lang and activeElement", (t) => {
+        t.equal(document.dir, "rtl");
+        t.equal(document.lang, "sv");
+        document.dir = "ltr";
+        document.lang = "en";
+        t.equal(document.documentElement.getAttribute("dir"), "ltr");
+        t.equal(document.documentElement.getAttribute("lang"), "en");
+        t.equal(document.activeElement, null);
+        t.end();
+    });
+
+    t.test("head and body are found by name", (t) => {
+        t.equal(document.head.tagName, "HEAD");
+        t.equal(document.body.tagName, "BODY");
+
+        const { document: frames } = new Html5EverDom(
+            "<frameset></frameset>",
+        ).window;
+        t.equal(frames.body.tagName, "FRAMESET");
+
+        frames.head.remove();
+        t.equal(frames.head, null);
+        t.equal(frames.body.tagName, "FRAMESET");
+        t.end();
+    });
+
+    t.end();
+});
+
+test("createComment, createAttribute and DOMImplementation", (t) => {
+    const { document } = new Html5EverDom("<!DOCTYPE html><p></p>").window;
+
+    t.test("createComment()", (t) => {
+        const comment = document.createComment("hi");
+        t.ok(comment instanceof Comment);
+        t.equal(comment.data, "hi");
+        t.equal(comment.ownerDocument, document);
+        t.end();
+    });
+
+    t.test("createAttribute() and createAttributeNS()", (t) => {
+        const attribute = document.createAttribute("Data-X");
+        t.equal(attribute.name, "data-x");
+        t.equal(attribute.value, "");
+        t.equal(attribute.namespaceURI, null);
+        t.equal(attribute.ownerElement, null);
+
+        attribute.value = "1";
+        document.body.attributes.setNamedItem(attribute);
+        t.equal(document.body.getAttribute("data-x"), "1");
+        t.equal(attribute.ownerElement, document.body);
+
+        const href = document.createAttributeNS(
+            "http://www.w3.org/1999/xlink",
+            "xlink:href",
+        );
+        t.equal(href.name, "xlink:href");
+        t.equal(href.localName, "href");
+        t.equal(href.prefix, "xlink");
+        t.equal(href.namespaceURI, "http://www.w3.org/1999/xlink");
+
+        t.throws(() => document.createAttribute("a b"), {
+            name: "InvalidCharacterError",
+        });
+        t.throws(() => document.createAttributeNS(null, "x:y"), {
+            name: "NamespaceError",
+            code: 14,
+        });
+        t.throws(() => document.createAttributeNS("urn:x", "xmlns:y"), {
+            name: "NamespaceError",
+        });
+        t.end();
+    });
+
+    t.test("createHTMLDocument()", (t) => {
+        const { implementation } = document;
+        t.ok(implementation instanceof DOMImplementation);
+        t.equal(document.implementation, implementation);
+        t.throws(() => new DOMImplementation(), /Illegal constructor/);
+
+        const created = implementation.createHTMLDocument("Title");
+        t.equal(created.contentType, "text/html");
+        t.equal(created.URL, "about:blank");
+        t.equal(created.doctype.name, "html");
+        t.equal(created.title, "Title");
+        t.equal(
+            created.documentElement.outerHTML,
+            "<html><head><title>Title</title></head><body></body></html>",
+        );
+        t.equal(created.body.tagName, "BODY");
+        t.equal(created.createElement("div").ownerDocument, created);
+
+        const untitled = implementation.createHTMLDocument();
+        t.equal(untitled.head.childNodes.length, 0);
+        t.end();
+    });
+
+    t.test("createDocument() and createDocumentType()", (t) => {
+        const { implementation } = document;
+        const doctype = implementation.createDocumentType(
+            "svg",
+            "-//W3C//DTD SVG 1.1//EN",
+            "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd",
+        );
+        t.equal(doctype.nodeName, "svg");
+        t.equal(doctype.publicId, "-//W3C//DTD SVG 1.1//EN");
+        t.throws(() => implementation.createDocumentType("a b", "", ""), {
+            name: "InvalidCharacterError",
+        });
+
+        const svg = implementation.createDocument(
+            "http://www.w3.org/2000/svg",
+            "svg:svg",
+            doctype,
+        );
+        t.equal(svg.contentType, "image/svg+xml");
+        t.equal(svg.doctype, doctype);
+        t.equal(svg.documentElement.tagName, "svg:svg");
+        t.equal(svg.documentElement.localName, "svg");
+        t.equal(svg.documentElement.prefix, "svg");
+        t.equal(
+            svg.documentElement.namespaceURI,
+            "http://www.w3.org/2000/svg",
+        );
+
+        const empty = implementation.createDocument(null, "", null);
+        t.equal(empty.contentType, "application/xml");
+        t.equal(empty.childNodes.length, 0);
+        t.equal(empty.documentElement, null);
+        t.equal(empty.body, null);
+
+        // the document element is the first element child
+        const commented = new Html5EverDom(
+            "<!DOCTYPE html><!-- c --><html></html>",
+        ).window.document;
+        t.equal(commented.childNodes[1].nodeName, "#comment");
+        t.equal(commented.documentElement, commented.childNodes[2]);
+
+        const element = empty.createElement("Foo");
+        t.equal(element.tagName, "Foo");
+        t.equal(element.namespaceURI, null);
+        t.end();
+    });
+
+    t.end();
+});
+
+test("DOMParser and XMLSerializer", (t) => {
+    const { window } = new Html5EverDom("<p></p>");
+    t.equal(window.DOMParser, DOMParser);
+    t.equal(window.XMLSerializer, XMLSerializer);
+    t.notOk(Object.keys(window).includes("DOMParser"));
+
+    const parser = new window.DOMParser();
+    const serializer = new window.XMLSerializer();
+
+    t.test("parseFromString() with text/html", (t) => {
+        const document = parser.parseFromString(
+            "<title>Hi</title><p>a<br>b",
+            "text/html",
+        );
+        t.ok(document instanceof Document);
+        t.equal(document.contentType, "text/html");
+        t.equal(document.title, "Hi");
+        t.equal(document.defaultView, null);
+        t.equal(document.body.outerHTML, "<body><p>a<br>b</p></body>");
+        t.end();
+    });
+
+    t.test("parseFromString() with XML types", (t) => {
+        const document = parser.parseFromString(
+            '<root xmlns="urn:a" xmlns:b="urn:b"><b:c b:d="1"/><Up/></root>',
+            "application/xml",
+        );
+        t.equal(document.contentType, "application/xml");
+        t.equal(document.defaultView, null);
+
+        const root = document.documentElement;
+        t.equal(root.namespaceURI, "urn:a");
+        t.equal(root.firstChild.tagName, "b:c");
+        t.equal(root.firstChild.namespaceURI, "urn:b");
+        t.equal(root.firstChild.attributes[0].namespaceURI, "urn:b");
+        t.equal(root.lastChild.tagName, "Up");
+
+        const broken = parser.parseFromString("<a><b></a>", "text/xml");
+        t.equal(broken.documentElement.localName, "parsererror");
+        t.equal(broken.childNodes.length, 1);
+
+        t.throws(() => parser.parseFromString("", "text/plain"), TypeError);
+        t.end();
+    });
+
+    t.test("parseFromString() with processing instructions", (t) => {
+        const declared = parser.parseFromString(
+            '<?xml version="1.0" encoding="UTF-8"?>\n<root/>',
+            "application/xml",
+        );
+        t.equal(declared.childNodes.length, 1);
+        t.equal(declared.documentElement.localName, "root");
+
+        // there are no processing instruction nodes, so they are left out
+        const document = parser.parseFromString(
+            '<root><?pi data?><a/>text<?other?></root>',
+            "text/xml",
+        );
+        t.equal(
+            serializer.serializeToString(document),
+            "<root><a/>text</root>",
+        );
+        t.end();
+    });
+
+    t.test("parseFromString() with misnested HTML", (t) => {
+        const html = (markup) =>
+            parser.parseFromString(markup, "text/html").body.innerHTML;
+
+        t.equal(
+            html("<table><div>x</div><tr><td>1</td></tr></table>"),
+            "<div>x</div><table><tbody><tr><td>1</td></tr></tbody></table>",
+        );
+        t.equal(html("<b><p>x</b>y</p>"), "<b></b><p><b>x</b>y</p>");
+        t.equal(
+            html("<p><b><i>x</p>y"),
+            "<p><b><i>x</i></b></p><b><i>y</i></b>",
+        );
+
+        const document = parser.parseFromString(
+            "<html a=1><html b=2 a=3>",
+            "text/html",
+        );
+        t.equal(document.documentElement.getAttribute("a"), "1");
+        t.equal(document.documentElement.getAttribute("b"), "2");
+        t.end();
+    });
+
+    t.test("serializeToString()", (t) => {
+        const document = parser.parseFromString(
+            '<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd">' +
+                '<svg xmlns="http://www.w3.org/2000/svg"' +
+                ' xmlns:xlink="http://www.w3.org/1999/xlink">' +
+                '<use xlink:href="#a"/><text>a &amp; b</text></svg>',
+            "image/svg+xml",
+        );
+        t.equal(
+            serializer.serializeToString(document),
+            '<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "svg11.dtd">' +
+                '<svg xmlns="http://www.w3.org/2000/svg">' +
+                '<use xmlns:xlink="http://www.w3.org/1999/xlink"' +
+                ' xlink:href="#a"/><text>a &amp; b</text></svg>',
+        );
+
+        const html = parser.parseFromString(
+            '<p title="&quot;">a<br>b</p>',
+            "text/html",
+        );
+        t.equal(
+            serializer.serializeToString(html.body.firstChild),
+            '<p xmlns="http://www.w3.org/1999/xhtml" title="&quot;">' +
+                "a<br />b</p>",
+        );
+
+        const element = html.createElementNS("urn:x", "x:y");
+        const attribute = html.createAttributeNS("urn:z", "z");
+        attribute.value = "1";
+        element.attributes.setNamedItem(attribute);
+        t.equal(
+            serializer.serializeToString(element),
+            '<x:y xmlns:x="urn:x" xmlns:ns1="urn:z" ns1:z="1"/>',
+        );
+        t.end();
+    });
+
+    t.end();
+});
+
+test("Window globals and instanceof Node", (t) => {
+    const { window } = new Html5EverDom("<!DOCTYPE html><p>a<!--b--></p>");
+    const { document, Node } = window;
+    const p = document.querySelector("p");
+
+    t.equal(window.Element, Element);
+    t.equal(window.Document, Document);
+    t.equal(window.DocumentType, DocumentType);
+    t.notOk(Object.keys(window).includes("Node"));
+    t.throws(() => new Node(), /Illegal constructor/);
+
+    for (const node of [
+        document,
+        document.doctype,
+        p,
+        p.firstChild,
+        p.lastChild,
+        document.createDocumentFragment(),
+    ]) {
+        t.ok(node instanceof Node);
+    }
+    t.notOk(p.attributes instanceof Node);
+    t.ok(p instanceof window.HTMLElement);
+    t.ok(p instanceof window.HTMLParagraphElement);
+    t.ok(p.firstChild instanceof window.Text);
+    t.ok(p.lastChild instanceof window.Comment);
+    t.ok(document.createElementNS(null, "x") instanceof window.Element);
+    t.notOk(document.createElementNS(null, "x") instanceof window.HTMLElement);
+
+    t.equal(Node.ELEMENT_NODE, 1);
+    t.equal(Node.DOCUMENT_FRAGMENT_NODE, 11);
+    t.equal(Node.DOCUMENT_POSITION_CONTAINED_BY, 16);
+    t.equal(Node.prototype.COMMENT_NODE, 8);
+    t.equal(window.Element.TEXT_NODE, 3);
+    t.equal(window.HTMLElement.DOCUMENT_NODE, 9);
+    t.equal(p.nodeType, Node.ELEMENT_NODE);
+    t.end();
+});
@@ -1,8 +1,6 @@
 export type ChildNode = Comment | DocumentType | Element | Text;
 export type ParentNode = Document | DocumentFragment | Element;
-export type ClassList = import("./types/generated").ClassList & {
-    [index: string]: string;
-};
+export type Node = ChildNode | ParentNode;
 export type StyleDeclaration = import("./types/generated").StyleDeclaration & {
     [index: string]: string;
 };
@@ -11,3 +9,241 @@ export type InsertPosition =
     | "afterbegin"
     | "beforeend"
     | "afterend";
+// Methods taking any number of arguments, which are defined at runtime (see
+// main/src/variadic.rs) and merged into the generated classes here.
+interface ChildNodeMethods {
+    before(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    after(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    replaceWith(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+}
+interface ParentNodeMethods {
+    prepend(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    append(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+    replaceChildren(...nodes: Array<ChildNode | DocumentFragment | string>): void;
+}
+export interface Comment extends ChildNodeMethods {}
+export interface DocumentType extends ChildNodeMethods {}
+export interface Element extends ChildNodeMethods, ParentNodeMethods {}
+export interface Text extends ChildNodeMethods {}
+export interface Document extends ParentNodeMethods {}
+export interface DocumentFragment extends ParentNodeMethods {}
+// Index access and the iteration methods shared with arrays, which are added
+// at runtime (see main/src/collections).
+export interface NodeList {
+    readonly [index: number]: ChildNode;
+    forEach(
+        callback: (value: ChildNode, key: number, parent: NodeList) => void,
+        thisArg?: any,
+    ): void;
+    entries(): IterableIterator<[number, ChildNode]>;
+    keys(): IterableIterator<number>;
+    values(): IterableIterator<ChildNode>;
+    [Symbol.iterator](): IterableIterator<ChildNode>;
+}
+export interface HTMLCollection {
+    readonly [index: number]: Element;
+    [Symbol.iterator](): IterableIterator<Element>;
+}
+export interface NamedNodeMap {
+    readonly [index: number]: Attr;
+    [Symbol.iterator](): IterableIterator<Attr>;
+}
+export interface DOMTokenList {
+    readonly [index: number]: string;
+    forEach(
+        callback: (value: string, key: number, parent: DOMTokenList) => void,
+        thisArg?: any,
+    ): void;
+    entries(): IterableIterator<[number, string]>;
+    keys(): IterableIterator<number>;
+    values(): IterableIterator<string>;
+    [Symbol.iterator](): IterableIterator<string>;
+}
+export interface DOMStringMap {
+    [name: string]: string | undefined;
+}
+// The interfaces of HTML elements, which are prototypes set at runtime (see
+// main/src/html_elements) rather than generated classes.
+export interface HTMLElement extends Element {
+    title: string;
+    lang: string;
+    dir: string;
+    hidden: boolean;
+    inert: boolean;
+    accessKey: string;
+    autofocus: boolean;
+    nonce: string;
+    tabIndex: number;
+}
+interface HTMLHyperlinkElementUtils {
+    href: string;
+    readonly origin: string;
+    protocol: string;
+    username: string;
+    password: string;
+    host: string;
+    hostname: string;
+    port: string;
+    pathname: string;
+    search: string;
+    hash: string;
+}
+export interface HTMLAnchorElement
+    extends HTMLElement,
+        HTMLHyperlinkElementUtils {
+    target: string;
+    download: string;
+    ping: string;
+    rel: string;
+    referrerPolicy: string;
+    hreflang: string;
+    type: string;
+    name: string;
+    text: string;
+}
+export interface HTMLFormElement extends HTMLElement {
+    acceptCharset: string;
+    action: string;
+    autocomplete: string;
+    enctype: string;
+    encoding: string;
+    method: string;
+    name: string;
+    noValidate: boolean;
+    target: string;
+    rel: string;
+    readonly elements: HTMLCollection;
+    readonly length: number;
+}
+export interface HTMLImageElement extends HTMLElement {
+    name: string;
+    alt: string;
+    src: string;
+    srcset: string;
+    sizes: string;
+    crossOrigin: string | null;
+    useMap: string;
+    isMap: boolean;
+    width: number;
+    height: number;
+    referrerPolicy: string;
+    decoding: string;
+    loading: string;
+}
+export interface HTMLInputElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    accept: string;
+    alt: string;
+    autocomplete: string;
+    defaultChecked: boolean;
+    checked: boolean;
+    dirName: string;
+    formAction: string;
+    formNoValidate: boolean;
+    formTarget: string;
+    max: string;
+    maxLength: number;
+    min: string;
+    minLength: number;
+    multiple: boolean;
+    pattern: string;
+    placeholder: string;
+    readOnly: boolean;
+    required: boolean;
+    size: number;
+    src: string;
+    step: string;
+    type: string;
+    defaultValue: string;
+    value: string;
+}
+export interface HTMLOptionElement extends HTMLElement {
+    disabled: boolean;
+    label: string;
+    defaultSelected: boolean;
+    selected: boolean;
+    value: string;
+    text: string;
+}
+export interface HTMLSelectElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    autocomplete: string;
+    multiple: boolean;
+    required: boolean;
+    size: number;
+    readonly type: string;
+    readonly options: HTMLCollection;
+    readonly length: number;
+    readonly selectedIndex: number;
+    value: string;
+}
+export interface HTMLTableCellElement extends HTMLElement {
+    colSpan: number;
+    rowSpan: number;
+    headers: string;
+    scope: string;
+    abbr: string;
+}
+export interface HTMLTextAreaElement extends HTMLElement {
+    disabled: boolean;
+    name: string;
+    autocomplete: string;
+    cols: number;
+    rows: number;
+    dirName: string;
+    maxLength: number;
+    minLength: number;
+    placeholder: string;
+    readOnly: boolean;
+    required: boolean;
+    wrap: string;
+    defaultValue: string;
+    value: string;
+}
+// The interfaces that only exist at runtime, which cannot be constructed (see
+// main/src/node_wrappers/interface.rs and main/src/html_elements).
+interface Interface<T> extends Function {
+    readonly prototype: T;
+}
+export interface NodeInterface extends Interface<Node> {
+    readonly ELEMENT_NODE: 1;
+    readonly ATTRIBUTE_NODE: 2;
+    readonly TEXT_NODE: 3;
+    readonly CDATA_SECTION_NODE: 4;
+    readonly PROCESSING_INSTRUCTION_NODE: 7;
+    readonly COMMENT_NODE: 8;
+    readonly DOCUMENT_NODE: 9;
+    readonly DOCUMENT_TYPE_NODE: 10;
+    readonly DOCUMENT_FRAGMENT_NODE: 11;
+    readonly DOCUMENT_POSITION_DISCONNECTED: 0x01;
+    readonly DOCUMENT_POSITION_PRECEDING: 0x02;
+    readonly DOCUMENT_POSITION_FOLLOWING: 0x04;
+    readonly DOCUMENT_POSITION_CONTAINS: 0x08;
+    readonly DOCUMENT_POSITION_CONTAINED_BY: 0x10;
+    readonly DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC: 0x20;
+}
+// Classes that are globals in browsers, which are defined on every window at
+// runtime (see main/src/nodes/window.rs).
+export interface Window {
+    Node: NodeInterface;
+    Comment: typeof Comment;
+    Document: typeof Document;
+    DocumentFragment: typeof DocumentFragment;
+    DocumentType: typeof DocumentType;
+    Element: typeof Element;
+    Text: typeof Text;
+    DOMParser: typeof DOMParser;
+    XMLSerializer: typeof XMLSerializer;
+    HTMLElement: Interface<HTMLElement>;
+    HTMLAnchorElement: Interface<HTMLAnchorElement>;
+    HTMLFormElement: Interface<HTMLFormElement>;
+    HTMLImageElement: Interface<HTMLImageElement>;
+    HTMLInputElement: Interface<HTMLInputElement>;
+    HTMLOptionElement: Interface<HTMLOptionElement>;
+    HTMLSelectElement: Interface<HTMLSelectElement>;
+    HTMLTableCellElement: Interface<HTMLTableCellElement>;
+    HTMLTextAreaElement: Interface<HTMLTextAreaElement>;
+    readonly [name: `HTML${string}Element`]: Interface<HTMLElement>;
+}
@@ -3,23 +3,125 @@ export const enum QuirksMode {
   LimitedQuirks = 1,
   NoQuirks = 2
 }
+/** A single selector of a selector list passed to `parseSelector`. */
+export interface SelectorInfo {
+  /** The selector serialized in its canonical form. */
+  text: string
+  /** `[ids, classes, types]` */
+  specificity: Array<number>
+}
+export interface SelectorParseError {
+  message: string
+  /** 1-based line of the error within the selector text. */
+  line: number
+  /** 1-based column of the error within the selector text. */
+  column: number
+}
+export interface SelectorParseResult {
+  selectors: Array<SelectorInfo>
+  error?: SelectorParseError
+}
+/**
+ * Parses a selector list without matching anything, returning the
+ * specificity and canonical serialization of each selector or where parsing
+ * failed.
+ */
+export function parseSelector(selectors: string): SelectorParseResult
+export function compileSelector(selectors: string): CompiledSelector
+export type HtmlCollection = HTMLCollection
 export type Html5everDom = Html5EverDom
+export type DomParser = DOMParser
+export type DomImplementation = DOMImplementation
+export type DomStringMap = DOMStringMap
+export type DomTokenList = DOMTokenList
+export type XmlSerializer = XMLSerializer
+/**
+ * A collection of elements, as returned by `children`,
+ * `getElementsByTagName()` and `getElementsByClassName()`.
+ *
+ * The collection is live: it searches the tree below its root again once
+ * the tree changes.
+ */
+export class HTMLCollection {
+  /** Collections are only created by the tree, like in browsers. */
+  constructor()
+  get length(): number
+  item(index: number): Element | null
+  /** The first element with `name` as its `id` or `name` attribute. */
+  namedItem(name: string): Element | null
+}
+/**
+ * The attributes of an element, as returned by `attributes`.
+ *
+ * The map is live: it reads the attributes of its element on every access,
+ * and its `Attr`s, one per attribute, write their value through to the
+ * element.
+ */
+export class NamedNodeMap {
+  /** Maps are only created by the tree, like in browsers. */
+  constructor()
+  get length(): number
+  item(index: number): Attr | null
+  getNamedItem(qualifiedName: string): Attr | null
+  getNamedItemNS(namespace: string | undefined | null, localName: string): Attr | null
+  /**
+   * Puts `attr` on the element, replacing the attribute with the same
+   * namespace and local name, whose `Attr` is returned.
+   */
+  setNamedItem(attr: Attr): Attr | null
+  setNamedItemNS(attr: Attr): Attr | null
+  removeNamedItem(qualifiedName: string): Attr
+  removeNamedItemNS(namespace: string | undefined | null, localName: string): Attr
+}
+/**
+ * The child nodes of a node, as returned by `childNodes`, or the elements
+ * below it that match a filter, as returned by `getElementsByName()`.
+ *
+ * The list is live: it reads the child nodes on every access, and searches
+ * the tree below its parent again once the tree changes.
+ */
+export class NodeList {
+  /** Collections are only created by the tree, like in browsers. */
+  constructor()
+  get length(): number
+  item(index: number): ChildNode | null
+}
 export class Html5EverDom {
   errors: Array<string>
-  constructor(html?: string | undefined | null)
+  /**
+   * Parses `html` into a document with the URL `url`, `about:blank` by
+   * default, which relative URLs in it resolve against.
+   */
+  constructor(html?: string | undefined | null, url?: string | undefined | null)
   static createDocumentFragment(html: string, maybeQuirksMode?: QuirksMode | undefined | null): DocumentFragment
   get window(): Window
   get quirksMode(): QuirksMode
   serialize(): string
 }
+/** See https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#domparser */
+export class DOMParser {
+  constructor()
+  /**
+   * Parses `string` as HTML for `text/html` and as XML for the XML types,
+   * into a document without a window.
+   */
+  parseFromString(string: string, r#type: string): Document
+}
 export class Comment {
   data: string
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
   get parentElement(): Element | null
   get parentNode(): ParentNode | null
-  get ownerDocument(): Document | null
   remove(): void
   get previousSibling(): ChildNode | null
   get previousElementSibling(): Element | null
@@ -30,6 +132,12 @@ export class Comment {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
@@ -37,22 +145,60 @@ export class Comment {
   get textContent(): string | null
   cloneNode(): this
 }
+/**
+ * Creates documents and doctypes without parsing, as returned by
+ * `document.implementation`, see
+ * https://dom.spec.whatwg.org/#interface-domimplementation
+ */
+export class DOMImplementation {
+  /**
+   * Implementations are only created by their document, like in
+   * browsers.
+   */
+  constructor()
+  /** See https://dom.spec.whatwg.org/#dom-domimplementation-createdocumenttype */
+  createDocumentType(name: string, publicId: string, systemId: string): DocumentType
+  /**
+   * An XML document with a document element named `qualified_name`,
+   * unless it is empty, see
+   * https://dom.spec.whatwg.org/#dom-domimplementation-createdocument
+   */
+  createDocument(namespace?: string | undefined | null, qualifiedName?: string | undefined | null, doctype?: DocumentType | undefined | null): Document
+  /**
+   * An HTML document with a doctype, a head with a `title` if there is
+   * one, and a body, see
+   * https://dom.spec.whatwg.org/#dom-domimplementation-createhtmldocument
+   */
+  createHTMLDocument(title?: string | undefined | null): Document
+  /**
+   * Always `true`, like in browsers, see
+   * https://dom.spec.whatwg.org/#dom-domimplementation-hasfeature
+   */
+  hasFeature(): boolean
+}
 export class Document {
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
-  get childNodes(): Array<ChildNode>
-  get children(): Array<Element>
-  append(childNodeOrText: ChildNode | string): void
-  prepend(childNodeOrText: ChildNode | string): void
-  appendChild<T extends ChildNode>(child: T): T
-  insertBefore<T extends ChildNode>(new_node: T, reference_node: ChildNode): T
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
+  get childNodes(): NodeList
+  get children(): HTMLCollection
+  appendChild<T extends ChildNode | DocumentFragment>(child: T): T
+  insertBefore<T extends ChildNode | DocumentFragment>(new_node: T, reference_node: ChildNode): T
+  replaceChild<T extends ChildNode>(node: ChildNode | DocumentFragment, child: T): T
   removeChild<T extends ChildNode>(child: T): T
   getElementById(id: string): Element | null
-  getElementsByClassName(className: string): Array<Element>
-  getElementsByTagName(qualifiedName: string): Array<Element>
-  querySelector(selectors: string): Element | null
-  querySelectorAll(selectors: string): Array<Element>
+  getElementsByClassName(className: string): HTMLCollection
+  getElementsByTagName(qualifiedName: string): HTMLCollection
+  querySelector(selectors: string | CompiledSelector): Element | null
+  querySelectorAll(selectors: string | CompiledSelector): Array<Element>
   get firstChild(): ChildNode | null
   get firstElementChild(): Element | null
   get lastChild(): ChildNode | null
@@ -63,36 +209,103 @@ export class Document {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
   get TEXT_NODE(): number
   get doctype(): DocumentType | null
-  get documentElement(): Element
+  /**
+   * The first element child, if any, see
+   * https://dom.spec.whatwg.org/#dom-document-documentelement
+   */
+  get documentElement(): Element | null
+  get contentType(): string
+  /**
+   * The same object on every access, see
+   * https://dom.spec.whatwg.org/#dom-document-implementation
+   */
+  get implementation(): DOMImplementation
+  get URL(): string
+  get documentURI(): string
   get defaultView(): Window | null
-  get head(): Element
-  get body(): Element
+  /** See https://html.spec.whatwg.org/#dom-document-head */
+  get head(): Element | null
+  /** See https://html.spec.whatwg.org/#dom-document-body */
+  get body(): Element | null
+  /** The text of the `title` element, with its whitespace collapsed. */
+  get title(): string
+  /**
+   * Replaces the text of the `title` element, which is added to the head
+   * if there is none. Does nothing without a head, see
+   * https://html.spec.whatwg.org/#document.title
+   */
+  set title(title: string)
+  /**
+   * The `dir` of the `html` element, see
+   * https://html.spec.whatwg.org/#dom-document-dir
+   */
+  get dir(): string
+  set dir(dir: string)
+  /** The `lang` of the `html` element. */
+  get lang(): string
+  set lang(lang: string)
+  /** Always `null`, as nothing is ever focused. */
+  get activeElement(): Element | null
+  get forms(): HTMLCollection
+  get images(): HTMLCollection
+  get embeds(): HTMLCollection
+  get scripts(): HTMLCollection
+  get links(): HTMLCollection
+  get anchors(): HTMLCollection
+  /** The HTML elements with `name` as their `name` attribute. */
+  getElementsByName(name: string): NodeList
   get textContent(): string | null
+  /** See https://dom.spec.whatwg.org/#dom-document-createelement */
   createElement(name: string): Element
+  /** See https://dom.spec.whatwg.org/#dom-document-createelementns */
+  createElementNS(namespace: string | undefined | null, qualifiedName: string): Element
+  createComment(data: string): Comment
+  /**
+   * An attribute that is not on an element yet, see
+   * https://dom.spec.whatwg.org/#dom-document-createattribute
+   */
+  createAttribute(localName: string): Attr
+  createAttributeNS(namespace: string | undefined | null, qualifiedName: string): Attr
   createTextNode(data: string): Text
   createDocumentFragment(html?: string | undefined | null): DocumentFragment
+  cloneNode(deep?: boolean | undefined | null): this
+  importNode(node: ChildNode | DocumentFragment, deep?: boolean): Node
+  adoptNode<T extends ChildNode | DocumentFragment>(node: T): T
 }
 export class DocumentFragment {
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
-  get childNodes(): Array<ChildNode>
-  get children(): Array<Element>
-  append(childNodeOrText: ChildNode | string): void
-  prepend(childNodeOrText: ChildNode | string): void
-  appendChild<T extends ChildNode>(child: T): T
-  insertBefore<T extends ChildNode>(new_node: T, reference_node: ChildNode): T
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
+  get childNodes(): NodeList
+  get children(): HTMLCollection
+  appendChild<T extends ChildNode | DocumentFragment>(child: T): T
+  insertBefore<T extends ChildNode | DocumentFragment>(new_node: T, reference_node: ChildNode): T
+  replaceChild<T extends ChildNode>(node: ChildNode | DocumentFragment, child: T): T
   removeChild<T extends ChildNode>(child: T): T
   getElementById(id: string): Element | null
-  getElementsByClassName(className: string): Array<Element>
-  getElementsByTagName(qualifiedName: string): Array<Element>
-  querySelector(selectors: string): Element | null
-  querySelectorAll(selectors: string): Array<Element>
+  getElementsByClassName(className: string): HTMLCollection
+  getElementsByTagName(qualifiedName: string): HTMLCollection
+  querySelector(selectors: string | CompiledSelector): Element | null
+  querySelectorAll(selectors: string | CompiledSelector): Array<Element>
   get firstChild(): ChildNode | null
   get firstElementChild(): Element | null
   get lastChild(): ChildNode | null
@@ -103,11 +316,18 @@ export class DocumentFragment {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
   get TEXT_NODE(): number
   get textContent(): string | null
+  cloneNode(deep?: boolean | undefined | null): this
 }
 export class DocumentType {
   name: string
@@ -116,9 +336,16 @@ export class DocumentType {
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
   get parentElement(): Element | null
   get parentNode(): ParentNode | null
-  get ownerDocument(): Document | null
   remove(): void
   get previousSibling(): ChildNode | null
   get previousElementSibling(): Element | null
@@ -129,6 +356,12 @@ export class DocumentType {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
@@ -138,21 +371,58 @@ export class DocumentType {
 }
 export class Attr {
   get localName(): string
+  /** The qualified name, with the prefix if there is one. */
   get name(): string
-  get namespaceUri(): string
+  get namespaceURI(): string | null
   get ownerDocument(): Document | null
-  get ownerElement(): Element
+  get ownerElement(): Element | null
+  /**
+   * The prefix of an attribute created in a namespace. Attributes parsed
+   * from HTML have none, but a colon in their local name still reads as
+   * one, as it always has.
+   */
   get prefix(): string | null
   get value(): string
+  set value(value: string)
+}
+/**
+ * The `data-*` attributes of an element by their camelCase names, as
+ * returned by `dataset`, see
+ * https://html.spec.whatwg.org/#domstringmap
+ */
+export class DOMStringMap {
+  /** Maps are only created by the tree, like in browsers. */
+  constructor()
 }
-export class ClassList {
+/**
+ * The tokens of an attribute of an element, like `class` for `classList`
+ * and `rel` for `relList`, see
+ * https://dom.spec.whatwg.org/#interface-domtokenlist
+ */
+export class DOMTokenList {
+  /** Lists are only created by their element, like in browsers. */
+  constructor()
   item(index: number): string | null
   add(token1?: string | undefined | null, token2?: string | undefined | null, token3?: string | undefined | null, token4?: string | undefined | null, token5?: string | undefined | null): void
   remove(token1?: string | undefined | null, token2?: string | undefined | null, token3?: string | undefined | null, token4?: string | undefined | null, token5?: string | undefined | null): void
+  /**
+   * Adds `token` if it is missing and removes it otherwise, unless
+   * `force` says which to do. Returns whether it is there after.
+   */
   toggle(token: string, force?: boolean | undefined | null): boolean
+  /**
+   * Puts `new_token` where `token` is, unless `token` is missing. Returns
+   * whether it was replaced.
+   */
+  replace(token: string, newToken: string): boolean
   contains(token: string): boolean
   get length(): number
   get value(): string
+  /**
+   * Whether `token` is one of the tokens the attribute supports. Throws a
+   * `TypeError` for attributes without supported tokens, like `class`.
+   */
+  supports(token: string): boolean
   toString(): string
   set value(value: string)
 }
@@ -160,26 +430,32 @@ export class Element {
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
   get parentElement(): Element | null
   get parentNode(): ParentNode | null
-  get ownerDocument(): Document | null
   remove(): void
   get previousSibling(): ChildNode | null
   get previousElementSibling(): Element | null
   get nextSibling(): ChildNode | null
   get nextElementSibling(): Element | null
-  get childNodes(): Array<ChildNode>
-  get children(): Array<Element>
-  append(childNodeOrText: ChildNode | string): void
-  prepend(childNodeOrText: ChildNode | string): void
-  appendChild<T extends ChildNode>(child: T): T
-  insertBefore<T extends ChildNode>(new_node: T, reference_node: ChildNode): T
+  get childNodes(): NodeList
+  get children(): HTMLCollection
+  appendChild<T extends ChildNode | DocumentFragment>(child: T): T
+  insertBefore<T extends ChildNode | DocumentFragment>(new_node: T, reference_node: ChildNode): T
+  replaceChild<T extends ChildNode>(node: ChildNode | DocumentFragment, child: T): T
   removeChild<T extends ChildNode>(child: T): T
   getElementById(id: string): Element | null
-  getElementsByClassName(className: string): Array<Element>
-  getElementsByTagName(qualifiedName: string): Array<Element>
-  querySelector(selectors: string): Element | null
-  querySelectorAll(selectors: string): Array<Element>
+  getElementsByClassName(className: string): HTMLCollection
+  getElementsByTagName(qualifiedName: string): HTMLCollection
+  querySelector(selectors: string | CompiledSelector): Element | null
+  querySelectorAll(selectors: string | CompiledSelector): Array<Element>
   get firstChild(): ChildNode | null
   get firstElementChild(): Element | null
   get lastChild(): ChildNode | null
@@ -190,18 +466,41 @@ export class Element {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
   get TEXT_NODE(): number
-  get attributes(): Array<Attr>
+  get content(): DocumentFragment | null
+  get attributes(): NamedNodeMap
   getAttribute(name: string): string | null
   removeAttribute(name: string): void
   setAttribute(name: string, value: string): void
   hasAttribute(name: string): boolean
-  get classList(): ClassList
+  /**
+   * Adds the attribute `name` if it is missing and removes it otherwise,
+   * unless `force` says which to do. Returns whether it is there after.
+   */
+  toggleAttribute(name: string, force?: boolean | undefined | null): boolean
+  getAttributeNames(): Array<string>
+  hasAttributes(): boolean
+  get dataset(): DOMStringMap
+  get classList(): DOMTokenList
+  /** The link types of `a`, `area`, `form` and `link` elements. */
+  get relList(): DOMTokenList | null
+  /** The restrictions lifted for the content of `iframe` elements. */
+  get sandbox(): DOMTokenList | null
+  get part(): DOMTokenList
   get style(): StyleDeclaration
   get tagName(): string
+  get namespaceURI(): string | null
+  get prefix(): string | null
+  get localName(): string
   get innerHTML(): string
   set innerHTML(html: string)
   get outerHTML(): string
@@ -215,16 +514,23 @@ export class Element {
   insertAdjacentElement(position: InsertPosition, element: Element): Element
   insertAdjacentHTML(position: InsertPosition, html: string): void
   insertAdjacentText(position: InsertPosition, text: string): void
-  matches(selectors: string): boolean
+  matches(selectors: string | CompiledSelector): boolean
 }
 export class Text {
   data: string
   get nodeName(): string
   get nodeType(): number
   get nodeValue(): string | null
+  get ownerDocument(): Document | null
+  get baseURI(): string
+  get isConnected(): boolean
+  getRootNode(): Node
+  contains(other?: Node | undefined | null): boolean
+  compareDocumentPosition(other: Node): number
+  isEqualNode(other?: Node | undefined | null): boolean
+  isSameNode(other?: Node | undefined | null): boolean
   get parentElement(): Element | null
   get parentNode(): ParentNode | null
-  get ownerDocument(): Document | null
   remove(): void
   get previousSibling(): ChildNode | null
   get previousElementSibling(): Element | null
@@ -235,6 +541,12 @@ export class Text {
   get COMMENT_NODE(): number
   get DOCUMENT_FRAGMENT_NODE(): number
   get DOCUMENT_NODE(): number
+  get DOCUMENT_POSITION_CONTAINED_BY(): number
+  get DOCUMENT_POSITION_CONTAINS(): number
+  get DOCUMENT_POSITION_DISCONNECTED(): number
+  get DOCUMENT_POSITION_FOLLOWING(): number
+  get DOCUMENT_POSITION_IMPLEMENTATION_SPECIFIC(): number
+  get DOCUMENT_POSITION_PRECEDING(): number
   get DOCUMENT_TYPE_NODE(): number
   get ELEMENT_NODE(): number
   get PROCESSING_INSTRUCTION_NODE(): number
@@ -245,6 +557,13 @@ export class Text {
 export class Window {
   get document(): Document
 }
+/**
+ * A selector list that has been parsed once and can be passed to `matches`,
+ * `querySelector` and `querySelectorAll` instead of a string.
+ */
+export class CompiledSelector {
+  get selectorText(): string
+}
 export class StyleDeclaration {
   get accentColor(): string
   set accentColor(value?: string | undefined | null)
@@ -1165,3 +1484,8 @@ export class StyleDeclaration {
   item(index: number): string | null
   get length(): number
 }
+/** See https://w3c.github.io/DOM-Parsing/#the-xmlserializer-interface */
+export class XMLSerializer {
+  constructor()
+  serializeToString(node: Node): string
+}